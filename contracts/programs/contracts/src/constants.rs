@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
-/// ZK Circuit Verifier Program IDs (deployed on Solana devnet)
-/// Each circuit has its own verifier program
+// ZK Circuit Verifier Program IDs (deployed on Solana devnet)
+// Each circuit has its own verifier program
 
 /// Deck Circuit Verifier - for hole card commitments
 pub const DECK_VERIFIER_PROGRAM_ID: Pubkey = pubkey!("5mWDL7NZwacC8fxVouwEwUgvJGQMpcaAfjmyMNkwzWEd");
@@ -15,14 +15,16 @@ pub const REVEAL_VERIFIER_PROGRAM_ID: Pubkey = pubkey!("9Yp14dZ4ZVY9ckWn5tzyEaym
 /// Showdown Circuit Verifier - for hand reveals at showdown
 pub const SHOWDOWN_VERIFIER_PROGRAM_ID: Pubkey = pubkey!("7urWEDFxTrKSE6X6zGdd9wgkCEieAWHXSCxEd8zxcTgh");
 
-/// Bet Circuit Verifier - for bet/balance verification
-pub const BET_VERIFIER_PROGRAM_ID: Pubkey = pubkey!("6kucgYYg8q9PVWTxvzH1sA9vgg5onhmSYUcuMD3zkwai");
-
 /// PDA Seeds
 pub const GLOBAL_SEED: &[u8] = b"global";
 pub const TABLE_SEED: &[u8] = b"table";
 pub const HAND_SEED: &[u8] = b"hand";
 pub const VAULT_SEED: &[u8] = b"vault";
+pub const TABLE_STATS_SEED: &[u8] = b"table_stats";
+pub const PROFILE_SEED: &[u8] = b"profile";
+
+/// Domain tag hashed with the deck seed to draw a random opening button
+pub const OPENING_BUTTON_DOMAIN: &[u8] = b"opening_button";
 
 /// Default action timeout (seconds)
 pub const DEFAULT_ACTION_TIMEOUT: i64 = 30;
@@ -33,18 +35,93 @@ pub const MIN_ACTION_TIMEOUT: i64 = 10;
 /// Maximum action timeout (seconds)
 pub const MAX_ACTION_TIMEOUT: i64 = 120;
 
+/// Minimum card commit deadline (seconds): generating and uploading a
+/// multi-chunk Groth16 proof does not fit in the shortest action timeout
+pub const MIN_PROOF_TIMEOUT: i64 = 60;
+
+/// Maximum card commit deadline (seconds), leaving room for client-side
+/// proof generation
+pub const MAX_COMMIT_TIMEOUT: i64 = 300;
+
+/// Default per-seat time bank (seconds)
+pub const DEFAULT_TIME_BANK: i64 = 60;
+
+/// Maximum per-seat time bank (seconds)
+pub const MAX_TIME_BANK: i64 = 600;
+
+/// Maximum showdown dispute window (seconds)
+pub const MAX_DISPUTE_WINDOW: i64 = 3600;
+
+/// Maximum wait before a player who left may sit back down (seconds)
+pub const MAX_REJOIN_COOLDOWN: i64 = 86400;
+
+/// Maximum rake in basis points (10%)
+pub const MAX_RAKE_BPS: u16 = 1000;
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Maximum number of levels in a table's blind schedule
+pub const MAX_BLIND_LEVELS: usize = 8;
+
+/// Allowed big blind to small blind ratio (inclusive)
+pub const MIN_BLIND_RATIO: u64 = 2;
+pub const MAX_BLIND_RATIO: u64 = 4;
+
+/// Maximum number of players on a private table's allowlist
+pub const MAX_ALLOWLIST: usize = 4;
+
 /// Groth16 proof size (bytes)
 pub const PROOF_SIZE: usize = 388;
 
+/// Maximum proof buffer size (bytes), comfortably above proof + public witness
+pub const MAX_PROOF_BUFFER_SIZE: u16 = 2048;
+
+/// Proof buffers a seat may hold open at once for a single hand
+pub const MAX_OPEN_BUFFERS: u8 = 3;
+
+/// Maximum bytes per `upload_proof_chunk`, keeping the transaction within size limits
+pub const MAX_CHUNK_SIZE: usize = 900;
+
+/// Public witness header size (nbPublic, nbSecret, vector length as u32 each)
+pub const PUBLIC_WITNESS_HEADER_SIZE: usize = 12;
+
+/// Serialized field element size in the public witness (big-endian)
+pub const FIELD_ELEMENT_SIZE: usize = 32;
+
+/// Public witness elements of the DECK circuit:
+/// hand_id, deck_root, positions[2], commitments[2] (output)
+pub const DECK_PUBLIC_INPUT_LEN: usize = 6;
+
+/// Public witness elements of the DEAL circuit:
+/// hand_id, deck_seed[32], deck_root (output)
+pub const DEAL_PUBLIC_INPUT_LEN: usize = 34;
+
+/// Public witness elements of the REVEAL circuit:
+/// hand_id, deck_seed[32], cards[5], num_cards, offset, deck commitment (output)
+pub const REVEAL_PUBLIC_INPUT_LEN: usize = 41;
+
+/// Public witness elements of the SHOWDOWN circuit:
+/// hand_id, commitments[2], community_cards[5], rank (output)
+pub const SHOWDOWN_PUBLIC_INPUT_LEN: usize = 9;
+
 /// Card commitment size (bytes)
 pub const COMMITMENT_SIZE: usize = 32;
 
 /// Number of cards in deck
 pub const DECK_SIZE: u8 = 52;
 
+/// Number of cards in a short deck (sixes through aces)
+pub const SHORT_DECK_SIZE: u8 = 36;
+
 /// Number of hole cards per player
 pub const HOLE_CARDS: u8 = 2;
 
+/// Legal range of a showdown hand rank: category * 16^5 followed by the five
+/// card values (4 bits each), from 7-5-4-3-2 high up to a royal flush
+pub const MIN_HAND_RANK: u64 = 0x05_3210;
+pub const MAX_HAND_RANK: u64 = 0x9C_BA98;
+
 /// Number of community cards
 pub const COMMUNITY_CARDS: u8 = 5;
 
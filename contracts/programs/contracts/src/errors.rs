@@ -26,6 +26,45 @@ pub enum ZkPokerError {
     #[msg("Invalid table configuration")]
     InvalidTableConfig,
 
+    #[msg("Both players must ready up before the next hand")]
+    PlayersNotReady,
+
+    #[msg("Table does not use auto-continue")]
+    AutoContinueDisabled,
+
+    #[msg("Table reached its hand limit and is closing")]
+    TableClosing,
+
+    #[msg("Table still has seated players or chips in the vault")]
+    TableNotEmpty,
+
+    #[msg("Player is not on this table's allowlist")]
+    NotAllowlisted,
+
+    #[msg("Table allowlist is full")]
+    AllowlistFull,
+
+    #[msg("Maximum number of active tables reached")]
+    TableLimitReached,
+
+    #[msg("Stack does not exceed the maximum buy-in")]
+    NoExcessChips,
+
+    #[msg("Cannot leave with winnings before the table's minimum hands")]
+    HitAndRunBlocked,
+
+    #[msg("Vault holds nothing beyond stacks and rake")]
+    NoVaultDust,
+
+    #[msg("Left this table too recently to rejoin")]
+    RejoinCooldown,
+
+    #[msg("Token account balance does not cover the amount")]
+    InsufficientTokenBalance,
+
+    #[msg("This table splits hand rent: the other seated player must co-sign")]
+    CoPayerRequired,
+
     // ============================================
     // Hand Errors (6100-6199)
     // ============================================
@@ -44,6 +83,9 @@ pub enum ZkPokerError {
     #[msg("Invalid seed reveal - hash mismatch")]
     InvalidSeedReveal,
 
+    #[msg("Seed matches the opponent's")]
+    DuplicateSeedCommitment,
+
     #[msg("Cards already committed")]
     CardsAlreadyCommitted,
 
@@ -56,6 +98,12 @@ pub enum ZkPokerError {
     #[msg("Hand already complete")]
     HandAlreadyComplete,
 
+    #[msg("Player is not playing in this hand")]
+    PlayerNotInHand,
+
+    #[msg("Hand is not complete")]
+    HandNotComplete,
+
     // ============================================
     // Betting Errors (6200-6299)
     // ============================================
@@ -80,6 +128,24 @@ pub enum ZkPokerError {
     #[msg("Bet must be at least big blind")]
     BetTooSmall,
 
+    #[msg("Nothing to call - check instead")]
+    NothingToCall,
+
+    #[msg("Table does not allow straddles")]
+    StraddleNotAllowed,
+
+    #[msg("Straddle already posted")]
+    AlreadyStraddled,
+
+    #[msg("Betting is closed - an all-in was called")]
+    BettingClosed,
+
+    #[msg("Acting too fast - wait for the table's minimum action interval")]
+    ActingTooFast,
+
+    #[msg("Cannot raise - action has not been reopened to this seat")]
+    CannotRaise,
+
     // ============================================
     // ZK Errors (6300-6399)
     // ============================================
@@ -95,9 +161,30 @@ pub enum ZkPokerError {
     #[msg("Invalid card index")]
     InvalidCardIndex,
 
+    #[msg("Community card already on the board")]
+    DuplicateCommunityCard,
+
     #[msg("Invalid hand rank")]
     InvalidHandRank,
 
+    #[msg("Proof is bound to a different hand")]
+    ProofHandMismatch,
+
+    #[msg("Proof is for a different deck seed")]
+    DeckSeedMismatch,
+
+    #[msg("Proof is for different cards than this hand's")]
+    ProofCardsMismatch,
+
+    #[msg("Proof is for another seat's hole card positions")]
+    HolePositionMismatch,
+
+    #[msg("Deck shuffle not yet verified")]
+    DealNotVerified,
+
+    #[msg("Deck shuffle already verified")]
+    DealAlreadyVerified,
+
     // ============================================
     // Timeout Errors (6400-6499)
     // ============================================
@@ -110,6 +197,9 @@ pub enum ZkPokerError {
     #[msg("Invalid timeout configuration")]
     InvalidTimeoutConfig,
 
+    #[msg("Cannot claim a timeout against your own seat")]
+    CannotTimeoutSelf,
+
     // ============================================
     // Reveal Errors (6500-6599)
     // ============================================
@@ -128,6 +218,24 @@ pub enum ZkPokerError {
     #[msg("Hand already revealed")]
     HandAlreadyRevealed,
 
+    #[msg("Hand is not in an all-in runout")]
+    NotAllInRunout,
+
+    #[msg("Already agreed to run it twice")]
+    RunTwiceAlreadyConsented,
+
+    #[msg("Board is not being run twice")]
+    RunTwiceNotAgreed,
+
+    #[msg("Second board already revealed")]
+    SecondBoardAlreadyRevealed,
+
+    #[msg("Second board must be revealed first")]
+    SecondBoardNotRevealed,
+
+    #[msg("Current street's cards are not revealed yet")]
+    StreetNotRevealed,
+
     // ============================================
     // Showdown Errors (6600-6699)
     // ============================================
@@ -143,6 +251,36 @@ pub enum ZkPokerError {
     #[msg("Both players must reveal before claiming")]
     PlayersNotRevealed,
 
+    #[msg("Both players must agree on the result")]
+    ResultNotAgreed,
+
+    #[msg("Result does not match the other player's proposal")]
+    ResultMismatch,
+
+    #[msg("Agreed result must be a winner or a split")]
+    InvalidResult,
+
+    #[msg("The last aggressor must show first")]
+    ShowdownOrderViolation,
+
+    #[msg("Hand already mucked")]
+    HandAlreadyMucked,
+
+    #[msg("Showdown result can still be challenged")]
+    DisputeWindowOpen,
+
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+
+    #[msg("Proven rank matches the claimed rank")]
+    RankNotDisputed,
+
+    #[msg("A split needs both hands revealed and neither mucked")]
+    SplitNotProven,
+
+    #[msg("Claimed hand rank differs from the proven rank")]
+    RankMismatch,
+
     // ============================================
     // Global Errors (6700-6799)
     // ============================================
@@ -158,6 +296,15 @@ pub enum ZkPokerError {
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
 
+    #[msg("Chips were created or destroyed during the hand")]
+    ChipConservationViolated,
+
+    #[msg("Protocol must be paused first")]
+    NotPaused,
+
+    #[msg("Global config is already initialized")]
+    AlreadyInitialized,
+
     // ============================================
     // Proof Buffer Errors (6800-6899)
     // ============================================
@@ -172,4 +319,13 @@ pub enum ZkPokerError {
 
     #[msg("Proof buffer mismatch")]
     BufferMismatch,
+
+    #[msg("Proof buffer cannot shrink below uploaded data")]
+    InvalidBufferSize,
+
+    #[msg("Proof buffer was uploaded by another player")]
+    BufferNotOwned,
+
+    #[msg("Too many proof buffers open for this hand")]
+    TooManyBuffers,
 }
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::{HandWinner, CompletionReason};
+
+/// A player's stack fell below the big blind at the end of a hand; they sit
+/// out until they add chips
+#[event]
+pub struct PlayerNeedsRebuy {
+    pub table: Pubkey,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub chips: u64,
+}
+
+/// A hand finished and its pot was settled. Emitted exactly once per hand,
+/// whichever path completed it
+#[event]
+pub struct HandResult {
+    pub table: Pubkey,
+    pub table_id: u64,
+    pub hand_number: u64,
+    pub winner: HandWinner,
+    /// How the hand ended
+    pub reason: CompletionReason,
+    /// Chips paid out to the players
+    pub pot: u64,
+    /// Chips kept by the house
+    pub rake: u64,
+    /// Both seats' stacks after the payout
+    pub stacks: [u64; 2],
+}
+
+/// The next hand was started with `rematch` on the same seats
+#[event]
+pub struct RematchStarted {
+    pub table: Pubkey,
+    pub table_id: u64,
+    pub hand_number: u64,
+    /// Seat holding the button for the new hand
+    pub button: u8,
+}
+
+/// The clock started on a seat's decision. Sent whenever the action moves
+/// or the hand's last action time is refreshed, so clients count down to
+/// the program's deadline instead of their own clock
+#[event]
+pub struct ActionTimerStarted {
+    pub table_id: u64,
+    pub hand_number: u64,
+    /// Seat the action is on
+    pub seat: u8,
+    /// Last moment the seat can act before it can be timed out
+    pub deadline: i64,
+}
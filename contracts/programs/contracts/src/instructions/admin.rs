@@ -1,23 +1,28 @@
 use anchor_lang::prelude::*;
-use crate::state::GlobalConfig;
+use crate::state::{GlobalConfig, Table, Hand, CompletionReason};
 use crate::errors::ZkPokerError;
+use crate::instructions::hand::refund_bets;
+use crate::instructions::table::complete_hand;
 use crate::constants::{
     GLOBAL_SEED,
+    TABLE_SEED,
+    HAND_SEED,
     DECK_VERIFIER_PROGRAM_ID,
     DEAL_VERIFIER_PROGRAM_ID,
     REVEAL_VERIFIER_PROGRAM_ID,
-    SHOWDOWN_VERIFIER_PROGRAM_ID,
-    BET_VERIFIER_PROGRAM_ID
+    SHOWDOWN_VERIFIER_PROGRAM_ID
 };
 
-/// Initialize the global configuration
+/// Initialize the global configuration. `init_if_needed` so that a second
+/// call reaches the handler and fails with `AlreadyInitialized` instead of
+/// an opaque account-in-use error.
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         space = GlobalConfig::LEN,
         seeds = [GLOBAL_SEED],
@@ -61,11 +66,88 @@ pub struct Unpause<'info> {
     pub global_config: Account<'info, GlobalConfig>,
 }
 
+/// Set the cap on active tables
+#[derive(Accounts)]
+pub struct SetMaxTables<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = global_config.authority == authority.key() @ ZkPokerError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+/// Point the protocol at upgraded verifier programs
+#[derive(Accounts)]
+pub struct UpdateVerifiers<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = global_config.authority == authority.key() @ ZkPokerError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+/// Point the protocol at a different token mint
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = global_config.authority == authority.key() @ ZkPokerError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// New USDC mint account
+    pub usdc_mint: Account<'info, anchor_spl::token::Mint>,
+}
+
+/// Void a hand that cannot progress (e.g. a verifier outage) while paused
+#[derive(Accounts)]
+pub struct AdminAbortHand<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = global_config.authority == authority.key() @ ZkPokerError::Unauthorized,
+        constraint = global_config.is_paused @ ZkPokerError::NotPaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+}
+
 /// Initialize handler
 pub fn handle_initialize(ctx: Context<Initialize>) -> Result<()> {
     let global_config = &mut ctx.accounts.global_config;
     let bump = ctx.bumps.global_config;
 
+    // A fresh account is all zeroes; an initialized one always has an authority
+    require!(global_config.authority == Pubkey::default(), ZkPokerError::AlreadyInitialized);
+
     global_config.init(
         ctx.accounts.authority.key(),
         ctx.accounts.usdc_mint.key(),
@@ -73,7 +155,6 @@ pub fn handle_initialize(ctx: Context<Initialize>) -> Result<()> {
         DEAL_VERIFIER_PROGRAM_ID,
         REVEAL_VERIFIER_PROGRAM_ID,
         SHOWDOWN_VERIFIER_PROGRAM_ID,
-        BET_VERIFIER_PROGRAM_ID,
         bump,
     );
 
@@ -84,7 +165,6 @@ pub fn handle_initialize(ctx: Context<Initialize>) -> Result<()> {
     msg!("Deal Verifier: {}", DEAL_VERIFIER_PROGRAM_ID);
     msg!("Reveal Verifier: {}", REVEAL_VERIFIER_PROGRAM_ID);
     msg!("Showdown Verifier: {}", SHOWDOWN_VERIFIER_PROGRAM_ID);
-    msg!("Bet Verifier: {}", BET_VERIFIER_PROGRAM_ID);
 
     Ok(())
 }
@@ -108,3 +188,74 @@ pub fn handle_unpause(ctx: Context<Unpause>) -> Result<()> {
 
     Ok(())
 }
+
+/// Set max tables handler. Lowering the cap below the active count only
+/// blocks new tables; existing ones are unaffected.
+pub fn handle_set_max_tables(ctx: Context<SetMaxTables>, max_tables: u64) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.max_tables = max_tables;
+
+    msg!("Max tables set to {} ({} active)", max_tables, global_config.active_tables);
+
+    Ok(())
+}
+
+/// Update verifiers handler. Bumps the circuit version so hands started from
+/// now on reject proofs bound to the old circuits; hands already in flight
+/// keep the version they started under.
+pub fn handle_update_verifiers(
+    ctx: Context<UpdateVerifiers>,
+    deck_verifier: Pubkey,
+    deal_verifier: Pubkey,
+    reveal_verifier: Pubkey,
+    showdown_verifier: Pubkey,
+) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.deck_verifier = deck_verifier;
+    global_config.deal_verifier = deal_verifier;
+    global_config.reveal_verifier = reveal_verifier;
+    global_config.showdown_verifier = showdown_verifier;
+    global_config.circuit_version = global_config
+        .circuit_version
+        .checked_add(1)
+        .ok_or(ZkPokerError::ArithmeticOverflow)?;
+
+    msg!("Verifiers updated, circuit version {}", global_config.circuit_version);
+
+    Ok(())
+}
+
+/// Update config handler. Existing tables keep the mint they were created
+/// with; clients read the config mint as the default for new ones.
+pub fn handle_update_config(ctx: Context<UpdateConfig>) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.usdc_mint = ctx.accounts.usdc_mint.key();
+
+    msg!("USDC Mint updated: {}", global_config.usdc_mint);
+
+    Ok(())
+}
+
+/// Admin abort hand handler. Emergency escape hatch: each seat gets back
+/// exactly what it put into the pot and the table returns to `Between`.
+/// Nobody wins, so no rake, stats or profile results are recorded.
+pub fn handle_admin_abort_hand(ctx: Context<AdminAbortHand>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+
+    hand.require_not_complete()?;
+
+    let refunded = hand.total_bet;
+    refund_bets(table, hand);
+    complete_hand(table, hand, 0, 0, CompletionReason::Aborted);
+
+    msg!(
+        "Hand {} aborted by {}, refunded {} / {}",
+        hand.hand_number,
+        ctx.accounts.authority.key(),
+        refunded[0],
+        refunded[1]
+    );
+
+    hand.check_chip_conservation(table)
+}
@@ -1,11 +1,15 @@
 use anchor_lang::prelude::*;
-use crate::state::{Table, Hand, TableStatus, HandStage};
+use crate::state::{Table, Hand, HandStage, HandWinner, CompletionReason, TableStats, PlayerProfile};
 use crate::errors::ZkPokerError;
-use crate::constants::{TABLE_SEED, HAND_SEED};
+use crate::constants::{TABLE_SEED, HAND_SEED, TABLE_STATS_SEED, PROFILE_SEED};
+use crate::utils::verify_bet_balance;
+use crate::instructions::table::{complete_hand, take_rake};
+use crate::events::ActionTimerStarted;
 
 /// Betting action context (shared by all betting instructions)
 #[derive(Accounts)]
 pub struct BettingAction<'info> {
+    #[account(mut)]
     pub player: Signer<'info>,
 
     #[account(
@@ -22,16 +26,55 @@ pub struct BettingAction<'info> {
         constraint = hand.table == table.key()
     )]
     pub hand: Account<'info, Hand>,
+
+    /// Lifetime table statistics (created when the first hand completes)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = TableStats::LEN,
+        seeds = [TABLE_STATS_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub table_stats: Account<'info, TableStats>,
+
+    /// Lifetime profile of the player in seat 0
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [PROFILE_SEED, table.get_player(0).as_ref()],
+        bump
+    )]
+    pub player_one_profile: Account<'info, PlayerProfile>,
+
+    /// Lifetime profile of the player in seat 1
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [PROFILE_SEED, table.get_player(1).as_ref()],
+        bump
+    )]
+    pub player_two_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
 }
 
 /// Validate that the player can take a betting action
 fn validate_betting_action(table: &Table, hand: &Hand, player: &Pubkey) -> Result<u8> {
-    // Get player's seat
-    let seat = table.get_seat(player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // Get player's seat from the hand's roster
+    let seat = hand.player_seat(table, player)?;
 
     // Verify it's a betting stage
     require!(hand.stage.is_betting_stage(), ZkPokerError::InvalidStage);
 
+    // Nothing left to decide once an all-in is called, whoever `action_on` names
+    require!(!hand.fast_showdown(), ZkPokerError::BettingClosed);
+
+    // A street's betting opens only once its cards are revealed; the reveal
+    // handlers in turn only accept a street after the previous round closed
+    require!(hand.street_revealed(), ZkPokerError::StreetNotRevealed);
+
     // Verify it's player's turn
     require!(hand.action_on == seat, ZkPokerError::NotYourTurn);
 
@@ -44,46 +87,125 @@ fn validate_betting_action(table: &Table, hand: &Hand, player: &Pubkey) -> Resul
     Ok(seat)
 }
 
+/// Charge the acting seat for any time spent past the action timeout.
+/// Late actions draw down the seat's time bank, and actions inside the
+/// table's minimum interval are refused; returns the current timestamp.
+fn consume_action_time(table: &mut Table, hand: &Hand, seat: u8) -> Result<i64> {
+    let clock = Clock::get()?;
+    let elapsed = clock.unix_timestamp - hand.last_action_at;
+    require!(elapsed >= table.min_action_interval, ZkPokerError::ActingTooFast);
+    table.consume_time_bank(seat, elapsed)?;
+    Ok(clock.unix_timestamp)
+}
+
+/// Chips a seat may still add this hand under the table's pot cap or cap
+/// game; uncapped tables never limit it
+pub(crate) fn wager_room(table: &Table, hand: &Hand, seat: u8) -> u64 {
+    table.seat_wager_limit().saturating_sub(hand.get_total_bet(seat))
+}
+
+/// Mark a seat all-in once its stack is empty or it wagered up to the
+/// table's cap; returns whether it is
+fn settle_all_in(table: &Table, hand: &mut Hand, seat: u8) -> bool {
+    let all_in = table.get_chips(seat) == 0 || wager_room(table, hand, seat) == 0;
+    if all_in {
+        hand.set_all_in(seat);
+    }
+    all_in
+}
+
+/// Only a seat whose action was reopened may raise, and only against an
+/// opponent with chips left to face it
+fn may_raise(hand: &Hand, seat: u8) -> bool {
+    !hand.has_acted_this_street(seat) && !hand.is_all_in(hand.other_seat(seat))
+}
+
+/// A short all-in call leaves part of the opponent's bet uncalled: return it
+/// to their stack so the street settles at the amount `seat` called
+fn return_uncalled(table: &mut Table, hand: &mut Hand, seat: u8) -> Result<()> {
+    let called_to = hand.get_bet_this_street(seat);
+    if called_to < hand.current_bet {
+        let opponent = hand.other_seat(seat);
+        let uncalled = hand.current_bet - called_to;
+        hand.return_bet(opponent, uncalled)?;
+        table.add_chips(opponent, uncalled);
+        hand.current_bet = called_to;
+        msg!("Returned {} uncalled to seat {}", uncalled, opponent);
+    }
+    Ok(())
+}
+
 /// Handle street transition after betting completes
-fn handle_street_transition(table: &mut Table, hand: &mut Hand) -> Result<()> {
+pub(crate) fn handle_street_transition(
+    table: &mut Table,
+    hand: &mut Hand,
+    stats: &mut TableStats,
+    profiles: [&mut PlayerProfile; 2],
+) -> Result<()> {
     // Check if someone folded
     if hand.remaining_players() == 1 {
+        hand.require_not_complete()?;
+
         // Award pot to remaining player
         if let Some(winner) = hand.non_folded_seat() {
-            hand.winner = winner;
+            hand.winner = HandWinner::from_seat(winner);
+            let rake = take_rake(table, hand);
             table.add_chips(winner, hand.pot);
-            hand.pot = 0;
-            hand.stage = HandStage::Complete;
-            table.status = TableStatus::Between;
-            table.current_hand = None;
-            table.increment_hands_played();
-            table.rotate_button();
+            stats.record_hand(hand, hand.pot, rake);
+            for (seat, profile) in (0u8..).zip(profiles) {
+                profile.record_hand(table.get_player(seat), hand, seat, hand.pot);
+            }
+            complete_hand(table, hand, hand.pot, rake, CompletionReason::Fold);
             msg!("Player folded, seat {} wins pot", winner);
         }
-        return Ok(());
+        return hand.check_chip_conservation(table);
     }
 
     // Check if betting round is complete
     if hand.is_betting_complete() {
+        // An all-in was called: the rest of the hand is reveals and showdown
+        if hand.any_all_in() && !hand.fast_showdown() {
+            hand.set_fast_showdown(true);
+            msg!("All-in called, betting closed for the rest of the hand");
+        }
+
         // Advance to next stage
         if let Some(next_stage) = hand.stage.next_betting_stage() {
+            let street_aggressor = hand.last_aggressor;
             hand.stage = next_stage;
             hand.reset_street();
 
-            // Set action to big blind (first to act post-flop in heads-up)
-            let bb_seat = table.big_blind_seat();
-            hand.action_on = bb_seat;
+            // Open the new street with the table's first-to-act seat (the
+            // big blind in standard heads-up)
+            hand.action_on = table.first_to_act(next_stage);
 
             msg!("Advancing to {:?}", next_stage);
 
-            // If showdown, both players need to reveal
+            // If showdown, both players need to reveal. The river's last
+            // aggressor shows first; if the river checked through, the
+            // first player to act does.
             if next_stage == HandStage::Showdown {
-                msg!("Showdown reached!");
+                if street_aggressor != 255 {
+                    hand.last_aggressor = street_aggressor;
+                    hand.action_on = street_aggressor;
+                }
+                msg!("Showdown reached! Seat {} shows first", hand.action_on);
             }
         }
     }
 
-    Ok(())
+    emit_action_timer(table, hand);
+    hand.check_chip_conservation(table)
+}
+
+/// Announce the deadline of the seat the action is now on
+pub(crate) fn emit_action_timer(table: &Table, hand: &Hand) {
+    emit!(ActionTimerStarted {
+        table_id: table.table_id,
+        hand_number: hand.hand_number,
+        seat: hand.action_on,
+        deadline: hand.last_action_at.saturating_add(table.stage_timeout(hand.stage, hand.action_on)),
+    });
 }
 
 /// Check handler - pass without betting
@@ -102,8 +224,7 @@ pub fn handle_check(ctx: Context<BettingAction>) -> Result<()> {
     hand.set_acted_this_street(seat);
 
     // Update timestamp
-    let clock = Clock::get()?;
-    hand.last_action_at = clock.unix_timestamp;
+    hand.last_action_at = consume_action_time(table, hand, seat)?;
 
     // Switch action
     hand.switch_action();
@@ -111,7 +232,12 @@ pub fn handle_check(ctx: Context<BettingAction>) -> Result<()> {
     msg!("Seat {} checks", seat);
 
     // Handle potential street transition
-    handle_street_transition(table, hand)?;
+    handle_street_transition(
+        table,
+        hand,
+        &mut ctx.accounts.table_stats,
+        [&mut ctx.accounts.player_one_profile, &mut ctx.accounts.player_two_profile],
+    )?;
 
     Ok(())
 }
@@ -130,38 +256,48 @@ pub fn handle_bet(ctx: Context<BettingAction>, amount: u64) -> Result<()> {
     // Bet must be at least big blind
     require!(amount >= table.big_blind, ZkPokerError::BetTooSmall);
 
-    // Get player's available chips
-    let available_chips = table.get_chips(seat);
-    require!(amount <= available_chips, ZkPokerError::InsufficientChips);
+    // A bet past the wager cap is cut to it; the rest stays in the stack
+    let amount = amount.min(wager_room(table, hand, seat));
+
+    // Verify player can cover the bet
+    verify_bet_balance(table.get_chips(seat), amount)?;
 
     // Remove chips from player
     table.remove_chips(seat, amount);
 
     // Add to pot and track bet
-    hand.add_bet(seat, amount);
+    hand.add_bet(seat, amount)?;
     hand.current_bet = amount;
+    hand.last_raise_size = amount;
     hand.last_aggressor = seat;
 
     // Mark as acted
     hand.set_acted_this_street(seat);
 
+    // Reset opponent's acted flag: a seat that checked may still raise
+    let opponent = hand.other_seat(seat);
+    hand.clear_acted_this_street(opponent);
+
     // Check if all-in
-    if table.get_chips(seat) == 0 {
-        hand.set_all_in(seat);
+    if settle_all_in(table, hand, seat) {
         msg!("Seat {} bets {} (ALL-IN)", seat, amount);
     } else {
         msg!("Seat {} bets {}", seat, amount);
     }
 
     // Update timestamp
-    let clock = Clock::get()?;
-    hand.last_action_at = clock.unix_timestamp;
+    hand.last_action_at = consume_action_time(table, hand, seat)?;
 
     // Switch action
     hand.switch_action();
 
     // Handle potential street transition
-    handle_street_transition(table, hand)?;
+    handle_street_transition(
+        table,
+        hand,
+        &mut ctx.accounts.table_stats,
+        [&mut ctx.accounts.player_one_profile, &mut ctx.accounts.player_two_profile],
+    )?;
 
     Ok(())
 }
@@ -178,7 +314,7 @@ pub fn handle_call(ctx: Context<BettingAction>) -> Result<()> {
     let player_bet = hand.get_bet_this_street(seat);
     let to_call = hand.current_bet.saturating_sub(player_bet);
 
-    require!(to_call > 0, ZkPokerError::CannotCheck); // Should use check if nothing to call
+    require!(to_call > 0, ZkPokerError::NothingToCall); // Should use check if nothing to call
 
     // Get player's available chips
     let available_chips = table.get_chips(seat);
@@ -188,28 +324,33 @@ pub fn handle_call(ctx: Context<BettingAction>) -> Result<()> {
     table.remove_chips(seat, actual_call);
 
     // Add to pot and track bet
-    hand.add_bet(seat, actual_call);
+    hand.add_bet(seat, actual_call)?;
+
+    return_uncalled(table, hand, seat)?;
 
     // Mark as acted
     hand.set_acted_this_street(seat);
 
-    // Check if all-in (couldn't fully call)
-    if table.get_chips(seat) == 0 {
-        hand.set_all_in(seat);
+    // Check if all-in (couldn't fully call, or the call reached the wager cap)
+    if settle_all_in(table, hand, seat) {
         msg!("Seat {} calls {} (ALL-IN)", seat, actual_call);
     } else {
         msg!("Seat {} calls {}", seat, actual_call);
     }
 
     // Update timestamp
-    let clock = Clock::get()?;
-    hand.last_action_at = clock.unix_timestamp;
+    hand.last_action_at = consume_action_time(table, hand, seat)?;
 
     // Switch action
     hand.switch_action();
 
     // Handle potential street transition
-    handle_street_transition(table, hand)?;
+    handle_street_transition(
+        table,
+        hand,
+        &mut ctx.accounts.table_stats,
+        [&mut ctx.accounts.player_one_profile, &mut ctx.accounts.player_two_profile],
+    )?;
 
     Ok(())
 }
@@ -222,57 +363,68 @@ pub fn handle_raise_to(ctx: Context<BettingAction>, amount: u64) -> Result<()> {
 
     let seat = validate_betting_action(table, hand, &player)?;
 
+    require!(may_raise(hand, seat), ZkPokerError::CannotRaise);
+    let opponent = hand.other_seat(seat);
+
     // Raise must be to an amount greater than current bet
     require!(amount > hand.current_bet, ZkPokerError::RaiseTooSmall);
 
-    // Minimum raise is current_bet + big_blind (or current_bet * 2 for simplicity)
-    let min_raise = hand.current_bet.saturating_add(table.big_blind);
+    // A raise adds at least the street's last bet or raise (a big blind at minimum)
+    let min_raise = hand
+        .current_bet
+        .checked_add(hand.min_raise_increment(table.big_blind))
+        .ok_or(ZkPokerError::ArithmeticOverflow)?;
     require!(amount >= min_raise, ZkPokerError::RaiseTooSmall);
 
-    // Calculate how much more to put in
+    // A raise past the wager cap is cut to it; the rest stays in the stack
     let player_bet = hand.get_bet_this_street(seat);
-    let additional = amount.saturating_sub(player_bet);
+    let amount = amount.min(player_bet.saturating_add(wager_room(table, hand, seat)));
 
-    // Get player's available chips
-    let available_chips = table.get_chips(seat);
-    require!(additional <= available_chips, ZkPokerError::InsufficientChips);
+    // Calculate how much more to put in
+    let additional = amount.checked_sub(player_bet).ok_or(ZkPokerError::ArithmeticOverflow)?;
+
+    // Verify player can cover the raise
+    verify_bet_balance(table.get_chips(seat), additional)?;
 
     // Remove chips from player
     table.remove_chips(seat, additional);
 
     // Add to pot and track bet
-    hand.add_bet(seat, additional);
+    hand.add_bet(seat, additional)?;
+
+    // A full raise resets the minimum and gives the opponent a new decision;
+    // one cut short by the wager cap only has to be called
+    let increment = amount - hand.current_bet;
+    if hand.reopens_action(increment, table.big_blind) {
+        hand.last_raise_size = increment;
+        hand.clear_acted_this_street(opponent);
+    }
     hand.current_bet = amount;
     hand.last_aggressor = seat;
 
     // Mark as acted
     hand.set_acted_this_street(seat);
 
-    // Reset opponent's acted flag (they need to act again)
-    let opponent = hand.other_seat(seat);
-    match opponent {
-        0 => hand.p1_acted_this_street = false,
-        1 => hand.p2_acted_this_street = false,
-        _ => {}
-    }
-
     // Check if all-in
-    if table.get_chips(seat) == 0 {
-        hand.set_all_in(seat);
+    if settle_all_in(table, hand, seat) {
         msg!("Seat {} raises to {} (ALL-IN)", seat, amount);
     } else {
         msg!("Seat {} raises to {}", seat, amount);
     }
 
     // Update timestamp
-    let clock = Clock::get()?;
-    hand.last_action_at = clock.unix_timestamp;
+    hand.last_action_at = consume_action_time(table, hand, seat)?;
 
     // Switch action
     hand.switch_action();
 
     // Handle potential street transition
-    handle_street_transition(table, hand)?;
+    handle_street_transition(
+        table,
+        hand,
+        &mut ctx.accounts.table_stats,
+        [&mut ctx.accounts.player_one_profile, &mut ctx.accounts.player_two_profile],
+    )?;
 
     Ok(())
 }
@@ -289,13 +441,17 @@ pub fn handle_fold(ctx: Context<BettingAction>) -> Result<()> {
     hand.set_folded(seat);
 
     // Update timestamp
-    let clock = Clock::get()?;
-    hand.last_action_at = clock.unix_timestamp;
+    hand.last_action_at = consume_action_time(table, hand, seat)?;
 
     msg!("Seat {} folds", seat);
 
     // Handle street transition (will award pot to winner)
-    handle_street_transition(table, hand)?;
+    handle_street_transition(
+        table,
+        hand,
+        &mut ctx.accounts.table_stats,
+        [&mut ctx.accounts.player_one_profile, &mut ctx.accounts.player_two_profile],
+    )?;
 
     Ok(())
 }
@@ -308,49 +464,67 @@ pub fn handle_all_in(ctx: Context<BettingAction>) -> Result<()> {
 
     let seat = validate_betting_action(table, hand, &player)?;
 
-    // Get player's entire stack
-    let available_chips = table.get_chips(seat);
-    require!(available_chips > 0, ZkPokerError::InsufficientChips);
+    // Get player's entire stack, cut to the wager cap
+    let mut available_chips = table.get_chips(seat).min(wager_room(table, hand, seat));
+
+    // A seat that may not raise only calls with its shove; the rest of the
+    // stack stays behind
+    if !may_raise(hand, seat) {
+        let to_call = hand.current_bet.saturating_sub(hand.get_bet_this_street(seat));
+        available_chips = available_chips.min(to_call);
+    }
+    verify_bet_balance(table.get_chips(seat), available_chips)?;
 
     // Remove all chips from player
     table.remove_chips(seat, available_chips);
 
     // Calculate total bet this street
     let player_bet = hand.get_bet_this_street(seat);
-    let new_total = player_bet.saturating_add(available_chips);
+    let new_total = player_bet
+        .checked_add(available_chips)
+        .ok_or(ZkPokerError::ArithmeticOverflow)?;
 
     // Add to pot and track bet
-    hand.add_bet(seat, available_chips);
+    hand.add_bet(seat, available_chips)?;
 
     // Update current bet if this is a raise
     if new_total > hand.current_bet {
+        // Only a full raise resets the minimum and reopens the action; after
+        // a short all-in an opponent who already acted may only call or fold
+        let raise_size = new_total - hand.current_bet;
+        if hand.reopens_action(raise_size, table.big_blind) {
+            hand.last_raise_size = raise_size;
+            let opponent = hand.other_seat(seat);
+            hand.clear_acted_this_street(opponent);
+        }
         hand.current_bet = new_total;
         hand.last_aggressor = seat;
-
-        // Reset opponent's acted flag
-        let opponent = hand.other_seat(seat);
-        match opponent {
-            0 => hand.p1_acted_this_street = false,
-            1 => hand.p2_acted_this_street = false,
-            _ => {}
-        }
+    } else {
+        return_uncalled(table, hand, seat)?;
     }
 
-    // Mark as all-in and acted
-    hand.set_all_in(seat);
+    // Mark as acted, and all-in unless the shove was capped to a call
     hand.set_acted_this_street(seat);
 
-    msg!("Seat {} goes ALL-IN for {}", seat, available_chips);
+    if settle_all_in(table, hand, seat) {
+        msg!("Seat {} goes ALL-IN for {}", seat, available_chips);
+    } else {
+        msg!("Seat {} may not raise, all-in capped to a call of {}", seat, available_chips);
+    }
 
     // Update timestamp
-    let clock = Clock::get()?;
-    hand.last_action_at = clock.unix_timestamp;
+    hand.last_action_at = consume_action_time(table, hand, seat)?;
 
     // Switch action
     hand.switch_action();
 
     // Handle potential street transition
-    handle_street_transition(table, hand)?;
+    handle_street_transition(
+        table,
+        hand,
+        &mut ctx.accounts.table_stats,
+        [&mut ctx.accounts.player_one_profile, &mut ctx.accounts.player_two_profile],
+    )?;
 
     Ok(())
 }
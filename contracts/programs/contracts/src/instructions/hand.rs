@@ -1,9 +1,16 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
 use solana_keccak_hasher as keccak;
-use crate::state::{GlobalConfig, Table, Hand, TableStatus, HandStage, ProofBuffer, ProofType};
+use crate::state::{GlobalConfig, Table, Hand, TableStatus, HandStage, HandWinner, CompletionReason, ProofBuffer, ProofType, TableStats, PlayerProfile, TimeoutPolicy};
 use crate::errors::ZkPokerError;
-use crate::constants::{GLOBAL_SEED, TABLE_SEED, HAND_SEED};
-use crate::utils::verify_hole_card_commitments;
+use crate::constants::{
+    GLOBAL_SEED, TABLE_SEED, HAND_SEED, TABLE_STATS_SEED, PROFILE_SEED,
+    P1_CARD_1_POS, P1_CARD_2_POS, P2_CARD_1_POS, P2_CARD_2_POS, OPENING_BUTTON_DOMAIN,
+};
+use crate::utils::{verify_deal, verify_hole_card_commitments, hand_binding};
+use crate::instructions::table::complete_hand;
+use crate::instructions::betting::handle_street_transition;
+use crate::events::RematchStarted;
 
 /// Start a new hand
 #[derive(Accounts)]
@@ -34,6 +41,86 @@ pub struct StartHand<'info> {
     )]
     pub hand: Account<'info, Hand>,
 
+    /// The other seated player, co-signing to pay half the hand's rent on
+    /// split-rent tables
+    #[account(mut)]
+    pub co_payer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Start the next hand on the same seats, without re-joining
+#[derive(Accounts)]
+pub struct Rematch<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = !global_config.is_paused @ ZkPokerError::GamePaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        init,
+        payer = player,
+        space = Hand::LEN,
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hands_played.to_le_bytes()],
+        bump
+    )]
+    pub hand: Account<'info, Hand>,
+
+    /// The other seated player, co-signing to pay half the hand's rent on
+    /// split-rent tables
+    #[account(mut)]
+    pub co_payer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Ready up for the next hand on an auto-continue table
+#[derive(Accounts)]
+pub struct ReadyUp<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = !global_config.is_paused @ ZkPokerError::GamePaused
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Next hand, created when this ready_up makes both seats ready
+    #[account(
+        init,
+        payer = player,
+        space = Hand::LEN,
+        seeds = [HAND_SEED, table.key().as_ref(), &table.hands_played.to_le_bytes()],
+        bump
+    )]
+    pub hand: Option<Account<'info, Hand>>,
+
+    /// The other seated player, co-signing to pay half the hand's rent on
+    /// split-rent tables
+    #[account(mut)]
+    pub co_payer: Option<Signer<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -63,6 +150,7 @@ pub struct RevealSeed<'info> {
     pub player: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
         bump = table.bump
     )]
@@ -77,6 +165,48 @@ pub struct RevealSeed<'info> {
     pub hand: Account<'info, Hand>,
 }
 
+/// Prove the deck shuffle (with ZK proof from buffer)
+#[derive(Accounts)]
+pub struct VerifyDeal<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+
+    /// Proof buffer containing the ZK proof data
+    #[account(
+        mut,
+        close = player,
+        has_one = player @ ZkPokerError::Unauthorized,
+        constraint = proof_buffer.hand == hand.key() @ ZkPokerError::BufferMismatch,
+        constraint = proof_buffer.proof_type == ProofType::Deal @ ZkPokerError::BufferMismatch,
+        constraint = proof_buffer.complete @ ZkPokerError::BufferNotComplete
+    )]
+    pub proof_buffer: Account<'info, ProofBuffer>,
+
+    /// CHECK: DEAL verifier program - verified in verification function
+    #[account(constraint = verifier_program.key() == global_config.deal_verifier @ ZkPokerError::ProofVerificationFailed)]
+    pub verifier_program: AccountInfo<'info>,
+}
+
 /// Commit hole cards (with ZK proof from buffer)
 #[derive(Accounts)]
 pub struct CommitHoleCards<'info> {
@@ -119,9 +249,32 @@ pub struct CommitHoleCards<'info> {
     pub verifier_program: AccountInfo<'info>,
 }
 
-/// Timeout claim
+/// Straddle before the deal
+#[derive(Accounts)]
+pub struct Straddle<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+}
+
+/// Timeout claim. Anyone may call it (e.g. a keeper bot settling stalled
+/// hands), but a seated player can never time out their own seat.
 #[derive(Accounts)]
 pub struct Timeout<'info> {
+    #[account(mut)]
     pub caller: Signer<'info>,
 
     #[account(
@@ -138,49 +291,241 @@ pub struct Timeout<'info> {
         constraint = hand.table == table.key()
     )]
     pub hand: Account<'info, Hand>,
+
+    /// Lifetime table statistics (created when the first hand completes)
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = TableStats::LEN,
+        seeds = [TABLE_STATS_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub table_stats: Account<'info, TableStats>,
+
+    /// Lifetime profile of the player in seat 0
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PlayerProfile::LEN,
+        seeds = [PROFILE_SEED, table.get_player(0).as_ref()],
+        bump
+    )]
+    pub player_one_profile: Account<'info, PlayerProfile>,
+
+    /// Lifetime profile of the player in seat 1
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PlayerProfile::LEN,
+        seeds = [PROFILE_SEED, table.get_player(1).as_ref()],
+        bump
+    )]
+    pub player_two_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Close a completed hand and refund its rent
+#[derive(Accounts)]
+pub struct CloseHand<'info> {
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+
+    /// CHECK: Receives the rent; must be whoever paid for the hand
+    #[account(mut, address = hand.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// CHECK: Receives half the rent on split-rent tables; checked against
+    /// the hand's other player in the handler
+    #[account(mut)]
+    pub co_payer: Option<UncheckedAccount<'info>>,
 }
 
 /// Start hand handler
 pub fn handle_start_hand(ctx: Context<StartHand>) -> Result<()> {
     let table = &mut ctx.accounts.table;
-    let hand = &mut ctx.accounts.hand;
     let player = ctx.accounts.player.key();
 
     // Verify player is at table
     let _seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
 
-    // Verify table is full and between hands
-    require!(table.is_full(), ZkPokerError::NotEnoughPlayers);
-    require!(table.status == TableStatus::Between, ZkPokerError::InvalidStage);
+    // Auto-continue tables only start once both players are ready
+    require!(!table.auto_continue || table.both_ready(), ZkPokerError::PlayersNotReady);
 
-    // Verify both players have enough chips for blinds
-    let sb_seat = table.small_blind_seat();
-    let bb_seat = table.big_blind_seat();
+    charge_co_payer(
+        table,
+        &ctx.accounts.player,
+        ctx.accounts.co_payer.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let circuit_version = ctx.accounts.global_config.circuit_version;
+    begin_hand(table, &mut ctx.accounts.hand, player, circuit_version, ctx.bumps.hand)
+}
+
+/// Rematch handler
+pub fn handle_rematch(ctx: Context<Rematch>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let player = ctx.accounts.player.key();
+
+    // Verify player is at table
+    let _seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+
+    require!(!table.auto_continue || table.both_ready(), ZkPokerError::PlayersNotReady);
+
+    // Both seats must cover a big blind, whichever blind they post next
+    let (_, big_blind) = table.current_blinds(Clock::get()?.unix_timestamp);
     require!(
-        table.get_chips(sb_seat) >= table.small_blind,
+        table.get_chips(0) >= big_blind && table.get_chips(1) >= big_blind,
         ZkPokerError::InsufficientChips
     );
+
+    charge_co_payer(
+        table,
+        &ctx.accounts.player,
+        ctx.accounts.co_payer.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    // The button already moved when the previous hand completed
+    let circuit_version = ctx.accounts.global_config.circuit_version;
+    begin_hand(table, &mut ctx.accounts.hand, player, circuit_version, ctx.bumps.hand)?;
+
+    emit!(RematchStarted {
+        table: table.key(),
+        table_id: table.table_id,
+        hand_number: ctx.accounts.hand.hand_number,
+        button: table.button,
+    });
+
+    Ok(())
+}
+
+/// Ready up handler
+pub fn handle_ready_up(ctx: Context<ReadyUp>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let player = ctx.accounts.player.key();
+
+    // Verify player is at table
+    let seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+
+    require!(table.auto_continue, ZkPokerError::AutoContinueDisabled);
+    require!(table.status == TableStatus::Between, ZkPokerError::InvalidStage);
+
+    table.set_ready(seat, true);
+    msg!("Seat {} is ready", seat);
+
+    if !table.both_ready() {
+        // Nothing to initialize yet, refund the unused hand account
+        if let Some(hand) = &ctx.accounts.hand {
+            hand.close(ctx.accounts.player.to_account_info())?;
+        }
+        return Ok(());
+    }
+
+    // Both ready - the caller must supply the next hand account
+    let hand = ctx.accounts.hand.as_mut().ok_or(ZkPokerError::HandNotFound)?;
+    let bump = ctx.bumps.hand.ok_or(ZkPokerError::HandNotFound)?;
+
+    charge_co_payer(
+        table,
+        &ctx.accounts.player,
+        ctx.accounts.co_payer.as_ref(),
+        &ctx.accounts.system_program,
+    )?;
+    let circuit_version = ctx.accounts.global_config.circuit_version;
+    begin_hand(table, hand, player, circuit_version, bump)
+}
+
+/// On split-rent tables, have the other seated player pay the hand's
+/// starter back half of the rent it just paid
+fn charge_co_payer<'info>(
+    table: &Table,
+    payer: &Signer<'info>,
+    co_payer: Option<&Signer<'info>>,
+    system: &Program<'info, System>,
+) -> Result<()> {
+    if !table.split_hand_rent {
+        return Ok(());
+    }
+
+    let co_payer = co_payer.ok_or(ZkPokerError::CoPayerRequired)?;
+    let payer_seat = table.get_seat(&payer.key()).ok_or(ZkPokerError::PlayerNotAtTable)?;
     require!(
-        table.get_chips(bb_seat) >= table.big_blind,
-        ZkPokerError::InsufficientChips
+        table.get_seat(&co_payer.key()) == Some(table.other_seat(payer_seat)),
+        ZkPokerError::CoPayerRequired
     );
 
+    let half = Rent::get()?.minimum_balance(Hand::LEN) / 2;
+    let cpi_accounts = Transfer {
+        from: co_payer.to_account_info(),
+        to: payer.to_account_info(),
+    };
+    system_program::transfer(CpiContext::new(system.to_account_info(), cpi_accounts), half)?;
+
+    msg!("Hand rent split: {} paid {}", co_payer.key(), half);
+    Ok(())
+}
+
+/// Post blinds and initialize a new hand on a full table between hands
+fn begin_hand(
+    table: &mut Account<Table>,
+    hand: &mut Account<Hand>,
+    rent_payer: Pubkey,
+    circuit_version: u16,
+    bump: u8,
+) -> Result<()> {
+    // Verify table is full and between hands
+    require!(table.is_full(), ZkPokerError::NotEnoughPlayers);
+    require!(table.status == TableStatus::Between, ZkPokerError::InvalidStage);
+    require!(!table.closing, ZkPokerError::TableClosing);
+
     let clock = Clock::get()?;
-    let bump = ctx.bumps.hand;
+
+    // Move to the scheduled blind level (static blinds without a schedule)
+    let (small_blind, big_blind) = table.current_blinds(clock.unix_timestamp);
+    if (small_blind, big_blind) != (table.small_blind, table.big_blind) {
+        table.small_blind = small_blind;
+        table.big_blind = big_blind;
+        msg!("Blind level changed to {}/{}", small_blind, big_blind);
+    }
+
     let hand_number = table.hands_played;
 
     // Initialize hand
-    hand.init(table.key(), hand_number, clock.unix_timestamp, bump);
-
-    // Post blinds (copy values first to avoid borrow conflict)
-    let small_blind = table.small_blind;
-    let big_blind = table.big_blind;
-    let sb_amount = table.remove_chips(sb_seat, small_blind);
-    let bb_amount = table.remove_chips(bb_seat, big_blind);
+    hand.init(
+        table.key(),
+        hand_number,
+        table.get_player(0),
+        table.get_player(1),
+        rent_payer,
+        circuit_version,
+        clock.unix_timestamp,
+        bump,
+    );
 
-    hand.add_bet(sb_seat, sb_amount);
-    hand.add_bet(bb_seat, bb_amount);
-    hand.current_bet = bb_amount;
+    // The opening button is drawn from both seeds, which nobody knows yet;
+    // the blinds follow it once both are revealed
+    if table.draws_opening_button() {
+        msg!("Blinds wait for the opening button draw");
+    } else {
+        post_blinds(table, hand)?;
+    }
+    hand.chips_in_play = table
+        .get_chips(0)
+        .checked_add(table.get_chips(1))
+        .and_then(|chips| chips.checked_add(hand.pot))
+        .ok_or(ZkPokerError::ArithmeticOverflow)?;
 
     // Set action to small blind player first for seed commit
     hand.action_on = 0; // Either player can commit first
@@ -188,11 +533,41 @@ pub fn handle_start_hand(ctx: Context<StartHand>) -> Result<()> {
     // Update table state
     table.status = TableStatus::Playing;
     table.current_hand = Some(hand.key());
+    table.ready = [false; 2];
 
     msg!("Hand {} started", hand_number);
+    msg!("Pot: {}", hand.pot);
+
+    Ok(())
+}
+
+/// Move the small and big blinds from the stacks into the hand
+fn post_blinds(table: &mut Table, hand: &mut Hand) -> Result<()> {
+    let (small_blind, big_blind) = (table.small_blind, table.big_blind);
+
+    // Verify both players have enough chips for blinds. The blinds follow
+    // the button as the last hand, a returning sit-out or a vacated seat
+    // left it, so no player posts the same blind twice in a row
+    let sb_seat = table.small_blind_seat();
+    let bb_seat = table.big_blind_seat();
+    require!(
+        table.get_chips(sb_seat) >= small_blind,
+        ZkPokerError::InsufficientChips
+    );
+    require!(
+        table.get_chips(bb_seat) >= big_blind,
+        ZkPokerError::InsufficientChips
+    );
+
+    let sb_amount = table.remove_chips(sb_seat, small_blind);
+    let bb_amount = table.remove_chips(bb_seat, big_blind);
+
+    hand.add_bet(sb_seat, sb_amount)?;
+    hand.add_bet(bb_seat, bb_amount)?;
+    hand.current_bet = bb_amount;
+
     msg!("Small blind: {} from seat {}", sb_amount, sb_seat);
     msg!("Big blind: {} from seat {}", bb_amount, bb_seat);
-    msg!("Pot: {}", hand.pot);
 
     Ok(())
 }
@@ -203,29 +578,36 @@ pub fn handle_commit_seed(ctx: Context<CommitSeed>, seed_hash: [u8; 32]) -> Resu
     let hand = &mut ctx.accounts.hand;
     let player = ctx.accounts.player.key();
 
-    // Verify player is at table
-    let seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // Verify player was dealt into this hand and still holds the seat
+    let seat = hand.player_seat(table, &player)?;
 
     // Verify stage
     require!(hand.stage == HandStage::SeedCommit, ZkPokerError::InvalidStage);
 
     // Check if player already committed
     let already_committed = match seat {
-        0 => hand.p1_seed_committed,
-        1 => hand.p2_seed_committed,
+        0 => hand.p1_seed_committed(),
+        1 => hand.p2_seed_committed(),
         _ => return Err(ZkPokerError::PlayerNotAtTable.into()),
     };
     require!(!already_committed, ZkPokerError::SeedAlreadyCommitted);
 
+    // Reject a copy of the opponent's commitment
+    let opponent_commit = match seat {
+        0 => hand.p2_seed_committed().then_some(hand.seed_commit_two),
+        _ => hand.p1_seed_committed().then_some(hand.seed_commit_one),
+    };
+    require!(opponent_commit != Some(seed_hash), ZkPokerError::DuplicateSeedCommitment);
+
     // Store commitment
     match seat {
         0 => {
             hand.seed_commit_one = seed_hash;
-            hand.p1_seed_committed = true;
+            hand.set_p1_seed_committed(true);
         }
         1 => {
             hand.seed_commit_two = seed_hash;
-            hand.p2_seed_committed = true;
+            hand.set_p2_seed_committed(true);
         }
         _ => {}
     }
@@ -237,7 +619,7 @@ pub fn handle_commit_seed(ctx: Context<CommitSeed>, seed_hash: [u8; 32]) -> Resu
     msg!("Player {} (seat {}) committed seed", player, seat);
 
     // Check if both committed, advance stage
-    if hand.p1_seed_committed && hand.p2_seed_committed {
+    if hand.p1_seed_committed() && hand.p2_seed_committed() {
         hand.stage = HandStage::SeedReveal;
         msg!("Both seeds committed, advancing to SeedReveal");
     }
@@ -247,28 +629,28 @@ pub fn handle_commit_seed(ctx: Context<CommitSeed>, seed_hash: [u8; 32]) -> Resu
 
 /// Reveal seed handler
 pub fn handle_reveal_seed(ctx: Context<RevealSeed>, seed: [u8; 32]) -> Result<()> {
-    let table = &ctx.accounts.table;
+    let table = &mut ctx.accounts.table;
     let hand = &mut ctx.accounts.hand;
     let player = ctx.accounts.player.key();
 
-    // Verify player is at table
-    let seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // Verify player was dealt into this hand and still holds the seat
+    let seat = hand.player_seat(table, &player)?;
 
     // Verify stage
     require!(hand.stage == HandStage::SeedReveal, ZkPokerError::InvalidStage);
 
     // Check if player has committed
     let committed = match seat {
-        0 => hand.p1_seed_committed,
-        1 => hand.p2_seed_committed,
+        0 => hand.p1_seed_committed(),
+        1 => hand.p2_seed_committed(),
         _ => return Err(ZkPokerError::PlayerNotAtTable.into()),
     };
     require!(committed, ZkPokerError::SeedNotCommitted);
 
     // Check if player already revealed
     let already_revealed = match seat {
-        0 => hand.p1_seed_revealed,
-        1 => hand.p2_seed_revealed,
+        0 => hand.p1_seed_revealed(),
+        1 => hand.p2_seed_revealed(),
         _ => return Err(ZkPokerError::PlayerNotAtTable.into()),
     };
     require!(!already_revealed, ZkPokerError::SeedAlreadyCommitted);
@@ -286,15 +668,22 @@ pub fn handle_reveal_seed(ctx: Context<RevealSeed>, seed: [u8; 32]) -> Result<()
         ZkPokerError::InvalidSeedReveal
     );
 
+    // Reject a seed identical to the opponent's
+    let opponent_seed = match seat {
+        0 => hand.p2_seed_revealed().then_some(hand.seed_two),
+        _ => hand.p1_seed_revealed().then_some(hand.seed_one),
+    };
+    require!(opponent_seed != Some(seed), ZkPokerError::DuplicateSeedCommitment);
+
     // Store revealed seed
     match seat {
         0 => {
             hand.seed_one = seed;
-            hand.p1_seed_revealed = true;
+            hand.set_p1_seed_revealed(true);
         }
         1 => {
             hand.seed_two = seed;
-            hand.p2_seed_revealed = true;
+            hand.set_p2_seed_revealed(true);
         }
         _ => {}
     }
@@ -306,11 +695,19 @@ pub fn handle_reveal_seed(ctx: Context<RevealSeed>, seed: [u8; 32]) -> Result<()
     msg!("Player {} (seat {}) revealed seed", player, seat);
 
     // Check if both revealed, compute deck seed and advance stage
-    if hand.p1_seed_revealed && hand.p2_seed_revealed {
+    if hand.p1_seed_revealed() && hand.p2_seed_revealed() {
         // Compute deck_seed = keccak(seed_1 || seed_2)
         let deck_seed = keccak::hashv(&[&hand.seed_one, &hand.seed_two]);
         hand.deck_seed = deck_seed.to_bytes();
 
+        // Neither seat could steer the draw: each seed was committed before
+        // the other was revealed
+        if table.draws_opening_button() {
+            table.button = keccak::hashv(&[OPENING_BUTTON_DOMAIN, &hand.deck_seed]).to_bytes()[0] & 1;
+            msg!("Opening button drawn: seat {}", table.button);
+            post_blinds(table, hand)?;
+        }
+
         hand.stage = HandStage::CardCommit;
         msg!("Both seeds revealed, deck_seed computed");
         msg!("Advancing to CardCommit stage");
@@ -319,6 +716,41 @@ pub fn handle_reveal_seed(ctx: Context<RevealSeed>, seed: [u8; 32]) -> Result<()
     Ok(())
 }
 
+/// Verify deal handler. Either player proves the deck shuffled from
+/// deck_seed is a permutation of all 52 cards; hole card commitments are
+/// only accepted once it is.
+pub fn handle_verify_deal(ctx: Context<VerifyDeal>) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+
+    // Verify player was dealt into this hand and still holds the seat
+    let seat = hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
+
+    require!(hand.stage == HandStage::CardCommit, ZkPokerError::InvalidStage);
+    require!(!hand.deal_verified(), ZkPokerError::DealAlreadyVerified);
+
+    // Verify ZK proof via CPI to DEAL verifier program
+    hand.deck_root = verify_deal(
+        &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.deal_verifier,
+        ctx.accounts.proof_buffer.get_proof_data()?,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
+        &hand.deck_seed,
+    )?;
+    hand.set_deal_verified(true);
+
+    let clock = Clock::get()?;
+    hand.last_action_at = clock.unix_timestamp;
+
+    msg!("✓ Deck shuffle verified by seat {}", seat);
+
+    Ok(())
+}
+
 /// Commit hole cards handler (with ZK proof verification from buffer)
 pub fn handle_commit_hole_cards(
     ctx: Context<CommitHoleCards>,
@@ -329,16 +761,20 @@ pub fn handle_commit_hole_cards(
     let player = ctx.accounts.player.key();
     let proof_buffer = &ctx.accounts.proof_buffer;
 
-    // Verify player is at table
-    let seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // Verify player was dealt into this hand and still holds the seat
+    let seat = hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
 
     // Verify stage
     require!(hand.stage == HandStage::CardCommit, ZkPokerError::InvalidStage);
+    require!(hand.deal_verified(), ZkPokerError::DealNotVerified);
 
     // Check if player already committed
     let already_committed = match seat {
-        0 => hand.p1_cards_committed,
-        1 => hand.p2_cards_committed,
+        0 => hand.p1_cards_committed(),
+        1 => hand.p2_cards_committed(),
         _ => return Err(ZkPokerError::PlayerNotAtTable.into()),
     };
     require!(!already_committed, ZkPokerError::CardsAlreadyCommitted);
@@ -346,14 +782,25 @@ pub fn handle_commit_hole_cards(
     // Get proof data from buffer
     let proof_data = proof_buffer.get_proof_data()?;
 
+    // Each seat is dealt fixed deck positions; the proof must be for ours
+    let positions = match seat {
+        0 => [P1_CARD_1_POS, P1_CARD_2_POS],
+        _ => [P2_CARD_1_POS, P2_CARD_2_POS],
+    };
+
     // Verify ZK proof via CPI to DECK verifier program
     // The proof verifies:
-    // 1. Cards are at correct positions (0,1 for P1 or 2,3 for P2)
-    // 2. Cards derived from deck_seed correctly
+    // 1. Cards are at this seat's positions of the dealt deck
+    // 2. The deck is the one proven by verify_deal (deck root)
     // 3. Commitments are hash(card, salt)
     verify_hole_card_commitments(
         &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.deck_verifier,
         proof_data,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
+        &hand.deck_root,
+        positions,
+        &commitments,
     )?;
 
     msg!("✓ Hole card commitments verified for seat {}", seat);
@@ -362,11 +809,11 @@ pub fn handle_commit_hole_cards(
     match seat {
         0 => {
             hand.p1_hole_commits = commitments;
-            hand.p1_cards_committed = true;
+            hand.set_p1_cards_committed(true);
         }
         1 => {
             hand.p2_hole_commits = commitments;
-            hand.p2_cards_committed = true;
+            hand.set_p2_cards_committed(true);
         }
         _ => {}
     }
@@ -378,21 +825,24 @@ pub fn handle_commit_hole_cards(
     msg!("Player {} (seat {}) committed hole cards", player, seat);
 
     // Check if both committed, advance to preflop
-    if hand.p1_cards_committed && hand.p2_cards_committed {
+    if hand.p1_cards_committed() && hand.p2_cards_committed() {
         hand.stage = HandStage::Preflop;
 
         // Reset street betting state
         hand.reset_street();
 
-        // In heads-up, small blind (button) acts first preflop
-        // But blinds are already posted, so action is on SB to call/raise/fold
-        let sb_seat = table.small_blind_seat();
-        hand.action_on = sb_seat;
+        // Blinds are already posted, so the table's first-to-act seat
+        // (the small blind in standard heads-up) calls, raises or folds.
+        // A straddling small blind acts last instead, so the big blind goes first.
+        hand.action_on = if hand.straddle_amount > 0 {
+            table.big_blind_seat()
+        } else {
+            table.first_to_act(HandStage::Preflop)
+        };
 
-        // Restore the bet amounts (blinds were already posted in start_hand)
-        hand.p1_bet_this_street = if sb_seat == 0 { table.small_blind } else { table.big_blind };
-        hand.p2_bet_this_street = if sb_seat == 0 { table.big_blind } else { table.small_blind };
-        hand.current_bet = table.big_blind;
+        // Restore the bet amounts (blinds and any straddle were posted before the deal)
+        hand.bet_this_street = hand.total_bet;
+        hand.current_bet = hand.total_bet[0].max(hand.total_bet[1]);
 
         msg!("Both cards committed, advancing to Preflop");
         msg!("Action on seat {}", hand.action_on);
@@ -401,29 +851,58 @@ pub fn handle_commit_hole_cards(
     Ok(())
 }
 
-/// Timeout handler
-pub fn handle_timeout(ctx: Context<Timeout>) -> Result<()> {
+/// Straddle handler. The small blind (the button, unless the table has the
+/// button post the big blind) raises to twice the big blind before hole
+/// cards are dealt, and acts last preflop.
+pub fn handle_straddle(ctx: Context<Straddle>) -> Result<()> {
     let table = &mut ctx.accounts.table;
     let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
 
-    // Verify hand is not complete
-    require!(hand.stage != HandStage::Complete, ZkPokerError::HandAlreadyComplete);
+    // Verify player was dealt into this hand and still holds the seat
+    let seat = hand.player_seat(table, &player)?;
 
-    // Get timeout setting
-    let timeout = table.action_timeout;
+    require!(table.straddle_allowed, ZkPokerError::StraddleNotAllowed);
+    require!(hand.stage.is_setup_stage(), ZkPokerError::InvalidStage);
+    // The blinds (and so the small blind seat) wait for the opening draw
+    require!(
+        !table.draws_opening_button() || hand.stage == HandStage::CardCommit,
+        ZkPokerError::InvalidStage
+    );
+    require!(seat == table.small_blind_seat(), ZkPokerError::NotYourTurn);
+    require!(hand.straddle_amount == 0, ZkPokerError::AlreadyStraddled);
 
-    // Check if timeout has occurred
-    let clock = Clock::get()?;
-    let elapsed = clock.unix_timestamp - hand.last_action_at;
-    require!(elapsed > timeout, ZkPokerError::NoTimeout);
+    // Top the small blind up to the straddle
+    let straddle = table.big_blind.checked_mul(2).ok_or(ZkPokerError::ArithmeticOverflow)?;
+    let additional = straddle.saturating_sub(hand.get_total_bet(seat));
+    require!(table.get_chips(seat) >= additional, ZkPokerError::InsufficientChips);
+
+    let posted = table.remove_chips(seat, additional);
+    hand.add_bet(seat, posted)?;
+    hand.current_bet = straddle;
+    hand.straddle_amount = straddle;
+
+    msg!("Seat {} straddles to {}", seat, straddle);
+    msg!("Pot: {}", hand.pot);
+
+    hand.check_chip_conservation(table)
+}
+
+/// Timeout handler
+pub fn handle_timeout(ctx: Context<Timeout>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+
+    // Verify the hand has not already been settled
+    hand.require_not_complete()?;
 
     // Determine who timed out based on stage
     let timed_out_seat = match hand.stage {
         HandStage::SeedCommit => {
             // Whoever hasn't committed
-            if !hand.p1_seed_committed {
+            if !hand.p1_seed_committed() {
                 0
-            } else if !hand.p2_seed_committed {
+            } else if !hand.p2_seed_committed() {
                 1
             } else {
                 return Err(ZkPokerError::NoTimeout.into());
@@ -431,9 +910,9 @@ pub fn handle_timeout(ctx: Context<Timeout>) -> Result<()> {
         }
         HandStage::SeedReveal => {
             // Whoever hasn't revealed
-            if !hand.p1_seed_revealed {
+            if !hand.p1_seed_revealed() {
                 0
-            } else if !hand.p2_seed_revealed {
+            } else if !hand.p2_seed_revealed() {
                 1
             } else {
                 return Err(ZkPokerError::NoTimeout.into());
@@ -441,9 +920,9 @@ pub fn handle_timeout(ctx: Context<Timeout>) -> Result<()> {
         }
         HandStage::CardCommit => {
             // Whoever hasn't committed cards
-            if !hand.p1_cards_committed {
+            if !hand.p1_cards_committed() {
                 0
-            } else if !hand.p2_cards_committed {
+            } else if !hand.p2_cards_committed() {
                 1
             } else {
                 return Err(ZkPokerError::NoTimeout.into());
@@ -454,10 +933,13 @@ pub fn handle_timeout(ctx: Context<Timeout>) -> Result<()> {
             hand.action_on
         }
         HandStage::Showdown => {
-            // Whoever hasn't revealed
-            if !hand.p1_revealed {
+            // The seat due to show first stalls the showdown until it does;
+            // after that, whoever has neither revealed nor mucked
+            if hand.first_reveal_ordered() {
+                hand.action_on
+            } else if !hand.has_revealed(0) && !hand.has_mucked(0) {
                 0
-            } else if !hand.p2_revealed {
+            } else if !hand.has_revealed(1) && !hand.has_mucked(1) {
                 1
             } else {
                 return Err(ZkPokerError::NoTimeout.into());
@@ -468,24 +950,113 @@ pub fn handle_timeout(ctx: Context<Timeout>) -> Result<()> {
         }
     };
 
-    // Award pot to non-timed-out player
+    // Keepers have no seat; a seated caller may only time out the opponent
+    if let Some(caller_seat) = hand.get_seat(&ctx.accounts.caller.key()) {
+        require!(caller_seat != timed_out_seat, ZkPokerError::CannotTimeoutSelf);
+    }
+
+    // Betting decisions may run into the seat's time bank before timing out
+    let time_bank = if hand.stage.is_betting_stage() {
+        table.get_time_bank(timed_out_seat)
+    } else {
+        0
+    };
+    let timeout = table.stage_timeout(hand.stage, timed_out_seat);
+
+    // Check if timeout has occurred
+    let clock = Clock::get()?;
+    let elapsed = clock.unix_timestamp - hand.last_action_at;
+    require!(elapsed > timeout, ZkPokerError::NoTimeout);
+
+    // The timed-out seat has burned through its whole bank
+    if time_bank > 0 {
+        table.set_time_bank(timed_out_seat, 0);
+    }
+
+    // Disconnect protection: a pending betting decision is made for the
+    // seat (check if it can, fold to a bet) instead of forfeiting the pot
+    let deciding = hand.stage.is_betting_stage() && !hand.fast_showdown() && hand.street_revealed();
+    if table.timeout_policy == TimeoutPolicy::AutoCheckFold && deciding {
+        if hand.current_bet == hand.get_bet_this_street(timed_out_seat) {
+            hand.set_acted_this_street(timed_out_seat);
+            hand.switch_action();
+            msg!("Seat {} timed out and checks", timed_out_seat);
+        } else {
+            hand.set_folded(timed_out_seat);
+            msg!("Seat {} timed out and folds", timed_out_seat);
+        }
+        hand.last_action_at = clock.unix_timestamp;
+
+        return handle_street_transition(
+            table,
+            hand,
+            &mut ctx.accounts.table_stats,
+            [&mut ctx.accounts.player_one_profile, &mut ctx.accounts.player_two_profile],
+        );
+    }
+
+    // The non-timed-out player wins
     let winner_seat = hand.other_seat(timed_out_seat);
-    hand.winner = winner_seat;
+    hand.winner = HandWinner::from_seat(winner_seat);
     hand.set_folded(timed_out_seat);
 
-    // Transfer pot to winner
-    table.add_chips(winner_seat, hand.pot);
-    hand.pot = 0;
+    let pot = if hand.stage.is_setup_stage() {
+        // Nobody has acted yet, so the pot is only blinds: return them to
+        // their owners rather than handing the big blind's extra chips to
+        // whoever didn't time out
+        refund_bets(table, hand);
+        0
+    } else {
+        // Transfer pot to winner
+        table.add_chips(winner_seat, hand.pot);
+        hand.pot
+    };
+    ctx.accounts.table_stats.record_hand(hand, pot, 0);
+    ctx.accounts.player_one_profile.record_hand(table.get_player(0), hand, 0, pot);
+    ctx.accounts.player_two_profile.record_hand(table.get_player(1), hand, 1, pot);
 
     // Complete the hand
-    hand.stage = HandStage::Complete;
-    table.status = TableStatus::Between;
-    table.current_hand = None;
-    table.increment_hands_played();
-    table.rotate_button();
+    complete_hand(table, hand, pot, 0, CompletionReason::Timeout);
 
     msg!("Seat {} timed out", timed_out_seat);
     msg!("Seat {} wins pot", winner_seat);
 
+    hand.check_chip_conservation(table)
+}
+
+/// Return each seat's posted chips to its stack and empty the pot, so the
+/// hand settles net-zero for both players
+pub(crate) fn refund_bets(table: &mut Table, hand: &mut Hand) {
+    for seat in 0..2 {
+        table.add_chips(seat, hand.get_total_bet(seat));
+    }
+    hand.total_bet = [0; 2];
+    hand.pot = 0;
+}
+
+/// Close hand handler. Only settled hands can be closed; hand numbers only
+/// grow, so a closed hand's address is never reused by a later hand.
+pub fn handle_close_hand(ctx: Context<CloseHand>) -> Result<()> {
+    let hand = &ctx.accounts.hand;
+
+    require!(
+        hand.stage == HandStage::Complete && hand.pot_claimed(),
+        ZkPokerError::HandNotComplete
+    );
+
+    // Split-rent tables return half to the player who did not start the
+    // hand; `close` sends the rest to the starter
+    if ctx.accounts.table.split_hand_rent {
+        let co_payer = ctx.accounts.co_payer.as_ref().ok_or(ZkPokerError::CoPayerRequired)?;
+        let expected = if hand.rent_payer == hand.player_one { hand.player_two } else { hand.player_one };
+        require_keys_eq!(co_payer.key(), expected, ZkPokerError::CoPayerRequired);
+
+        let half = hand.to_account_info().lamports() / 2;
+        hand.sub_lamports(half)?;
+        co_payer.add_lamports(half)?;
+    }
+
+    msg!("Hand {} closed", hand.hand_number);
+
     Ok(())
 }
@@ -5,6 +5,7 @@ pub mod betting;
 pub mod reveal;
 pub mod showdown;
 pub mod proof_buffer;
+pub mod view;
 
 pub use admin::*;
 pub use table::*;
@@ -13,3 +14,4 @@ pub use betting::*;
 pub use reveal::*;
 pub use showdown::*;
 pub use proof_buffer::*;
+pub use view::*;
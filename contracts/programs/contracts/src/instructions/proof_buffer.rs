@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{ProofBuffer, ProofType};
+use crate::state::{Hand, ProofBuffer, ProofType};
 use crate::errors::ZkPokerError;
 
 /// Initialize a proof buffer for uploading a ZK proof
@@ -9,9 +9,9 @@ pub struct InitProofBuffer<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
 
-    /// The hand this proof is for
-    /// CHECK: Validated by seeds constraint
-    pub hand: AccountInfo<'info>,
+    /// The hand this proof is for (counts the player's open buffers)
+    #[account(mut)]
+    pub hand: Account<'info, Hand>,
 
     /// Proof buffer PDA
     #[account(
@@ -44,6 +44,38 @@ pub struct UploadProofChunk<'info> {
     pub proof_buffer: Account<'info, ProofBuffer>,
 }
 
+/// Resize a proof buffer whose initial size was wrong
+#[derive(Accounts)]
+#[instruction(new_size: u16)]
+pub struct ResizeProofBuffer<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = player @ ZkPokerError::Unauthorized,
+        constraint = !proof_buffer.complete @ ZkPokerError::BufferAlreadyComplete,
+        realloc = ProofBuffer::space(new_size),
+        realloc::payer = player,
+        realloc::zero = false
+    )]
+    pub proof_buffer: Account<'info, ProofBuffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Clear a proof buffer for re-upload, e.g. after its proof failed to verify
+#[derive(Accounts)]
+pub struct ResetProofBuffer<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = player @ ZkPokerError::Unauthorized
+    )]
+    pub proof_buffer: Account<'info, ProofBuffer>,
+}
+
 /// Close a proof buffer and reclaim rent (after verification)
 #[derive(Accounts)]
 pub struct CloseProofBuffer<'info> {
@@ -56,6 +88,14 @@ pub struct CloseProofBuffer<'info> {
         has_one = player @ ZkPokerError::Unauthorized
     )]
     pub proof_buffer: Account<'info, ProofBuffer>,
+
+    /// The buffer's hand, to free its open-buffer slot; omitted once the
+    /// hand account itself has been closed
+    #[account(
+        mut,
+        constraint = hand.key() == proof_buffer.hand @ ZkPokerError::BufferMismatch
+    )]
+    pub hand: Option<Account<'info, Hand>>,
 }
 
 /// Initialize a proof buffer
@@ -65,16 +105,29 @@ pub fn handle_init_proof_buffer(
     proof_size: u16,
 ) -> Result<()> {
     let buffer = &mut ctx.accounts.proof_buffer;
-    
+    let hand = &mut ctx.accounts.hand;
+
+    ProofBuffer::validate_size(proof_size)?;
+
     let pt = match proof_type {
         0 => ProofType::Deck,
         1 => ProofType::Reveal,
         2 => ProofType::Showdown,
+        3 => ProofType::Deal,
         _ => return Err(ZkPokerError::InvalidProofType.into()),
     };
 
+    // Bound how many buffers one seat can leave open in a hand
+    hand.open_buffer(&ctx.accounts.player.key())?;
+
+    // Only allow buffers for proofs the hand can currently consume
+    require!(
+        pt.is_valid_for_stage(hand.stage),
+        ZkPokerError::InvalidProofType
+    );
+
     buffer.init(
-        ctx.accounts.hand.key(),
+        hand.key(),
         ctx.accounts.player.key(),
         pt,
         proof_size,
@@ -106,8 +159,41 @@ pub fn handle_upload_proof_chunk(
     Ok(())
 }
 
+/// Resize a proof buffer (extra rent is charged to or refunded to the player)
+pub fn handle_resize_proof_buffer(
+    ctx: Context<ResizeProofBuffer>,
+    new_size: u16,
+) -> Result<()> {
+    let buffer = &mut ctx.accounts.proof_buffer;
+
+    ProofBuffer::validate_size(new_size)?;
+
+    // Never drop bytes that were already uploaded
+    require!(new_size >= buffer.uploaded, ZkPokerError::InvalidBufferSize);
+
+    let old_size = buffer.size;
+    buffer.resize(new_size);
+
+    msg!("Proof buffer resized: {} -> {} bytes", old_size, new_size);
+    Ok(())
+}
+
+/// Reset a proof buffer. A failed verification reverts without closing the
+/// buffer, so the player can clear it and upload a corrected proof into the
+/// same account without reallocating.
+pub fn handle_reset_proof_buffer(ctx: Context<ResetProofBuffer>) -> Result<()> {
+    let buffer = &mut ctx.accounts.proof_buffer;
+    buffer.reset();
+
+    msg!("Proof buffer reset: {} bytes for {:?}", buffer.size, buffer.proof_type);
+    Ok(())
+}
+
 /// Close a proof buffer and reclaim rent
-pub fn handle_close_proof_buffer(_ctx: Context<CloseProofBuffer>) -> Result<()> {
+pub fn handle_close_proof_buffer(ctx: Context<CloseProofBuffer>) -> Result<()> {
+    if let Some(hand) = ctx.accounts.hand.as_mut() {
+        hand.release_buffer(&ctx.accounts.player.key());
+    }
     msg!("Proof buffer closed, rent reclaimed");
     Ok(())
 }
@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 use crate::state::{GlobalConfig, Table, Hand, HandStage, ProofBuffer, ProofType};
 use crate::errors::ZkPokerError;
 use crate::constants::{GLOBAL_SEED, TABLE_SEED, HAND_SEED};
-use crate::utils::verify_community_cards;
+use crate::utils::{verify_community_cards, hand_binding};
+use crate::instructions::betting::emit_action_timer;
 
 /// Reveal community cards context (proof from buffer)
 #[derive(Accounts)]
@@ -31,11 +32,11 @@ pub struct RevealCommunity<'info> {
     )]
     pub hand: Account<'info, Hand>,
 
-    /// Proof buffer containing the ZK proof data
+    /// Proof buffer containing the ZK proof data, uploaded by the signer
     #[account(
         mut,
         close = player,
-        has_one = player @ ZkPokerError::Unauthorized,
+        has_one = player @ ZkPokerError::BufferNotOwned,
         constraint = proof_buffer.hand == hand.key() @ ZkPokerError::BufferMismatch,
         constraint = proof_buffer.proof_type == ProofType::Reveal @ ZkPokerError::BufferMismatch,
         constraint = proof_buffer.complete @ ZkPokerError::BufferNotComplete
@@ -47,6 +48,47 @@ pub struct RevealCommunity<'info> {
     pub verifier_program: AccountInfo<'info>,
 }
 
+/// Agree to run the remaining board twice
+#[derive(Accounts)]
+pub struct ConsentRunTwice<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+}
+
+/// Advance an all-in runout past a street whose cards are revealed
+/// (callable by anyone, so a keeper can move a stalled hand along)
+#[derive(Accounts)]
+pub struct AdvanceRunout<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+}
+
 /// Reveal flop handler (3 cards, proof from buffer)
 pub fn handle_reveal_flop(
     ctx: Context<RevealCommunity>,
@@ -57,19 +99,25 @@ pub fn handle_reveal_flop(
     let player = ctx.accounts.player.key();
     let proof_buffer = &ctx.accounts.proof_buffer;
 
-    // Verify player is at table
-    let _seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // The buffer's owner (the signer, see `has_one`) must hold a seat in
+    // this hand; a buffer is never consumed on another player's behalf
+    hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
 
-    // Verify stage - must be in Flop stage (waiting for reveal)
+    // Verify stage - the previous betting round has closed and flop
+    // betting waits on this reveal
     require!(hand.stage == HandStage::Flop, ZkPokerError::InvalidStage);
 
     // Verify flop not already revealed
-    require!(!hand.flop_revealed, ZkPokerError::FlopAlreadyRevealed);
+    require!(!hand.flop_revealed(), ZkPokerError::FlopAlreadyRevealed);
 
     // Validate card indices
     for card in &cards {
-        require!(*card < 52, ZkPokerError::InvalidCardIndex);
+        require!(*card < table.deck_size, ZkPokerError::InvalidCardIndex);
     }
+    require_distinct(&cards)?;
 
     // Get proof data from buffer
     let proof_data = proof_buffer.get_proof_data()?;
@@ -77,16 +125,19 @@ pub fn handle_reveal_flop(
     // Verify ZK proof that cards are at correct positions
     verify_community_cards(
         &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.reveal_verifier,
         proof_data,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
     )?;
 
     // Store revealed flop
     hand.flop = cards;
-    hand.flop_revealed = true;
+    hand.set_flop_revealed(true);
 
     // Update timestamp
     let clock = Clock::get()?;
     hand.last_action_at = clock.unix_timestamp;
+    emit_action_timer(table, hand);
 
     msg!("Flop revealed: [{}, {}, {}]", cards[0], cards[1], cards[2]);
 
@@ -103,20 +154,26 @@ pub fn handle_reveal_turn(
     let player = ctx.accounts.player.key();
     let proof_buffer = &ctx.accounts.proof_buffer;
 
-    // Verify player is at table
-    let _seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // The buffer's owner (the signer, see `has_one`) must hold a seat in
+    // this hand; a buffer is never consumed on another player's behalf
+    hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
 
-    // Verify stage - must be in Turn stage (waiting for reveal)
+    // Verify stage - the previous betting round has closed and turn
+    // betting waits on this reveal
     require!(hand.stage == HandStage::Turn, ZkPokerError::InvalidStage);
 
     // Verify flop was revealed (must reveal in order)
-    require!(hand.flop_revealed, ZkPokerError::RevealOutOfOrder);
+    require!(hand.flop_revealed(), ZkPokerError::RevealOutOfOrder);
 
     // Verify turn not already revealed
-    require!(!hand.turn_revealed, ZkPokerError::TurnAlreadyRevealed);
+    require!(!hand.turn_revealed(), ZkPokerError::TurnAlreadyRevealed);
 
     // Validate card index
-    require!(card < 52, ZkPokerError::InvalidCardIndex);
+    require!(card < table.deck_size, ZkPokerError::InvalidCardIndex);
+    require!(!hand.flop.contains(&card), ZkPokerError::DuplicateCommunityCard);
 
     // Get proof data from buffer
     let proof_data = proof_buffer.get_proof_data()?;
@@ -124,16 +181,19 @@ pub fn handle_reveal_turn(
     // Verify ZK proof that card is at correct position
     verify_community_cards(
         &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.reveal_verifier,
         proof_data,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
     )?;
 
     // Store revealed turn
     hand.turn = card;
-    hand.turn_revealed = true;
+    hand.set_turn_revealed(true);
 
     // Update timestamp
     let clock = Clock::get()?;
     hand.last_action_at = clock.unix_timestamp;
+    emit_action_timer(table, hand);
 
     msg!("Turn revealed: {}", card);
 
@@ -150,20 +210,29 @@ pub fn handle_reveal_river(
     let player = ctx.accounts.player.key();
     let proof_buffer = &ctx.accounts.proof_buffer;
 
-    // Verify player is at table
-    let _seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // The buffer's owner (the signer, see `has_one`) must hold a seat in
+    // this hand; a buffer is never consumed on another player's behalf
+    hand.player_seat(table, &player)?;
 
-    // Verify stage - must be in River stage (waiting for reveal)
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
+
+    // Verify stage - the previous betting round has closed and river
+    // betting waits on this reveal
     require!(hand.stage == HandStage::River, ZkPokerError::InvalidStage);
 
     // Verify turn was revealed (must reveal in order)
-    require!(hand.turn_revealed, ZkPokerError::RevealOutOfOrder);
+    require!(hand.turn_revealed(), ZkPokerError::RevealOutOfOrder);
 
     // Verify river not already revealed
-    require!(!hand.river_revealed, ZkPokerError::RiverAlreadyRevealed);
+    require!(!hand.river_revealed(), ZkPokerError::RiverAlreadyRevealed);
 
     // Validate card index
-    require!(card < 52, ZkPokerError::InvalidCardIndex);
+    require!(card < table.deck_size, ZkPokerError::InvalidCardIndex);
+    require!(
+        !hand.flop.contains(&card) && card != hand.turn,
+        ZkPokerError::DuplicateCommunityCard
+    );
 
     // Get proof data from buffer
     let proof_data = proof_buffer.get_proof_data()?;
@@ -171,18 +240,226 @@ pub fn handle_reveal_river(
     // Verify ZK proof that card is at correct position
     verify_community_cards(
         &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.reveal_verifier,
         proof_data,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
     )?;
 
     // Store revealed river
     hand.river = card;
-    hand.river_revealed = true;
+    hand.set_river_revealed(true);
 
     // Update timestamp
     let clock = Clock::get()?;
     hand.last_action_at = clock.unix_timestamp;
+    emit_action_timer(table, hand);
 
     msg!("River revealed: {}", card);
 
     Ok(())
 }
+
+/// Reveal all remaining community cards at once during an all-in runout
+/// (one REVEAL proof covering all five positions, proof from buffer)
+pub fn handle_reveal_all_community(
+    ctx: Context<RevealCommunity>,
+    cards: [u8; 5],
+) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+    let proof_buffer = &ctx.accounts.proof_buffer;
+
+    // The buffer's owner (the signer, see `has_one`) must hold a seat in
+    // this hand; a buffer is never consumed on another player's behalf
+    hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
+
+    // Only when no more betting can happen
+    require!(hand.is_all_in_runout(), ZkPokerError::NotAllInRunout);
+
+    // Validate card indices
+    for card in cards.iter() {
+        require!(*card < table.deck_size, ZkPokerError::InvalidCardIndex);
+    }
+    require_distinct(&cards)?;
+
+    // Cards revealed on earlier streets must not change
+    if hand.flop_revealed() {
+        require!(cards[..3] == hand.flop, ZkPokerError::InvalidCardIndex);
+    }
+    if hand.turn_revealed() {
+        require!(cards[3] == hand.turn, ZkPokerError::InvalidCardIndex);
+    }
+    require!(!hand.river_revealed(), ZkPokerError::RiverAlreadyRevealed);
+
+    // When running it twice, the second board goes first and its own cards
+    // come from different deck positions
+    if hand.run_count == 2 {
+        require!(hand.second_board_revealed(), ZkPokerError::SecondBoardNotRevealed);
+        let shared = hand.second_board_shared as usize;
+        let second_run = &hand.second_board[shared..];
+        require!(
+            cards[shared..].iter().all(|card| !second_run.contains(card)),
+            ZkPokerError::DuplicateCommunityCard
+        );
+    }
+
+    // Get proof data from buffer
+    let proof_data = proof_buffer.get_proof_data()?;
+
+    // Verify ZK proof that all five cards are at the correct positions
+    verify_community_cards(
+        &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.reveal_verifier,
+        proof_data,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
+    )?;
+
+    // Store the full board
+    hand.flop = [cards[0], cards[1], cards[2]];
+    hand.turn = cards[3];
+    hand.river = cards[4];
+    hand.set_flop_revealed(true);
+    hand.set_turn_revealed(true);
+    hand.set_river_revealed(true);
+
+    // Nothing left to bet, go straight to showdown
+    hand.stage = HandStage::Showdown;
+    hand.reset_street();
+
+    // Update timestamp
+    let clock = Clock::get()?;
+    hand.last_action_at = clock.unix_timestamp;
+    emit_action_timer(table, hand);
+
+    msg!("Board run out: {:?} {} {}", hand.flop, hand.turn, hand.river);
+
+    Ok(())
+}
+
+/// Advance runout handler. With betting closed by an all-in, no betting
+/// action can move the hand past a revealed street, so anyone may step it
+/// forward; once the river is out the hand goes to showdown.
+pub fn handle_advance_runout(ctx: Context<AdvanceRunout>) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+
+    // Only when no more betting can happen
+    require!(hand.is_all_in_runout(), ZkPokerError::NotAllInRunout);
+
+    // The current street's cards must already be on the board
+    require!(hand.street_revealed(), ZkPokerError::StreetNotRevealed);
+
+    if let Some(next_stage) = hand.stage.next_betting_stage() {
+        hand.stage = next_stage;
+        hand.reset_street();
+    }
+
+    // Update timestamp
+    let clock = Clock::get()?;
+    hand.last_action_at = clock.unix_timestamp;
+    emit_action_timer(table, hand);
+
+    msg!("Runout advanced to {:?}", hand.stage);
+
+    Ok(())
+}
+
+/// Consent to run it twice handler. Once both players agree, the rest of
+/// the board is dealt twice and each run decides half of the pot.
+pub fn handle_consent_run_twice(ctx: Context<ConsentRunTwice>) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+
+    // Verify player was dealt into this hand
+    let seat = hand.player_seat(table, &player)?;
+
+    // Only when no more betting can happen and there are cards left to deal
+    require!(hand.is_all_in_runout(), ZkPokerError::NotAllInRunout);
+    require!(!hand.river_revealed(), ZkPokerError::RiverAlreadyRevealed);
+    require!(!hand.has_run_twice_consent(seat), ZkPokerError::RunTwiceAlreadyConsented);
+
+    hand.set_run_twice_consent(seat);
+    msg!("Seat {} agreed to run it twice", seat);
+
+    if hand.has_run_twice_consent(0) && hand.has_run_twice_consent(1) {
+        hand.run_count = 2;
+        msg!("Running it twice");
+    }
+
+    Ok(())
+}
+
+/// Reveal the second board when running it twice (proof from buffer)
+///
+/// `cards` is the full second board. Community cards already revealed on the
+/// first board are shared by both runs; the rest come from the deck positions
+/// after the first board (proved with a REVEAL offset of 5).
+pub fn handle_reveal_second_board(
+    ctx: Context<RevealCommunity>,
+    cards: [u8; 5],
+) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+    let proof_buffer = &ctx.accounts.proof_buffer;
+
+    // The buffer's owner (the signer, see `has_one`) must hold a seat in
+    // this hand; a buffer is never consumed on another player's behalf
+    hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
+
+    require!(hand.run_count == 2, ZkPokerError::RunTwiceNotAgreed);
+    require!(hand.is_all_in_runout(), ZkPokerError::NotAllInRunout);
+    require!(!hand.second_board_revealed(), ZkPokerError::SecondBoardAlreadyRevealed);
+
+    // Validate card indices
+    for card in cards.iter() {
+        require!(*card < table.deck_size, ZkPokerError::InvalidCardIndex);
+    }
+    require_distinct(&cards)?;
+
+    // Cards already on the first board are shared by the second run
+    let shared = hand.community_cards_revealed();
+    let first_board = [hand.flop[0], hand.flop[1], hand.flop[2], hand.turn, hand.river];
+    let shared_len = shared as usize;
+    require!(cards[..shared_len] == first_board[..shared_len], ZkPokerError::InvalidCardIndex);
+
+    // Get proof data from buffer
+    let proof_data = proof_buffer.get_proof_data()?;
+
+    // Verify ZK proof that the new cards are at the second board positions
+    verify_community_cards(
+        &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.reveal_verifier,
+        proof_data,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
+    )?;
+
+    hand.second_board = cards;
+    hand.second_board_shared = shared;
+    hand.set_second_board_revealed(true);
+
+    // Update timestamp
+    let clock = Clock::get()?;
+    hand.last_action_at = clock.unix_timestamp;
+    emit_action_timer(table, hand);
+
+    msg!("Second board: {:?} ({} shared)", cards, shared);
+
+    Ok(())
+}
+
+/// Reject a set of community cards containing the same card twice
+fn require_distinct(cards: &[u8]) -> Result<()> {
+    for (i, card) in cards.iter().enumerate() {
+        require!(!cards[i + 1..].contains(card), ZkPokerError::DuplicateCommunityCard);
+    }
+    Ok(())
+}
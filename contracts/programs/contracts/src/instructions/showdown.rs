@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::state::{GlobalConfig, Table, Hand, TableStatus, HandStage, ProofBuffer, ProofType};
+use crate::state::{GlobalConfig, Table, Hand, HandStage, HandWinner, CompletionReason, ProofBuffer, ProofType, TableStats, PlayerProfile};
 use crate::errors::ZkPokerError;
-use crate::constants::{GLOBAL_SEED, TABLE_SEED, HAND_SEED};
-use crate::utils::verify_hand_reveal;
+use crate::constants::{GLOBAL_SEED, TABLE_SEED, HAND_SEED, TABLE_STATS_SEED, PROFILE_SEED, MIN_HAND_RANK, MAX_HAND_RANK};
+use crate::utils::{verify_showdown_rank, hand_binding};
+use crate::instructions::table::{complete_hand, take_rake};
 
 /// Reveal hand at showdown (proof from buffer)
 #[derive(Accounts)]
@@ -47,9 +48,92 @@ pub struct RevealHand<'info> {
     pub verifier_program: AccountInfo<'info>,
 }
 
+/// Challenge a claimed hand rank during the dispute window (proof from buffer)
+#[derive(Accounts)]
+pub struct ChallengeShowdown<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+
+    /// Proof buffer containing the ZK proof data
+    #[account(
+        mut,
+        close = player,
+        has_one = player @ ZkPokerError::Unauthorized,
+        constraint = proof_buffer.hand == hand.key() @ ZkPokerError::BufferMismatch,
+        constraint = proof_buffer.proof_type == ProofType::Showdown @ ZkPokerError::BufferMismatch,
+        constraint = proof_buffer.complete @ ZkPokerError::BufferNotComplete
+    )]
+    pub proof_buffer: Account<'info, ProofBuffer>,
+
+    /// CHECK: SHOWDOWN verifier program - verified in verification function
+    #[account(constraint = verifier_program.key() == global_config.showdown_verifier @ ZkPokerError::ProofVerificationFailed)]
+    pub verifier_program: AccountInfo<'info>,
+}
+
+/// Propose or confirm a result both players agree on (instead of showdown proofs)
+#[derive(Accounts)]
+pub struct AgreeResult<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+}
+
+/// Muck (concede) a hand at showdown without revealing it
+#[derive(Accounts)]
+pub struct MuckHand<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+}
+
 /// Claim pot after showdown
 #[derive(Accounts)]
 pub struct ClaimPot<'info> {
+    #[account(mut)]
     pub player: Signer<'info>,
 
     #[account(
@@ -66,6 +150,38 @@ pub struct ClaimPot<'info> {
         constraint = hand.table == table.key()
     )]
     pub hand: Account<'info, Hand>,
+
+    /// Lifetime table statistics (created when the first hand completes)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = TableStats::LEN,
+        seeds = [TABLE_STATS_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub table_stats: Account<'info, TableStats>,
+
+    /// Lifetime profile of the player in seat 0
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [PROFILE_SEED, table.get_player(0).as_ref()],
+        bump
+    )]
+    pub player_one_profile: Account<'info, PlayerProfile>,
+
+    /// Lifetime profile of the player in seat 1
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [PROFILE_SEED, table.get_player(1).as_ref()],
+        bump
+    )]
+    pub player_two_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
 }
 
 /// Reveal hand handler (proof from buffer)
@@ -78,8 +194,11 @@ pub fn handle_reveal_hand(
     let player = ctx.accounts.player.key();
     let proof_buffer = &ctx.accounts.proof_buffer;
 
-    // Verify player is at table
-    let seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // Verify player was dealt into this hand
+    let seat = hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
 
     // Verify stage
     require!(hand.stage == HandStage::Showdown, ZkPokerError::InvalidStage);
@@ -87,35 +206,43 @@ pub fn handle_reveal_hand(
     // Verify player hasn't folded
     require!(!hand.has_folded(seat), ZkPokerError::AlreadyFolded);
 
-    // Verify player hasn't already revealed
-    let already_revealed = match seat {
-        0 => hand.p1_revealed,
-        1 => hand.p2_revealed,
-        _ => return Err(ZkPokerError::PlayerNotAtTable.into()),
-    };
-    require!(!already_revealed, ZkPokerError::HandAlreadyRevealed);
+    // Verify player hasn't already revealed or mucked
+    require!(!hand.has_revealed(seat), ZkPokerError::HandAlreadyRevealed);
+    require!(!hand.has_mucked(seat), ZkPokerError::HandAlreadyMucked);
+
+    // The player due to show first (last aggressor) must reveal before the
+    // other may. With a player all-in both hands are tabled in any order.
+    if hand.first_reveal_ordered() {
+        require!(seat == hand.action_on, ZkPokerError::ShowdownOrderViolation);
+    }
+
+    // The circuit never outputs a rank outside the encoding range
+    require!(
+        (MIN_HAND_RANK..=MAX_HAND_RANK).contains(&hand_rank),
+        ZkPokerError::InvalidHandRank
+    );
 
     // Get proof data from buffer
     let proof_data = proof_buffer.get_proof_data()?;
 
-    // Verify ZK proof
-    verify_hand_reveal(
+    // Verify ZK proof against the seat's commitments and the board; the
+    // claimed rank must be the one the proof outputs
+    let proven_rank = verify_showdown_rank(
         &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.showdown_verifier,
         proof_data,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
+        &hand.hole_commits(seat),
+        &hand.first_board(),
     )?;
+    require!(proven_rank == hand_rank, ZkPokerError::RankMismatch);
 
     // Store verified hand rank
     match seat {
-        0 => {
-            hand.p1_hand_rank = hand_rank;
-            hand.p1_revealed = true;
-        }
-        1 => {
-            hand.p2_hand_rank = hand_rank;
-            hand.p2_revealed = true;
-        }
-        _ => {}
+        0 => hand.p1_hand_rank = hand_rank,
+        _ => hand.p2_hand_rank = hand_rank,
     }
+    hand.set_revealed(seat);
 
     // Update timestamp
     let clock = Clock::get()?;
@@ -124,56 +251,264 @@ pub fn handle_reveal_hand(
     msg!("Seat {} revealed hand with rank {}", seat, hand_rank);
 
     // Check if both players revealed, determine winner
-    if hand.p1_revealed && hand.p2_revealed {
-        determine_winner(table, hand)?;
+    if hand.both_revealed() {
+        determine_winner(hand)?;
+    }
+
+    Ok(())
+}
+
+/// Reveal hand rank on the second board when running it twice (proof from buffer)
+pub fn handle_reveal_second_hand(
+    ctx: Context<RevealHand>,
+    hand_rank: u64,
+) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+    let proof_buffer = &ctx.accounts.proof_buffer;
+
+    // Verify player was dealt into this hand
+    let seat = hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
+
+    // Verify stage
+    require!(hand.stage == HandStage::Showdown, ZkPokerError::InvalidStage);
+    require!(hand.run_count == 2, ZkPokerError::RunTwiceNotAgreed);
+    require!(hand.second_board_revealed(), ZkPokerError::SecondBoardNotRevealed);
+    require!(!hand.has_second_revealed(seat), ZkPokerError::HandAlreadyRevealed);
+    require!(
+        (MIN_HAND_RANK..=MAX_HAND_RANK).contains(&hand_rank),
+        ZkPokerError::InvalidHandRank
+    );
+
+    // Get proof data from buffer
+    let proof_data = proof_buffer.get_proof_data()?;
+
+    // Verify ZK proof against the seat's commitments and the second board
+    let proven_rank = verify_showdown_rank(
+        &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.showdown_verifier,
+        proof_data,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
+        &hand.hole_commits(seat),
+        &hand.second_board,
+    )?;
+    require!(proven_rank == hand_rank, ZkPokerError::RankMismatch);
+
+    hand.set_second_rank(seat, hand_rank);
+
+    // Update timestamp
+    let clock = Clock::get()?;
+    hand.last_action_at = clock.unix_timestamp;
+
+    msg!("Seat {} revealed second board rank {}", seat, hand_rank);
+
+    if hand.has_second_revealed(0) && hand.has_second_revealed(1) {
+        hand.second_winner = compare_ranks(hand.p1_second_rank, hand.p2_second_rank);
+        msg!("Second run result: {:?}", hand.second_winner);
     }
 
     Ok(())
 }
 
+/// Muck hand handler
+///
+/// A player who has not revealed can give up the pot instead of proving
+/// their hand, typically after the opponent showed a winner.
+pub fn handle_muck_hand(ctx: Context<MuckHand>) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+
+    // Verify player was dealt into this hand
+    let seat = hand.player_seat(table, &player)?;
+
+    require!(hand.stage == HandStage::Showdown, ZkPokerError::InvalidStage);
+    require!(!hand.has_revealed(seat), ZkPokerError::HandAlreadyRevealed);
+    require!(!hand.has_mucked(seat), ZkPokerError::HandAlreadyMucked);
+
+    let other = hand.other_seat(seat);
+    require!(!hand.has_mucked(other), ZkPokerError::HandAlreadyMucked);
+
+    hand.set_mucked(seat);
+    hand.winner = HandWinner::from_seat(other);
+    hand.last_action_at = Clock::get()?.unix_timestamp;
+
+    msg!("Seat {} mucks, seat {} wins", seat, other);
+
+    Ok(())
+}
+
+/// Agree result handler
+///
+/// The first seat to call proposes `result`; the other seat confirms by
+/// calling with the same result. Only once both have signed does the result
+/// become the hand's winner, so neither player can settle the pot alone.
+pub fn handle_agree_result(ctx: Context<AgreeResult>, result: HandWinner) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+
+    // Verify player was dealt into this hand
+    let seat = hand.player_seat(table, &player)?;
+
+    // Only once the money is in: an all-in runout, or showdown before a winner is decided
+    let all_in_showdown = hand.stage == HandStage::Showdown && hand.any_all_in();
+    require!(hand.is_all_in_runout() || all_in_showdown, ZkPokerError::NotAllInRunout);
+    require!(hand.winner == HandWinner::None, ZkPokerError::InvalidStage);
+    require!(result != HandWinner::None, ZkPokerError::InvalidResult);
+
+    let other = hand.other_seat(seat);
+    if hand.has_agreed_result(other) {
+        // Confirm the other seat's proposal
+        require!(hand.proposed_result == result, ZkPokerError::ResultMismatch);
+    } else {
+        // Propose (or replace our own pending proposal)
+        hand.proposed_result = result;
+    }
+    hand.set_agreed_result(seat);
+
+    msg!("Seat {} agreed to result {:?}", seat, result);
+
+    if hand.result_agreed() {
+        // The agreement settles the whole pot, even if the board was to be run twice
+        hand.winner = result;
+        hand.run_count = 1;
+        msg!("Result agreed: {:?}", result);
+    }
+
+    Ok(())
+}
+
+/// Result of comparing two hand ranks (higher is better)
+fn compare_ranks(p1_rank: u64, p2_rank: u64) -> HandWinner {
+    match p1_rank.cmp(&p2_rank) {
+        std::cmp::Ordering::Greater => HandWinner::Seat0,
+        std::cmp::Ordering::Less => HandWinner::Seat1,
+        std::cmp::Ordering::Equal => HandWinner::Split,
+    }
+}
+
 /// Determine winner after both players reveal
-fn determine_winner(_table: &mut Table, hand: &mut Hand) -> Result<()> {
+fn determine_winner(hand: &mut Hand) -> Result<()> {
     // Compare hand ranks (higher is better)
-    // The hand_rank is a composite score: rank * 100 + primary_value
-    // This ensures proper comparison including kickers
+    // The hand_rank encodes the category and all five playing card values
+    // (category << 20 | v1 << 16 | ... | v5), so integer comparison orders
+    // hands exactly, kickers included, and equal ranks are true chops
 
     if hand.p1_hand_rank > hand.p2_hand_rank {
-        hand.winner = 0;
+        hand.winner = HandWinner::Seat0;
         msg!("Seat 0 wins with rank {} vs {}", hand.p1_hand_rank, hand.p2_hand_rank);
     } else if hand.p2_hand_rank > hand.p1_hand_rank {
-        hand.winner = 1;
+        hand.winner = HandWinner::Seat1;
         msg!("Seat 1 wins with rank {} vs {}", hand.p2_hand_rank, hand.p1_hand_rank);
     } else {
-        // Split pot
-        hand.winner = 2;
+        // Split pot - equal ranks only chop if both were proven
+        require!(hand.split_proven(), ZkPokerError::SplitNotProven);
+        hand.winner = HandWinner::Split;
         msg!("Split pot - both ranks equal at {}", hand.p1_hand_rank);
     }
 
+    // Payout waits for the table's dispute window from here
+    hand.winner_determined_at = Clock::get()?.unix_timestamp;
+
     Ok(())
 }
 
+/// Challenge showdown handler
+///
+/// `reveal_hand` only stores a rank its proof outputs, so this is a backstop
+/// rather than the rank check: it lets a stored rank be re-proven against the
+/// registered SHOWDOWN verifier, e.g. after the authority replaced one found
+/// to be faulty. While the dispute window is open either player can submit a
+/// proof for a revealed seat; it must match the seat's commitments and the
+/// board. If the rank it proves differs from the stored one, the rank is
+/// corrected and the winner decided again, which restarts the window.
+pub fn handle_challenge_showdown(ctx: Context<ChallengeShowdown>, seat: u8) -> Result<()> {
+    let table = &ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+
+    // Verify player was dealt into this hand
+    hand.player_seat(table, &player)?;
+
+    // The proof buffer closes with this instruction, freeing its slot
+    hand.release_buffer(&player);
+
+    require!(hand.stage == HandStage::Showdown, ZkPokerError::InvalidStage);
+    require!(hand.both_revealed(), ZkPokerError::PlayersNotRevealed);
+
+    let clock = Clock::get()?;
+    let window_ends = hand.winner_determined_at.saturating_add(table.dispute_window);
+    require!(clock.unix_timestamp < window_ends, ZkPokerError::DisputeWindowClosed);
+
+    let claimed_rank = match seat {
+        0 => hand.p1_hand_rank,
+        1 => hand.p2_hand_rank,
+        _ => return Err(ZkPokerError::PlayerNotInHand.into()),
+    };
+
+    let proven_rank = verify_showdown_rank(
+        &ctx.accounts.verifier_program,
+        &ctx.accounts.global_config.showdown_verifier,
+        ctx.accounts.proof_buffer.get_proof_data()?,
+        &hand_binding(table.table_id, hand.hand_number, hand.circuit_version),
+        &hand.hole_commits(seat),
+        &hand.first_board(),
+    )?;
+    require!(proven_rank != claimed_rank, ZkPokerError::RankNotDisputed);
+
+    match seat {
+        0 => hand.p1_hand_rank = proven_rank,
+        _ => hand.p2_hand_rank = proven_rank,
+    }
+    msg!("Seat {} rank corrected from {} to {}", seat, claimed_rank, proven_rank);
+
+    determine_winner(hand)
+}
+
 /// Claim pot handler
 pub fn handle_claim_pot(ctx: Context<ClaimPot>) -> Result<()> {
     let table = &mut ctx.accounts.table;
     let hand = &mut ctx.accounts.hand;
     let player = ctx.accounts.player.key();
 
-    // Verify player is at table
-    let seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    // Verify player was dealt into this hand
+    let seat = hand.player_seat(table, &player)?;
 
-    // Verify hand is in showdown or complete stage
-    require!(
-        hand.stage == HandStage::Showdown || hand.stage == HandStage::Complete,
-        ZkPokerError::InvalidStage
-    );
+    // Verify the pot has not already been paid by any completion path
+    hand.require_not_complete()?;
 
-    // Verify pot not already claimed
-    require!(!hand.pot_claimed, ZkPokerError::PotAlreadyClaimed);
+    // Verify hand is in showdown stage, unless settled by agreement
+    if !hand.result_agreed() && hand.stage != HandStage::Showdown {
+        // A pending proposal alone never settles the pot
+        let proposed = hand.has_agreed_result(0) || hand.has_agreed_result(1);
+        require!(!proposed, ZkPokerError::ResultNotAgreed);
+        return Err(ZkPokerError::InvalidStage.into());
+    }
 
     // Check if this was a fold win (only one player remaining)
     let fold_win = hand.remaining_players() == 1;
 
-    if fold_win {
+    // The house takes its cut before anyone is paid
+    let rake = take_rake(table, hand);
+
+    if hand.result_agreed() {
+        // Settled by agreement - pay out the result both players signed
+        let seat_one_share = hand.payout(0, hand.pot);
+        let seat_two_share = hand.payout(1, hand.pot);
+        table.add_chips(0, seat_one_share);
+        table.add_chips(1, seat_two_share);
+
+        msg!("Agreed result: Seat 0 gets {}, Seat 1 gets {}", seat_one_share, seat_two_share);
+
+        ctx.accounts.table_stats.record_hand(hand, hand.pot, rake);
+        ctx.accounts.player_one_profile.record_hand(table.get_player(0), hand, 0, hand.pot);
+        ctx.accounts.player_two_profile.record_hand(table.get_player(1), hand, 1, hand.pot);
+    } else if fold_win {
         // Fold win - non-folded player claims
         let winner = hand.non_folded_seat().ok_or(ZkPokerError::NotTheWinner)?;
         require!(seat == winner, ZkPokerError::NotTheWinner);
@@ -183,38 +518,95 @@ pub fn handle_claim_pot(ctx: Context<ClaimPot>) -> Result<()> {
         msg!("Seat {} claims pot of {} (fold)", winner, hand.pot);
     } else {
         // Showdown - verify both revealed and winner determined
-        require!(hand.p1_revealed && hand.p2_revealed, ZkPokerError::PlayersNotRevealed);
-        require!(hand.winner != 255, ZkPokerError::ShowdownNotReady);
+        // (fold wins were already recorded when the hand completed)
+
+        // On auto-muck tables, a seat that let the action timeout pass after
+        // its opponent showed concedes without a proof or a muck call
+        if table.auto_muck && hand.run_count == 1 {
+            if let Some(silent) = hand.unrevealed_seat() {
+                let deadline = hand.last_action_at.saturating_add(table.action_timeout);
+                require!(Clock::get()?.unix_timestamp > deadline, ZkPokerError::PlayersNotRevealed);
+                hand.set_mucked(silent);
+                hand.winner = HandWinner::from_seat(hand.other_seat(silent));
+                msg!("Seat {} auto-mucked", silent);
+            }
+        }
 
-        if hand.winner == 2 {
-            // Split pot
-            let half = hand.pot / 2;
-            let remainder = hand.pot % 2;
+        // A muck concedes the pot without the other hand being shown
+        let mucked = hand.has_mucked(0) || hand.has_mucked(1);
+        require!(mucked || (hand.both_revealed()), ZkPokerError::PlayersNotRevealed);
 
-            table.add_chips(0, half + remainder); // P1 gets odd chip
-            table.add_chips(1, half);
+        // Revealed ranks stand only once nobody can challenge them
+        if !mucked {
+            let window_ends = hand.winner_determined_at.saturating_add(table.dispute_window);
+            require!(Clock::get()?.unix_timestamp >= window_ends, ZkPokerError::DisputeWindowOpen);
+        }
 
-            msg!("Split pot: Seat 0 gets {}, Seat 1 gets {}", half + remainder, half);
+        if hand.run_count == 2 {
+            // Ran it twice - each run decides its half of the pot
+            require!(hand.winner != HandWinner::None, ZkPokerError::ShowdownNotReady);
+            require!(
+                hand.has_second_revealed(0) && hand.has_second_revealed(1),
+                ZkPokerError::PlayersNotRevealed
+            );
+            require!(hand.second_winner != HandWinner::None, ZkPokerError::ShowdownNotReady);
+
+            let seat_one_share = hand.payout(0, hand.pot);
+            let seat_two_share = hand.payout(1, hand.pot);
+            table.add_chips(0, seat_one_share);
+            table.add_chips(1, seat_two_share);
+
+            msg!("Ran it twice: Seat 0 gets {}, Seat 1 gets {}", seat_one_share, seat_two_share);
         } else {
-            // Single winner
-            require!(seat == hand.winner, ZkPokerError::NotTheWinner);
-            table.add_chips(hand.winner, hand.pot);
-            msg!("Seat {} claims pot of {}", hand.winner, hand.pot);
+            match hand.winner {
+                HandWinner::None => return Err(ZkPokerError::ShowdownNotReady.into()),
+                HandWinner::Split => {
+                    // Split pot: any unmatched contribution goes back first,
+                    // then the matched part is halved (P1 gets the odd chip)
+                    require!(hand.split_proven(), ZkPokerError::SplitNotProven);
+                    let seat_one_share = hand.payout(0, hand.pot);
+                    let seat_two_share = hand.payout(1, hand.pot);
+
+                    table.add_chips(0, seat_one_share);
+                    table.add_chips(1, seat_two_share);
+
+                    msg!("Split pot: Seat 0 gets {}, Seat 1 gets {}", seat_one_share, seat_two_share);
+                }
+                HandWinner::Seat0 | HandWinner::Seat1 => {
+                    // Single winner; an unmatched contribution from the
+                    // loser is still the loser's
+                    let winner = hand.winner.seat().ok_or(ZkPokerError::NotTheWinner)?;
+                    require!(seat == winner, ZkPokerError::NotTheWinner);
+                    let winnings = hand.payout(winner, hand.pot);
+                    let loser = hand.other_seat(winner);
+                    table.add_chips(winner, winnings);
+                    table.add_chips(loser, hand.pot - winnings);
+                    msg!("Seat {} claims pot of {}", winner, winnings);
+                }
+            }
         }
-    }
 
-    // Mark pot as claimed
-    hand.pot = 0;
-    hand.pot_claimed = true;
+        ctx.accounts.table_stats.record_hand(hand, hand.pot, rake);
+        ctx.accounts.player_one_profile.record_hand(table.get_player(0), hand, 0, hand.pot);
+        ctx.accounts.player_two_profile.record_hand(table.get_player(1), hand, 1, hand.pot);
+    }
 
-    // Complete the hand
-    hand.stage = HandStage::Complete;
-    table.status = TableStatus::Between;
-    table.current_hand = None;
-    table.increment_hands_played();
-    table.rotate_button();
+    // Mark pot as claimed and complete the hand
+    let reason = if hand.result_agreed() {
+        CompletionReason::Agreement
+    } else if fold_win {
+        CompletionReason::Fold
+    } else if hand.winner == HandWinner::Split
+        && (hand.run_count == 1 || hand.second_winner == HandWinner::Split)
+    {
+        CompletionReason::Split
+    } else {
+        CompletionReason::Showdown
+    };
+    let pot = hand.pot;
+    complete_hand(table, hand, pot, rake, reason);
 
     msg!("Hand {} complete", hand.hand_number);
 
-    Ok(())
+    hand.check_chip_conservation(table)
 }
@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{GlobalConfig, Table, TableStatus};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::state::{GlobalConfig, Table, TableConfig, TableStatus, Hand, HandStage, CompletionReason, TableStats, PlayerProfile};
 use crate::errors::ZkPokerError;
-use crate::constants::{GLOBAL_SEED, TABLE_SEED, VAULT_SEED, DEFAULT_ACTION_TIMEOUT, MIN_ACTION_TIMEOUT, MAX_ACTION_TIMEOUT};
+use crate::events::{HandResult, PlayerNeedsRebuy};
+use crate::constants::{GLOBAL_SEED, TABLE_SEED, VAULT_SEED, HAND_SEED, TABLE_STATS_SEED, PROFILE_SEED};
+use crate::instructions::betting::handle_street_transition;
+use crate::instructions::hand::refund_bets;
 
 /// Create a new table
 #[derive(Accounts)]
@@ -27,22 +30,19 @@ pub struct CreateTable<'info> {
     )]
     pub table: Account<'info, Table>,
 
-    /// Table vault for holding USDC
+    /// Table vault for holding the table's token
     #[account(
         init,
         payer = creator,
-        token::mint = usdc_mint,
+        token::mint = mint,
         token::authority = table,
         seeds = [VAULT_SEED, table.key().as_ref()],
         bump
     )]
     pub vault: Account<'info, TokenAccount>,
 
-    /// USDC mint
-    #[account(
-        constraint = usdc_mint.key() == global_config.usdc_mint @ ZkPokerError::InvalidMint
-    )]
-    pub usdc_mint: Account<'info, anchor_spl::token::Mint>,
+    /// Token mint the table is denominated in
+    pub mint: Account<'info, anchor_spl::token::Mint>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -68,11 +68,11 @@ pub struct JoinTable<'info> {
     )]
     pub table: Account<'info, Table>,
 
-    /// Player's USDC token account
+    /// Player's token account
     #[account(
         mut,
         constraint = player_token_account.owner == player.key(),
-        constraint = player_token_account.mint == global_config.usdc_mint
+        constraint = player_token_account.mint == table.mint @ ZkPokerError::InvalidMint
     )]
     pub player_token_account: Account<'info, TokenAccount>,
 
@@ -100,10 +100,49 @@ pub struct LeaveTable<'info> {
     )]
     pub table: Account<'info, Table>,
 
-    /// Player's USDC token account
+    /// The table's unsettled hand, only needed if one is still recorded
+    #[account(
+        mut,
+        constraint = hand.table == table.key() @ ZkPokerError::HandNotFound
+    )]
+    pub hand: Option<Account<'info, Hand>>,
+
+    /// Player's token account
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == table.mint @ ZkPokerError::InvalidMint
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// Table vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw the part of a stack above the maximum buy-in
+#[derive(Accounts)]
+pub struct CashOutExcess<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Player's token account
     #[account(
         mut,
-        constraint = player_token_account.owner == player.key()
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == table.mint @ ZkPokerError::InvalidMint
     )]
     pub player_token_account: Account<'info, TokenAccount>,
 
@@ -118,6 +157,78 @@ pub struct LeaveTable<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+/// Fold the current hand and leave the table
+#[derive(Accounts)]
+pub struct FoldAndLeave<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        mut,
+        seeds = [HAND_SEED, table.key().as_ref(), &hand.hand_number.to_le_bytes()],
+        bump = hand.bump,
+        constraint = hand.table == table.key()
+    )]
+    pub hand: Account<'info, Hand>,
+
+    /// Lifetime table statistics (created when the first hand completes)
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = TableStats::LEN,
+        seeds = [TABLE_STATS_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub table_stats: Account<'info, TableStats>,
+
+    /// Lifetime profile of the player in seat 0
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [PROFILE_SEED, table.get_player(0).as_ref()],
+        bump
+    )]
+    pub player_one_profile: Account<'info, PlayerProfile>,
+
+    /// Lifetime profile of the player in seat 1
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = PlayerProfile::LEN,
+        seeds = [PROFILE_SEED, table.get_player(1).as_ref()],
+        bump
+    )]
+    pub player_two_profile: Account<'info, PlayerProfile>,
+
+    /// Player's token account
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == table.mint @ ZkPokerError::InvalidMint
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// Table vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
 /// Add chips to stack
 #[derive(Accounts)]
 pub struct AddChips<'info> {
@@ -138,11 +249,11 @@ pub struct AddChips<'info> {
     )]
     pub table: Account<'info, Table>,
 
-    /// Player's USDC token account
+    /// Player's token account
     #[account(
         mut,
         constraint = player_token_account.owner == player.key(),
-        constraint = player_token_account.mint == global_config.usdc_mint
+        constraint = player_token_account.mint == table.mint @ ZkPokerError::InvalidMint
     )]
     pub player_token_account: Account<'info, TokenAccount>,
 
@@ -157,45 +268,196 @@ pub struct AddChips<'info> {
     pub token_program: Program<'info, Token>,
 }
 
-/// Create table handler
-pub fn handle_create_table(
-    ctx: Context<CreateTable>,
-    small_blind: u64,
-    big_blind: u64,
-    min_buy_in: u64,
-    max_buy_in: u64,
-    action_timeout: Option<i64>,
-) -> Result<()> {
-    // Validate configuration
-    require!(small_blind > 0, ZkPokerError::InvalidTableConfig);
-    require!(big_blind >= small_blind, ZkPokerError::InvalidTableConfig);
-    require!(min_buy_in >= big_blind * 10, ZkPokerError::InvalidTableConfig); // At least 10 BB
-    require!(max_buy_in >= min_buy_in, ZkPokerError::InvalidTableConfig);
+/// Collect the rake a table has accumulated
+#[derive(Accounts)]
+pub struct CollectRake<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = global_config.authority == authority.key() @ ZkPokerError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Token account receiving the rake
+    #[account(
+        mut,
+        constraint = destination.mint == table.mint @ ZkPokerError::InvalidMint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// Table vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Sweep vault tokens that no stack or rake accounts for
+#[derive(Accounts)]
+pub struct SweepVaultDust<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = global_config.authority == authority.key() @ ZkPokerError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Token account receiving the dust
+    #[account(
+        mut,
+        constraint = destination.mint == table.mint @ ZkPokerError::InvalidMint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// Table vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Swap seats between hands; both seated players must sign
+#[derive(Accounts)]
+pub struct SwapSeats<'info> {
+    pub player: Signer<'info>,
+
+    pub opponent: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+}
+
+/// Close an empty table
+#[derive(Accounts)]
+pub struct CloseTable<'info> {
+    /// Table creator or global authority
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED],
+        bump = global_config.bump,
+        constraint = authority.key() == table.creator
+            || authority.key() == global_config.authority @ ZkPokerError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Table vault, closed alongside the table
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, table.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Table creator, receives the rent of both accounts
+    #[account(mut, address = table.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Manage a private table's allowlist
+#[derive(Accounts)]
+pub struct ManageAllowlist<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump,
+        constraint = table.creator == creator.key() @ ZkPokerError::Unauthorized
+    )]
+    pub table: Account<'info, Table>,
+}
 
-    let timeout = action_timeout.unwrap_or(DEFAULT_ACTION_TIMEOUT);
-    require!(timeout >= MIN_ACTION_TIMEOUT && timeout <= MAX_ACTION_TIMEOUT, ZkPokerError::InvalidTimeoutConfig);
+/// Create table handler
+pub fn handle_create_table(ctx: Context<CreateTable>, config: TableConfig) -> Result<()> {
+    config.validate()?;
 
     let global_config = &mut ctx.accounts.global_config;
     let table = &mut ctx.accounts.table;
 
+    require!(global_config.has_table_capacity(), ZkPokerError::TableLimitReached);
     let table_id = global_config.next_table_id();
     let clock = Clock::get()?;
     let bump = ctx.bumps.table;
 
     table.init(
         table_id,
-        small_blind,
-        big_blind,
-        min_buy_in,
-        max_buy_in,
-        timeout,
+        ctx.accounts.creator.key(),
+        ctx.accounts.mint.key(),
+        &config,
         clock.unix_timestamp,
         bump,
     );
 
-    msg!("Table {} created", table_id);
-    msg!("Blinds: {}/{}", small_blind, big_blind);
-    msg!("Buy-in: {}-{}", min_buy_in, max_buy_in);
+    msg!("Table {} created by {}", table_id, ctx.accounts.creator.key());
+    msg!("Mint: {}", ctx.accounts.mint.key());
+    msg!("Blinds: {}/{}", config.small_blind, config.big_blind);
+    msg!("Blind levels: {}", config.blind_schedule.len());
+    msg!("Buy-in: {}-{}", config.min_buy_in, config.max_buy_in);
+    msg!("Buy-in cap: {}bb", config.max_buy_in_bb);
+    msg!("Time bank: {}s", config.time_bank());
+    msg!("Min action interval: {}s", config.min_action_interval);
+    msg!("Card commit deadline: {}s", config.commit_deadline());
+    msg!("Dispute window: {}s", config.dispute_window);
+    msg!("Rake: {}bps, cap {}, after flop only: {}", config.rake_bps, config.rake_cap, config.rake_only_after_flop);
+    msg!("Auto-continue: {}", config.auto_continue);
+    msg!("Max hands: {}", config.max_hands);
+    msg!("Min hands before leaving up: {}", config.min_hands_before_leave);
+    msg!("Rejoin cooldown: {}s", config.rejoin_cooldown);
+    msg!("Deck size: {}", config.deck_size);
+    msg!("Split hand rent: {}", config.split_hand_rent);
+    msg!("Button posts big blind: {}", config.button_is_big_blind);
+    msg!("Pot cap: {}", config.pot_cap);
+    msg!("Hand bet cap: {}bb", config.hand_bet_cap_bb);
+    msg!("Access: {:?}", config.access);
+    msg!("Straddle allowed: {}", config.straddle_allowed);
+    msg!("Deterministic seating: {}", config.deterministic_seating);
+    msg!("Random opening button: {}", config.random_button);
+    msg!("Auto-muck: {}", config.auto_muck);
+    msg!("First to act: {:?}", config.first_to_act_rule);
+    msg!("Timeout policy: {:?}", config.timeout_policy);
+    msg!("Big blind multiple buy-ins: {}", config.require_bb_multiple);
 
     Ok(())
 }
@@ -205,11 +467,21 @@ pub fn handle_join_table(ctx: Context<JoinTable>, buy_in_amount: u64) -> Result<
     let table = &mut ctx.accounts.table;
     let player = ctx.accounts.player.key();
 
+    // Fail fast with a clear error rather than the token program's
+    require!(
+        ctx.accounts.player_token_account.amount >= buy_in_amount,
+        ZkPokerError::InsufficientTokenBalance
+    );
+
     // Validate buy-in amount
     require!(
         buy_in_amount >= table.min_buy_in && buy_in_amount <= table.max_buy_in,
         ZkPokerError::InvalidBuyIn
     );
+    require!(table.is_valid_chip_amount(buy_in_amount), ZkPokerError::InvalidBuyIn);
+
+    // Check player may join this table
+    require!(table.can_join(&player), ZkPokerError::NotAllowlisted);
 
     // Check player not already at table
     require!(
@@ -217,16 +489,22 @@ pub fn handle_join_table(ctx: Context<JoinTable>, buy_in_amount: u64) -> Result<
         ZkPokerError::PlayerAlreadyAtTable
     );
 
+    // Check player didn't just leave
+    table.require_rejoin_allowed(&player, Clock::get()?.unix_timestamp)?;
+
     // Check table has empty seat
     require!(table.has_empty_seat(), ZkPokerError::TableFull);
 
+    // Check table is not winding down
+    require!(!table.closing, ZkPokerError::TableClosing);
+
     // Check no active hand
     require!(
         table.status != TableStatus::Playing,
         ZkPokerError::HandInProgress
     );
 
-    // Transfer USDC from player to vault
+    // Transfer tokens from player to vault
     let cpi_accounts = Transfer {
         from: ctx.accounts.player_token_account.to_account_info(),
         to: ctx.accounts.vault.to_account_info(),
@@ -236,19 +514,15 @@ pub fn handle_join_table(ctx: Context<JoinTable>, buy_in_amount: u64) -> Result<
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, buy_in_amount)?;
 
-    // Assign seat
-    if table.player_one.is_none() {
-        table.player_one = Some(player);
-        table.player_one_chips = buy_in_amount;
-        msg!("Player {} joined seat 0 with {} chips", player, buy_in_amount);
-    } else {
-        table.player_two = Some(player);
-        table.player_two_chips = buy_in_amount;
-        msg!("Player {} joined seat 1 with {} chips", player, buy_in_amount);
-    }
+    // Assign seat. A newcomer inherits the vacated seat's button position,
+    // which the last hand already rotated (see `reset_seat_for_rejoin`)
+    let seat = if table.player_one.is_none() { 0 } else { 1 };
+    table.seat_player(seat, player, buy_in_amount);
+    msg!("Player {} joined seat {} with {} chips", player, seat, buy_in_amount);
+    table.order_seats_by_pubkey();
 
-    // Update status if table is now full
-    if table.is_full() {
+    // Update status if table is now full and nobody is waiting on a rebuy
+    if table.is_full() && !table.sitting_out.contains(&true) {
         table.status = TableStatus::Between;
         msg!("Table is full, ready to start hand");
     }
@@ -270,48 +544,353 @@ pub fn handle_leave_table(ctx: Context<LeaveTable>) -> Result<()> {
         ZkPokerError::HandInProgress
     );
 
+    // No hit-and-run with winnings
+    table.require_can_leave(seat)?;
+
+    // Blinds are only posted once a hand starts, which puts the table in
+    // `Playing`. Should a hand still be recorded here anyway, void it if it
+    // never reached betting so both seats get their blinds back first.
+    if let Some(current) = table.current_hand {
+        let hand = ctx.accounts.hand.as_mut().ok_or(ZkPokerError::HandInProgress)?;
+        require!(hand.key() == current, ZkPokerError::HandNotFound);
+        require!(hand.stage.is_setup_stage(), ZkPokerError::HandInProgress);
+
+        refund_bets(table, hand);
+        complete_hand(table, hand, 0, 0, CompletionReason::Aborted);
+        msg!("Voided setup hand {} and returned the blinds", hand.hand_number);
+    }
+
+    let chips_returned = cash_out_seat(
+        table,
+        seat,
+        &ctx.accounts.vault,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.token_program,
+        ctx.bumps.vault,
+    )?;
+
+    msg!("Player {} left table with {} chips", player, chips_returned);
+
+    Ok(())
+}
+
+/// Cash out excess handler. Tops the stack down to the maximum buy-in
+/// between hands, returning the difference to the player.
+pub fn handle_cash_out_excess(ctx: Context<CashOutExcess>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let player = ctx.accounts.player.key();
+
+    // Get player's seat
+    let seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
+
+    // Check no active hand
+    require!(
+        table.status != TableStatus::Playing,
+        ZkPokerError::HandInProgress
+    );
+
+    let chips = table.get_chips(seat);
+    let max_buy_in = table.max_buy_in;
+    require!(chips > max_buy_in, ZkPokerError::NoExcessChips);
+    table.require_can_leave(seat)?;
+    let excess = chips - max_buy_in;
+
+    transfer_from_vault(
+        table,
+        &ctx.accounts.vault,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.token_program,
+        ctx.bumps.vault,
+        excess,
+    )?;
+    table.set_chips(seat, max_buy_in);
+
+    msg!("Player {} cashed out {} chips, new total: {}", player, excess, max_buy_in);
+
+    Ok(())
+}
+
+/// Fold and leave handler. Usable in any betting stage regardless of
+/// whose turn it is; the pot goes to the opponent via the normal fold path.
+pub fn handle_fold_and_leave(ctx: Context<FoldAndLeave>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let hand = &mut ctx.accounts.hand;
+    let player = ctx.accounts.player.key();
+
+    // Get player's seat from the hand's roster
+    let seat = hand.player_seat(table, &player)?;
+
+    // Only fold while betting, never while a proof is pending
+    require!(table.status == TableStatus::Playing, ZkPokerError::InvalidStage);
+    require!(table.current_hand == Some(hand.key()), ZkPokerError::HandNotFound);
+    require!(hand.stage.is_betting_stage(), ZkPokerError::InvalidStage);
+    require!(!hand.has_folded(seat), ZkPokerError::AlreadyFolded);
+
+    // Fold (awards pot to opponent and completes the hand)
+    hand.set_folded(seat);
+    hand.last_action_at = Clock::get()?.unix_timestamp;
+
+    msg!("Seat {} folds and leaves", seat);
+
+    handle_street_transition(
+        table,
+        hand,
+        &mut ctx.accounts.table_stats,
+        [&mut ctx.accounts.player_one_profile, &mut ctx.accounts.player_two_profile],
+    )?;
+    table.require_can_leave(seat)?;
+
+    let chips_returned = cash_out_seat(
+        table,
+        seat,
+        &ctx.accounts.vault,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.token_program,
+        ctx.bumps.vault,
+    )?;
+
+    msg!("Player {} left table with {} chips", player, chips_returned);
+
+    Ok(())
+}
+
+/// Add allowed player handler
+pub fn handle_add_allowed(ctx: Context<ManageAllowlist>, player: Pubkey) -> Result<()> {
+    ctx.accounts.table.add_allowed(player)?;
+
+    msg!("Player {} allowed at table {}", player, ctx.accounts.table.table_id);
+
+    Ok(())
+}
+
+/// Remove allowed player handler. Does not unseat a player already at the
+/// table.
+pub fn handle_remove_allowed(ctx: Context<ManageAllowlist>, player: Pubkey) -> Result<()> {
+    ctx.accounts.table.remove_allowed(&player)?;
+
+    msg!("Player {} removed from table {} allowlist", player, ctx.accounts.table.table_id);
+
+    Ok(())
+}
+
+/// Close table handler. Both seats must be cashed out and the vault empty;
+/// rent from both accounts goes back to the creator.
+pub fn handle_close_table(ctx: Context<CloseTable>) -> Result<()> {
+    let table = &ctx.accounts.table;
+
+    require!(table.status != TableStatus::Playing, ZkPokerError::HandInProgress);
+    require!(table.is_empty(), ZkPokerError::TableNotEmpty);
+    require!(ctx.accounts.vault.amount == 0, ZkPokerError::TableNotEmpty);
+
+    // The vault is owned by the table PDA
+    let table_id = table.table_id.to_le_bytes();
+    let seeds = &[TABLE_SEED, table_id.as_ref(), &[table.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.vault.to_account_info(),
+        destination: ctx.accounts.creator.to_account_info(),
+        authority: table.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::close_account(cpi_ctx)?;
+
+    ctx.accounts.global_config.release_table();
+
+    msg!("Table {} closed after {} hands", table.table_id, table.hands_played);
+
+    Ok(())
+}
+
+/// Collect rake handler. Moves the table's uncollected rake out of the vault.
+pub fn handle_collect_rake(ctx: Context<CollectRake>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+    let rake = table.rake_owed;
+
+    if rake > 0 {
+        transfer_from_vault(
+            table,
+            &ctx.accounts.vault,
+            &ctx.accounts.destination,
+            &ctx.accounts.token_program,
+            ctx.bumps.vault,
+            rake,
+        )?;
+        table.rake_owed = 0;
+    }
+
+    msg!("Collected {} rake from table {}", rake, table.table_id);
+
+    Ok(())
+}
+
+/// Swap seats handler. Stacks and per-seat state follow their players;
+/// never during a hand.
+pub fn handle_swap_seats(ctx: Context<SwapSeats>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+
+    require!(
+        table.status != TableStatus::Playing,
+        ZkPokerError::HandInProgress
+    );
+    require!(table.is_full(), ZkPokerError::NotEnoughPlayers);
+
+    // The two signers must hold the two different seats
+    let seat = table.get_seat(&ctx.accounts.player.key()).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    let opponent_seat = table.get_seat(&ctx.accounts.opponent.key()).ok_or(ZkPokerError::PlayerNotAtTable)?;
+    require!(seat != opponent_seat, ZkPokerError::PlayerNotAtTable);
+
+    table.swap_seats();
+
+    msg!("Seats swapped at table {}", table.table_id);
+
+    Ok(())
+}
+
+/// Sweep vault dust handler. Moves whatever the vault holds beyond the
+/// seated stacks and uncollected rake (tokens sent to it directly,
+/// rounding leftovers) to the destination. Only between hands, when no
+/// chips sit in a pot.
+pub fn handle_sweep_vault_dust(ctx: Context<SweepVaultDust>) -> Result<()> {
+    let table = &mut ctx.accounts.table;
+
+    require!(
+        table.status != TableStatus::Playing,
+        ZkPokerError::HandInProgress
+    );
+
+    let accounted = table
+        .player_one_chips
+        .checked_add(table.player_two_chips)
+        .and_then(|stacks| stacks.checked_add(table.rake_owed))
+        .ok_or(ZkPokerError::ArithmeticOverflow)?;
+    let dust = ctx.accounts.vault.amount.saturating_sub(accounted);
+    require!(dust > 0, ZkPokerError::NoVaultDust);
+
+    transfer_from_vault(
+        table,
+        &ctx.accounts.vault,
+        &ctx.accounts.destination,
+        &ctx.accounts.token_program,
+        ctx.bumps.vault,
+        dust,
+    )?;
+
+    msg!("Swept {} dust from table {}", dust, table.table_id);
+
+    Ok(())
+}
+
+/// Rake the pot at the end of a hand, setting the chips aside for the
+/// authority to collect. Only the matched part is raked; a contribution the
+/// opponent never matched goes back to its owner whole. Returns the rake taken.
+pub(crate) fn take_rake(table: &mut Table, hand: &mut Hand) -> u64 {
+    let matched = hand.pot - hand.unmatched_contribution(hand.pot).1;
+    let rake = table.rake_for(hand, matched);
+    hand.take_rake(rake);
+    table.rake_owed = table.rake_owed.saturating_add(rake);
+    rake
+}
+
+/// Settle a hand whose pot has been paid out: mark it complete, move the
+/// table to the next hand and publish the result
+pub(crate) fn complete_hand(
+    table: &mut Table,
+    hand: &mut Hand,
+    pot: u64,
+    rake: u64,
+    reason: CompletionReason,
+) {
+    hand.pot = 0;
+    hand.completion_reason = reason;
+    hand.set_pot_claimed(true);
+    hand.stage = HandStage::Complete;
+    table.status = TableStatus::Between;
+    table.current_hand = None;
+    table.increment_hands_played();
+    table.rotate_button();
+    sit_out_busted(table, hand);
+
+    emit!(HandResult {
+        table: hand.table,
+        table_id: table.table_id,
+        hand_number: hand.hand_number,
+        winner: hand.winner,
+        reason,
+        pot,
+        rake,
+        stacks: [table.get_chips(0), table.get_chips(1)],
+    });
+}
+
+/// Sit out busted seats at the end of a hand and tell clients they need
+/// to rebuy
+fn sit_out_busted(table: &mut Table, hand: &Hand) {
+    let busted = table.sit_out_busted();
+    for seat in 0..2u8 {
+        if busted[seat as usize] {
+            msg!("Seat {} is below the big blind and sits out", seat);
+            emit!(PlayerNeedsRebuy {
+                table: hand.table,
+                player: table.get_player(seat),
+                seat,
+                chips: table.get_chips(seat),
+            });
+        }
+    }
+}
+
+/// Transfer `amount` from the table vault to a player's token account
+fn transfer_from_vault<'info>(
+    table: &Account<'info, Table>,
+    vault: &Account<'info, TokenAccount>,
+    player_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let table_key = table.key();
+    let seeds = &[
+        VAULT_SEED,
+        table_key.as_ref(),
+        &[vault_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: vault.to_account_info(),
+        to: player_token_account.to_account_info(),
+        authority: table.to_account_info(),
+    };
+    let cpi_program = token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Transfer a seat's stack from the vault to the player and clear the seat.
+/// Returns the amount cashed out.
+fn cash_out_seat<'info>(
+    table: &mut Account<'info, Table>,
+    seat: u8,
+    vault: &Account<'info, TokenAccount>,
+    player_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    vault_bump: u8,
+) -> Result<u64> {
     // Get chips to return
     let chips_to_return = table.get_chips(seat);
 
     // Transfer chips back to player if any
     if chips_to_return > 0 {
-        let table_key = table.key();
-        let seeds = &[
-            VAULT_SEED,
-            table_key.as_ref(),
-            &[ctx.bumps.vault],
-        ];
-        let signer_seeds = &[&seeds[..]];
-
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.player_token_account.to_account_info(),
-            authority: table.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, chips_to_return)?;
+        transfer_from_vault(table, vault, player_token_account, token_program, vault_bump, chips_to_return)?;
     }
 
     // Clear seat
-    match seat {
-        0 => {
-            table.player_one = None;
-            table.player_one_chips = 0;
-        }
-        1 => {
-            table.player_two = None;
-            table.player_two_chips = 0;
-        }
-        _ => {}
-    }
+    table.record_leave(seat, Clock::get()?.unix_timestamp);
+    table.reset_seat_for_rejoin(seat);
 
-    // Update status
-    table.status = TableStatus::Waiting;
-
-    msg!("Player {} left table with {} chips", player, chips_to_return);
-
-    Ok(())
+    Ok(chips_to_return)
 }
 
 /// Add chips handler
@@ -319,6 +898,12 @@ pub fn handle_add_chips(ctx: Context<AddChips>, amount: u64) -> Result<()> {
     let table = &mut ctx.accounts.table;
     let player = ctx.accounts.player.key();
 
+    // Fail fast with a clear error rather than the token program's
+    require!(
+        ctx.accounts.player_token_account.amount >= amount,
+        ZkPokerError::InsufficientTokenBalance
+    );
+
     // Get player's seat
     let seat = table.get_seat(&player).ok_or(ZkPokerError::PlayerNotAtTable)?;
 
@@ -332,8 +917,9 @@ pub fn handle_add_chips(ctx: Context<AddChips>, amount: u64) -> Result<()> {
     let current_chips = table.get_chips(seat);
     let new_total = current_chips.checked_add(amount).ok_or(ZkPokerError::ArithmeticOverflow)?;
     require!(new_total <= table.max_buy_in, ZkPokerError::InvalidBuyIn);
+    require!(table.is_valid_chip_amount(amount), ZkPokerError::InvalidBuyIn);
 
-    // Transfer USDC from player to vault
+    // Transfer tokens from player to vault
     let cpi_accounts = Transfer {
         from: ctx.accounts.player_token_account.to_account_info(),
         to: ctx.accounts.vault.to_account_info(),
@@ -343,10 +929,39 @@ pub fn handle_add_chips(ctx: Context<AddChips>, amount: u64) -> Result<()> {
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, amount)?;
 
-    // Add chips to stack
+    // Add chips to stack, sitting back in if this covers the big blind
     table.add_chips(seat, amount);
+    table.bought_in[seat as usize] = table.bought_in[seat as usize].saturating_add(amount);
+    table.sit_in_if_covered(seat);
 
     msg!("Player {} added {} chips, new total: {}", player, amount, new_total);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::HandWinner;
+
+    #[test]
+    fn rake_skips_the_unmatched_contribution() {
+        let mut table = Table::deserialize(&mut &vec![0u8; Table::LEN - 8][..]).unwrap();
+        table.rake_bps = 500;
+
+        // Seat 1 put in 200 more than seat 0 matched
+        let mut hand = Hand::deserialize(&mut &vec![0u8; Hand::LEN - 8][..]).unwrap();
+        hand.total_bet = [300, 500];
+        hand.pot = 800;
+        hand.chips_in_play = 800;
+        hand.set_flop_revealed(true);
+
+        assert_eq!(take_rake(&mut table, &mut hand), 30);
+        assert_eq!(table.rake_owed, 30);
+
+        // The refund comes back unraked; the winner takes the raked rest
+        hand.winner = HandWinner::Seat0;
+        assert_eq!(hand.payout(0, hand.pot), 570);
+        assert_eq!(hand.payout(1, hand.pot), 200);
+    }
+}
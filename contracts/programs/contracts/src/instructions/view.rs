@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+use crate::state::{Table, Hand, TableStatus, HandStage, HandWinner};
+use crate::errors::ZkPokerError;
+use crate::constants::TABLE_SEED;
+use crate::instructions::betting::wager_room;
+
+/// Read-only view of a table and its current hand
+#[derive(Accounts)]
+pub struct ViewGameState<'info> {
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Current hand, if one is in progress
+    #[account(
+        constraint = hand.table == table.key() @ ZkPokerError::HandNotFound
+    )]
+    pub hand: Option<Account<'info, Hand>>,
+}
+
+/// Read-only countdown for the current hand
+#[derive(Accounts)]
+pub struct TimeUntilTimeout<'info> {
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        constraint = hand.table == table.key() @ ZkPokerError::HandNotFound
+    )]
+    pub hand: Account<'info, Hand>,
+}
+
+/// Read-only public snapshot of a table for spectators
+#[derive(Accounts)]
+pub struct Spectate<'info> {
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    /// Current hand, if one is in progress
+    #[account(
+        constraint = hand.table == table.key() @ ZkPokerError::HandNotFound
+    )]
+    pub hand: Option<Account<'info, Hand>>,
+}
+
+/// Read-only split of the current hand's pot
+#[derive(Accounts)]
+pub struct GetPotBreakdown<'info> {
+    #[account(
+        seeds = [TABLE_SEED, &table.table_id.to_le_bytes()],
+        bump = table.bump
+    )]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        constraint = hand.table == table.key() @ ZkPokerError::HandNotFound
+    )]
+    pub hand: Account<'info, Hand>,
+}
+
+/// How the pot divides between matched chips and an outstanding over-bet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PotBreakdown {
+    pub pot: u64,
+    /// Chips both seats have matched; `pot` minus `uncalled`
+    pub matched_pot: u64,
+    /// Each seat's total contribution this hand
+    pub total_bets: [u64; 2],
+    /// This street's bet not yet matched by the other seat
+    pub uncalled: u64,
+    /// Seat that made the unmatched bet, if any
+    pub uncalled_seat: Option<u8>,
+    /// Part of `uncalled` the other seat cannot cover, returned when the
+    /// street closes
+    pub refund: u64,
+}
+
+/// Table state as seen by every client
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GameView {
+    pub table_id: u64,
+    pub status: TableStatus,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub players: [Option<Pubkey>; 2],
+    pub chips: [u64; 2],
+    pub button: u8,
+    pub hands_played: u64,
+    pub hand: Option<HandView>,
+}
+
+/// Public state of a hand; hidden cards appear only as commitments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct HandView {
+    pub hand_number: u64,
+    pub stage: HandStage,
+    pub pot: u64,
+    pub current_bet: u64,
+    pub bets_this_street: [u64; 2],
+    pub action_on: u8,
+    pub last_action_at: i64,
+    /// Revealed community cards, in deal order
+    pub community_cards: Vec<u8>,
+    pub seed_commits: [[u8; 32]; 2],
+    pub hole_commits: [[[u8; 32]; 2]; 2],
+    pub winner: HandWinner,
+}
+
+/// What a spectator may see: everything already public at the table.
+/// Seed and hole card commitments, the deck seed and root, proof state and
+/// claimed ranks are left out so a spectator client has nothing to leak.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SpectatorSnapshot {
+    pub table_id: u64,
+    pub status: TableStatus,
+    pub small_blind: u64,
+    pub big_blind: u64,
+    pub players: [Option<Pubkey>; 2],
+    pub chips: [u64; 2],
+    pub button: u8,
+    pub hand_number: Option<u64>,
+    pub stage: Option<HandStage>,
+    pub pot: u64,
+    pub bets_this_street: [u64; 2],
+    /// Seat to act during betting, None outside it
+    pub action_on: Option<u8>,
+    /// Revealed community cards, in deal order
+    pub community_cards: Vec<u8>,
+    pub winner: HandWinner,
+}
+
+/// Community cards revealed so far, in deal order
+fn revealed_board(hand: &Hand) -> Vec<u8> {
+    let community = [hand.flop[0], hand.flop[1], hand.flop[2], hand.turn, hand.river];
+    community[..hand.community_cards_revealed() as usize].to_vec()
+}
+
+impl HandView {
+    fn new(hand: &Hand) -> Self {
+        Self {
+            hand_number: hand.hand_number,
+            stage: hand.stage,
+            pot: hand.pot,
+            current_bet: hand.current_bet,
+            bets_this_street: hand.bet_this_street,
+            action_on: hand.action_on,
+            last_action_at: hand.last_action_at,
+            community_cards: revealed_board(hand),
+            seed_commits: [hand.seed_commit_one, hand.seed_commit_two],
+            hole_commits: [hand.p1_hole_commits, hand.p2_hole_commits],
+            winner: hand.winner,
+        }
+    }
+}
+
+/// View game state handler. Returns the view as instruction return data
+/// without touching any account.
+pub fn handle_view_game_state(ctx: Context<ViewGameState>) -> Result<GameView> {
+    let table = &ctx.accounts.table;
+
+    Ok(GameView {
+        table_id: table.table_id,
+        status: table.status,
+        small_blind: table.small_blind,
+        big_blind: table.big_blind,
+        players: [table.player_one, table.player_two],
+        chips: [table.player_one_chips, table.player_two_chips],
+        button: table.button,
+        hands_played: table.hands_played,
+        hand: ctx.accounts.hand.as_ref().map(|hand| HandView::new(hand)),
+    })
+}
+
+/// Spectator snapshot handler. Returns only public table and hand state as
+/// return data; see `SpectatorSnapshot` for what is withheld.
+pub fn handle_spectator_snapshot(ctx: Context<Spectate>) -> Result<SpectatorSnapshot> {
+    let table = &ctx.accounts.table;
+    let hand = ctx.accounts.hand.as_ref();
+
+    Ok(SpectatorSnapshot {
+        table_id: table.table_id,
+        status: table.status,
+        small_blind: table.small_blind,
+        big_blind: table.big_blind,
+        players: [table.player_one, table.player_two],
+        chips: [table.player_one_chips, table.player_two_chips],
+        button: table.button,
+        hand_number: hand.map(|hand| hand.hand_number),
+        stage: hand.map(|hand| hand.stage),
+        pot: hand.map_or(0, |hand| hand.pot),
+        bets_this_street: hand.map_or([0; 2], |hand| hand.bet_this_street),
+        action_on: hand
+            .filter(|hand| hand.stage.is_betting_stage())
+            .map(|hand| hand.action_on),
+        community_cards: hand.map_or_else(Vec::new, |hand| revealed_board(hand)),
+        winner: hand.map_or(HandWinner::None, |hand| hand.winner),
+    })
+}
+
+/// Time until timeout handler. Returns the seconds left before `timeout`
+/// can be called on the hand, clamped at zero. During betting the seat to
+/// act also has its time bank; other stages get the action timeout alone.
+pub fn handle_time_until_timeout(ctx: Context<TimeUntilTimeout>) -> Result<i64> {
+    let table = &ctx.accounts.table;
+    let hand = &ctx.accounts.hand;
+    hand.require_not_complete()?;
+
+    let elapsed = Clock::get()?.unix_timestamp - hand.last_action_at;
+    let timeout = table.stage_timeout(hand.stage, hand.action_on);
+
+    Ok(timeout.saturating_sub(elapsed).max(0))
+}
+
+/// Get pot breakdown handler. Derives the matched pot and any over-bet
+/// from the hand's street bets; the refund is whatever exceeds the
+/// opponent's stack or remaining wager room.
+pub fn handle_get_pot_breakdown(ctx: Context<GetPotBreakdown>) -> Result<PotBreakdown> {
+    let table = &ctx.accounts.table;
+    let hand = &ctx.accounts.hand;
+
+    let bets = hand.bet_this_street;
+    let (uncalled_seat, uncalled) = if bets[0] > bets[1] {
+        (Some(0), bets[0] - bets[1])
+    } else if bets[1] > bets[0] {
+        (Some(1), bets[1] - bets[0])
+    } else {
+        (None, 0)
+    };
+
+    let refund = uncalled_seat.map_or(0, |seat| {
+        let caller = hand.other_seat(seat);
+        let coverable = table.get_chips(caller).min(wager_room(table, hand, caller));
+        uncalled.saturating_sub(coverable)
+    });
+
+    Ok(PotBreakdown {
+        pot: hand.pot,
+        matched_pot: hand.pot.saturating_sub(uncalled),
+        total_bets: hand.total_bet,
+        uncalled,
+        uncalled_seat,
+        refund,
+    })
+}
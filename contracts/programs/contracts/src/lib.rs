@@ -2,11 +2,13 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod state;
 pub mod instructions;
 pub mod utils;
 
 use instructions::*;
+use state::{HandWinner, TableConfig};
 
 declare_id!("GnDHa3pfhiqEG5xVTjtnTYue33ceX6disU8F2YJymqYr");
 
@@ -33,30 +35,48 @@ pub mod contracts {
         instructions::admin::handle_unpause(ctx)
     }
 
+    /// Cap the number of active tables (0 = unlimited)
+    pub fn set_max_tables(ctx: Context<SetMaxTables>, max_tables: u64) -> Result<()> {
+        instructions::admin::handle_set_max_tables(ctx, max_tables)
+    }
+
+    /// Replace the verifier programs and bump the circuit version (authority only)
+    pub fn update_verifiers(
+        ctx: Context<UpdateVerifiers>,
+        deck_verifier: Pubkey,
+        deal_verifier: Pubkey,
+        reveal_verifier: Pubkey,
+        showdown_verifier: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::handle_update_verifiers(
+            ctx,
+            deck_verifier,
+            deal_verifier,
+            reveal_verifier,
+            showdown_verifier,
+        )
+    }
+
+    /// Change the protocol's token mint (authority only)
+    pub fn update_config(ctx: Context<UpdateConfig>) -> Result<()> {
+        instructions::admin::handle_update_config(ctx)
+    }
+
+    /// Void a stuck hand and refund both seats' bets (authority, paused only)
+    pub fn admin_abort_hand(ctx: Context<AdminAbortHand>) -> Result<()> {
+        instructions::admin::handle_admin_abort_hand(ctx)
+    }
+
     // ============================================
     // TABLE INSTRUCTIONS
     // ============================================
 
     /// Create a new poker table
-    pub fn create_table(
-        ctx: Context<CreateTable>,
-        small_blind: u64,
-        big_blind: u64,
-        min_buy_in: u64,
-        max_buy_in: u64,
-        action_timeout: Option<i64>,
-    ) -> Result<()> {
-        instructions::table::handle_create_table(
-            ctx,
-            small_blind,
-            big_blind,
-            min_buy_in,
-            max_buy_in,
-            action_timeout,
-        )
+    pub fn create_table(ctx: Context<CreateTable>, config: TableConfig) -> Result<()> {
+        instructions::table::handle_create_table(ctx, config)
     }
 
-    /// Join a table with USDC buy-in
+    /// Join a table with a buy-in in the table's token
     pub fn join_table(ctx: Context<JoinTable>, buy_in_amount: u64) -> Result<()> {
         instructions::table::handle_join_table(ctx, buy_in_amount)
     }
@@ -66,11 +86,51 @@ pub mod contracts {
         instructions::table::handle_leave_table(ctx)
     }
 
+    /// Withdraw chips above the maximum buy-in between hands
+    pub fn cash_out_excess(ctx: Context<CashOutExcess>) -> Result<()> {
+        instructions::table::handle_cash_out_excess(ctx)
+    }
+
+    /// Fold the current hand and leave the table with remaining chips
+    pub fn fold_and_leave(ctx: Context<FoldAndLeave>) -> Result<()> {
+        instructions::table::handle_fold_and_leave(ctx)
+    }
+
     /// Add chips to your stack
     pub fn add_chips(ctx: Context<AddChips>, amount: u64) -> Result<()> {
         instructions::table::handle_add_chips(ctx, amount)
     }
 
+    /// Allow a player to join a private table (creator only)
+    pub fn add_allowed(ctx: Context<ManageAllowlist>, player: Pubkey) -> Result<()> {
+        instructions::table::handle_add_allowed(ctx, player)
+    }
+
+    /// Remove a player from a private table's allowlist (creator only)
+    pub fn remove_allowed(ctx: Context<ManageAllowlist>, player: Pubkey) -> Result<()> {
+        instructions::table::handle_remove_allowed(ctx, player)
+    }
+
+    /// Collect a table's accumulated rake (authority only)
+    pub fn collect_rake(ctx: Context<CollectRake>) -> Result<()> {
+        instructions::table::handle_collect_rake(ctx)
+    }
+
+    /// Sweep vault tokens not owed to any stack or rake (authority only)
+    pub fn sweep_vault_dust(ctx: Context<SweepVaultDust>) -> Result<()> {
+        instructions::table::handle_sweep_vault_dust(ctx)
+    }
+
+    /// Swap the two seats between hands (both players sign)
+    pub fn swap_seats(ctx: Context<SwapSeats>) -> Result<()> {
+        instructions::table::handle_swap_seats(ctx)
+    }
+
+    /// Close an empty table and refund its rent to the creator
+    pub fn close_table(ctx: Context<CloseTable>) -> Result<()> {
+        instructions::table::handle_close_table(ctx)
+    }
+
     // ============================================
     // HAND INSTRUCTIONS
     // ============================================
@@ -80,6 +140,17 @@ pub mod contracts {
         instructions::hand::handle_start_hand(ctx)
     }
 
+    /// Start the next hand on the same seats and emit `RematchStarted`
+    pub fn rematch(ctx: Context<Rematch>) -> Result<()> {
+        instructions::hand::handle_rematch(ctx)
+    }
+
+    /// Signal readiness for the next hand (auto-continue tables).
+    /// Pass the next hand account on the second ready_up to start it.
+    pub fn ready_up(ctx: Context<ReadyUp>) -> Result<()> {
+        instructions::hand::handle_ready_up(ctx)
+    }
+
     /// Commit shuffle seed hash
     pub fn commit_seed(ctx: Context<CommitSeed>, seed_hash: [u8; 32]) -> Result<()> {
         instructions::hand::handle_commit_seed(ctx, seed_hash)
@@ -90,6 +161,11 @@ pub mod contracts {
         instructions::hand::handle_reveal_seed(ctx, seed)
     }
 
+    /// Prove the deck was shuffled from the deck seed (DEAL circuit)
+    pub fn verify_deal(ctx: Context<VerifyDeal>) -> Result<()> {
+        instructions::hand::handle_verify_deal(ctx)
+    }
+
     /// Commit hole cards with ZK proof (proof is read from ProofBuffer PDA)
     pub fn commit_hole_cards(
         ctx: Context<CommitHoleCards>,
@@ -98,11 +174,21 @@ pub mod contracts {
         instructions::hand::handle_commit_hole_cards(ctx, commitments)
     }
 
+    /// Button straddles to twice the big blind before the deal
+    pub fn straddle(ctx: Context<Straddle>) -> Result<()> {
+        instructions::hand::handle_straddle(ctx)
+    }
+
     /// Claim win due to opponent timeout
     pub fn timeout(ctx: Context<Timeout>) -> Result<()> {
         instructions::hand::handle_timeout(ctx)
     }
 
+    /// Close a completed hand, refunding its rent to whoever paid for it
+    pub fn close_hand(ctx: Context<CloseHand>) -> Result<()> {
+        instructions::hand::handle_close_hand(ctx)
+    }
+
     // ============================================
     // PROOF BUFFER INSTRUCTIONS
     // ============================================
@@ -125,6 +211,19 @@ pub mod contracts {
         instructions::proof_buffer::handle_upload_proof_chunk(ctx, offset, data)
     }
 
+    /// Resize a proof buffer (grow if the initial size was underestimated)
+    pub fn resize_proof_buffer(
+        ctx: Context<ResizeProofBuffer>,
+        new_size: u16,
+    ) -> Result<()> {
+        instructions::proof_buffer::handle_resize_proof_buffer(ctx, new_size)
+    }
+
+    /// Clear an uploaded proof buffer so a corrected proof can be re-uploaded
+    pub fn reset_proof_buffer(ctx: Context<ResetProofBuffer>) -> Result<()> {
+        instructions::proof_buffer::handle_reset_proof_buffer(ctx)
+    }
+
     /// Close proof buffer and reclaim rent
     pub fn close_proof_buffer(ctx: Context<CloseProofBuffer>) -> Result<()> {
         instructions::proof_buffer::handle_close_proof_buffer(ctx)
@@ -192,6 +291,32 @@ pub mod contracts {
         instructions::reveal::handle_reveal_river(ctx, card)
     }
 
+    /// Reveal all five community cards with one ZK proof during an all-in runout
+    pub fn reveal_all_community(
+        ctx: Context<RevealCommunity>,
+        cards: [u8; 5],
+    ) -> Result<()> {
+        instructions::reveal::handle_reveal_all_community(ctx, cards)
+    }
+
+    /// Move an all-in runout on to the next street, or showdown after the river
+    pub fn advance_runout(ctx: Context<AdvanceRunout>) -> Result<()> {
+        instructions::reveal::handle_advance_runout(ctx)
+    }
+
+    /// Agree to run the rest of the board twice during an all-in runout
+    pub fn consent_run_twice(ctx: Context<ConsentRunTwice>) -> Result<()> {
+        instructions::reveal::handle_consent_run_twice(ctx)
+    }
+
+    /// Reveal the second board with ZK proof when running it twice
+    pub fn reveal_second_board(
+        ctx: Context<RevealCommunity>,
+        cards: [u8; 5],
+    ) -> Result<()> {
+        instructions::reveal::handle_reveal_second_board(ctx, cards)
+    }
+
     // ============================================
     // SHOWDOWN INSTRUCTIONS
     // ============================================
@@ -204,8 +329,56 @@ pub mod contracts {
         instructions::showdown::handle_reveal_hand(ctx, hand_rank)
     }
 
+    /// Reveal hand rank on the second board with ZK proof when running it twice
+    pub fn reveal_second_hand(
+        ctx: Context<RevealHand>,
+        hand_rank: u64,
+    ) -> Result<()> {
+        instructions::showdown::handle_reveal_second_hand(ctx, hand_rank)
+    }
+
+    /// Muck (concede) at showdown instead of revealing
+    pub fn muck_hand(ctx: Context<MuckHand>) -> Result<()> {
+        instructions::showdown::handle_muck_hand(ctx)
+    }
+
+    /// Correct a stored hand rank with a showdown proof during the dispute
+    /// window (backstop; ranks are already proven at reveal)
+    pub fn challenge_showdown(ctx: Context<ChallengeShowdown>, seat: u8) -> Result<()> {
+        instructions::showdown::handle_challenge_showdown(ctx, seat)
+    }
+
+    /// Propose or confirm a hand result both players agree on, skipping showdown proofs
+    pub fn agree_result(ctx: Context<AgreeResult>, result: HandWinner) -> Result<()> {
+        instructions::showdown::handle_agree_result(ctx, result)
+    }
+
     /// Claim the pot after winning
     pub fn claim_pot(ctx: Context<ClaimPot>) -> Result<()> {
         instructions::showdown::handle_claim_pot(ctx)
     }
+
+    // ============================================
+    // VIEW INSTRUCTIONS
+    // ============================================
+
+    /// Return the table and current hand state as return data (read-only)
+    pub fn view_game_state(ctx: Context<ViewGameState>) -> Result<GameView> {
+        instructions::view::handle_view_game_state(ctx)
+    }
+
+    /// Return only the public table and hand state, for spectators (read-only)
+    pub fn spectator_snapshot(ctx: Context<Spectate>) -> Result<SpectatorSnapshot> {
+        instructions::view::handle_spectator_snapshot(ctx)
+    }
+
+    /// Return the seconds left before the current hand can be timed out (read-only)
+    pub fn time_until_timeout(ctx: Context<TimeUntilTimeout>) -> Result<i64> {
+        instructions::view::handle_time_until_timeout(ctx)
+    }
+
+    /// Return the matched pot, each seat's contribution and any uncalled refund (read-only)
+    pub fn get_pot_breakdown(ctx: Context<GetPotBreakdown>) -> Result<PotBreakdown> {
+        instructions::view::handle_get_pot_breakdown(ctx)
+    }
 }
@@ -15,25 +15,35 @@ pub struct GlobalConfig {
     pub deal_verifier: Pubkey,      // Dealing cards
     pub reveal_verifier: Pubkey,    // Community card reveals
     pub showdown_verifier: Pubkey,  // Hand reveals at showdown
-    pub bet_verifier: Pubkey,       // Bet/balance verification
 
     /// Total number of tables created
     pub table_count: u64,
 
+    /// Tables created and not yet closed
+    pub active_tables: u64,
+
+    /// Cap on active tables (0 = unlimited)
+    pub max_tables: u64,
+
     /// Emergency pause flag
     pub is_paused: bool,
 
+    /// Bumped by every `update_verifiers`; hands bind their proofs to the
+    /// version they started under
+    pub circuit_version: u16,
+
     /// PDA bump seed
     pub bump: u8,
 }
 
 impl GlobalConfig {
     /// Account size for rent calculation
-    /// 8 (discriminator) + 32 (authority) + 32 (usdc_mint) + 32*5 (verifiers) + 8 (table_count) + 1 (is_paused) + 1 (bump)
-    /// = 8 + 32 + 32 + 160 + 8 + 1 + 1 = 242 bytes
-    pub const LEN: usize = 8 + 32 + 32 + 160 + 8 + 1 + 1;
+    /// 8 (discriminator) + 32 (authority) + 32 (usdc_mint) + 32*4 (verifiers) + 8 (table_count) + 8 (active_tables) + 8 (max_tables) + 1 (is_paused) + 2 (circuit_version) + 1 (bump)
+    /// = 8 + 32 + 32 + 128 + 8 + 8 + 8 + 1 + 2 + 1 = 228 bytes
+    pub const LEN: usize = 8 + 32 + 32 + 128 + 8 + 8 + 8 + 1 + 2 + 1;
 
     /// Initialize a new GlobalConfig
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         &mut self,
         authority: Pubkey,
@@ -42,7 +52,6 @@ impl GlobalConfig {
         deal_verifier: Pubkey,
         reveal_verifier: Pubkey,
         showdown_verifier: Pubkey,
-        bet_verifier: Pubkey,
         bump: u8,
     ) {
         self.authority = authority;
@@ -51,9 +60,11 @@ impl GlobalConfig {
         self.deal_verifier = deal_verifier;
         self.reveal_verifier = reveal_verifier;
         self.showdown_verifier = showdown_verifier;
-        self.bet_verifier = bet_verifier;
         self.table_count = 0;
+        self.active_tables = 0;
+        self.max_tables = 0;
         self.is_paused = false;
+        self.circuit_version = 0;
         self.bump = bump;
     }
 
@@ -61,6 +72,17 @@ impl GlobalConfig {
     pub fn next_table_id(&mut self) -> u64 {
         let id = self.table_count;
         self.table_count = self.table_count.saturating_add(1);
+        self.active_tables = self.active_tables.saturating_add(1);
         id
     }
+
+    /// Whether another table can be created under `max_tables`
+    pub fn has_table_capacity(&self) -> bool {
+        self.max_tables == 0 || self.active_tables < self.max_tables
+    }
+
+    /// Free a table slot when a table is closed
+    pub fn release_table(&mut self) {
+        self.active_tables = self.active_tables.saturating_sub(1);
+    }
 }
@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
+use crate::errors::ZkPokerError;
+use crate::state::Table;
+use crate::constants::MAX_OPEN_BUFFERS;
 
 /// Hand stage enum representing the current phase of the hand
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum HandStage {
     /// Waiting for both players to commit shuffle seeds
+    #[default]
     SeedCommit = 0,
     /// Waiting for both players to reveal shuffle seeds
     SeedReveal = 1,
@@ -23,18 +27,18 @@ pub enum HandStage {
     Complete = 8,
 }
 
-impl Default for HandStage {
-    fn default() -> Self {
-        HandStage::SeedCommit
-    }
-}
-
 impl HandStage {
     /// Check if this is a betting stage
     pub fn is_betting_stage(&self) -> bool {
         matches!(self, HandStage::Preflop | HandStage::Flop | HandStage::Turn | HandStage::River)
     }
 
+    /// Check if this is a pre-deal setup stage (seeds and card commitments),
+    /// where the pot holds only the blinds
+    pub fn is_setup_stage(&self) -> bool {
+        matches!(self, HandStage::SeedCommit | HandStage::SeedReveal | HandStage::CardCommit)
+    }
+
     /// Get the next betting stage (for street transitions)
     pub fn next_betting_stage(&self) -> Option<HandStage> {
         match self {
@@ -47,6 +51,111 @@ impl HandStage {
     }
 }
 
+/// Hand winner enum recording the showdown/fold result
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum HandWinner {
+    /// No winner determined yet
+    #[default]
+    None = 0,
+    /// Player in seat 0 wins
+    Seat0 = 1,
+    /// Player in seat 1 wins
+    Seat1 = 2,
+    /// Pot is split between both players
+    Split = 3,
+}
+
+impl HandWinner {
+    /// Winner for a single seat
+    pub fn from_seat(seat: u8) -> Self {
+        match seat {
+            0 => HandWinner::Seat0,
+            1 => HandWinner::Seat1,
+            _ => HandWinner::None,
+        }
+    }
+
+    /// Winning seat, if a single player won
+    pub fn seat(&self) -> Option<u8> {
+        match self {
+            HandWinner::Seat0 => Some(0),
+            HandWinner::Seat1 => Some(1),
+            HandWinner::None | HandWinner::Split => None,
+        }
+    }
+}
+
+/// How a completed hand was settled
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CompletionReason {
+    /// Hand not complete yet
+    #[default]
+    None = 0,
+    /// One player folded
+    Fold = 1,
+    /// A single winner at showdown (including a muck)
+    Showdown = 2,
+    /// A player ran out of time
+    Timeout = 3,
+    /// The pot was split at showdown
+    Split = 4,
+    /// Both players signed the result
+    Agreement = 5,
+    /// Voided by the protocol authority; every seat got its chips back
+    Aborted = 6,
+}
+
+/// Bit positions for the packed boolean state in `Hand::flags`.
+/// Per-seat flags are laid out in pairs: the seat 1 bit sits directly above seat 0.
+pub mod hand_flags {
+    pub const P1_SEED_COMMITTED: u32 = 1 << 0;
+    pub const P2_SEED_COMMITTED: u32 = 1 << 1;
+    pub const P1_SEED_REVEALED: u32 = 1 << 2;
+    pub const P2_SEED_REVEALED: u32 = 1 << 3;
+    pub const P1_CARDS_COMMITTED: u32 = 1 << 4;
+    pub const P2_CARDS_COMMITTED: u32 = 1 << 5;
+    pub const P1_REVEALED: u32 = 1 << 6;
+    pub const P2_REVEALED: u32 = 1 << 7;
+    pub const P1_FOLDED: u32 = 1 << 8;
+    pub const P2_FOLDED: u32 = 1 << 9;
+    pub const P1_ALL_IN: u32 = 1 << 10;
+    pub const P2_ALL_IN: u32 = 1 << 11;
+    pub const P1_ACTED_THIS_STREET: u32 = 1 << 12;
+    pub const P2_ACTED_THIS_STREET: u32 = 1 << 13;
+    pub const FLOP_REVEALED: u32 = 1 << 14;
+    pub const TURN_REVEALED: u32 = 1 << 15;
+    pub const RIVER_REVEALED: u32 = 1 << 16;
+    pub const POT_CLAIMED: u32 = 1 << 17;
+    pub const P1_RUN_TWICE_CONSENT: u32 = 1 << 18;
+    pub const P2_RUN_TWICE_CONSENT: u32 = 1 << 19;
+    pub const SECOND_BOARD_REVEALED: u32 = 1 << 20;
+    pub const P1_SECOND_REVEALED: u32 = 1 << 21;
+    pub const P2_SECOND_REVEALED: u32 = 1 << 22;
+    pub const P1_RESULT_AGREED: u32 = 1 << 23;
+    pub const P2_RESULT_AGREED: u32 = 1 << 24;
+    pub const P1_MUCKED: u32 = 1 << 25;
+    pub const P2_MUCKED: u32 = 1 << 26;
+    pub const DEAL_VERIFIED: u32 = 1 << 27;
+    pub const FAST_SHOWDOWN: u32 = 1 << 28;
+}
+
+/// Generate a getter/setter pair for a single bit in `Hand::flags`
+macro_rules! hand_flag {
+    ($(#[$doc:meta] $get:ident, $set:ident => $flag:path;)*) => {
+        $(
+            #[$doc]
+            pub fn $get(&self) -> bool {
+                self.get_flag($flag)
+            }
+
+            #[$doc]
+            pub fn $set(&mut self, value: bool) {
+                self.set_flag($flag, value);
+            }
+        )*
+    };
+}
+
 /// Hand account representing a single poker hand
 /// Seeds: ["hand", table.key(), hand_number.to_le_bytes()]
 #[account]
@@ -57,6 +166,19 @@ pub struct Hand {
     /// Sequential hand number at this table
     pub hand_number: u64,
 
+    /// Player in seat 0 when the hand started
+    pub player_one: Pubkey,
+
+    /// Player in seat 1 when the hand started
+    pub player_two: Pubkey,
+
+    /// Account that paid the hand's rent; refunded by `close_hand`
+    pub rent_payer: Pubkey,
+
+    /// `GlobalConfig::circuit_version` when the hand started; part of every
+    /// proof's hand binding
+    pub circuit_version: u16,
+
     /// Current hand stage
     pub stage: HandStage,
 
@@ -79,17 +201,8 @@ pub struct Hand {
     /// Combined deck seed: hash(seed_1 || seed_2)
     pub deck_seed: [u8; 32],
 
-    /// Tracking: has player 1 committed seed?
-    pub p1_seed_committed: bool,
-
-    /// Tracking: has player 2 committed seed?
-    pub p2_seed_committed: bool,
-
-    /// Tracking: has player 1 revealed seed?
-    pub p1_seed_revealed: bool,
-
-    /// Tracking: has player 2 revealed seed?
-    pub p2_seed_revealed: bool,
+    /// Poseidon2 root of the shuffled deck, set once the DEAL proof is verified
+    pub deck_root: [u8; 32],
 
     // ============================================
     // PHASE 2: Hole Card Commitments
@@ -101,12 +214,6 @@ pub struct Hand {
     /// Player 2 hole card commitments [commit_card1, commit_card2]
     pub p2_hole_commits: [[u8; 32]; 2],
 
-    /// Tracking: has player 1 committed cards?
-    pub p1_cards_committed: bool,
-
-    /// Tracking: has player 2 committed cards?
-    pub p2_cards_committed: bool,
-
     // ============================================
     // PHASE 4: Community Cards (ZK revealed)
     // ============================================
@@ -120,11 +227,6 @@ pub struct Hand {
     /// River card (index 0-51), 255 = not revealed
     pub river: u8,
 
-    /// Tracking reveal status
-    pub flop_revealed: bool,
-    pub turn_revealed: bool,
-    pub river_revealed: bool,
-
     // ============================================
     // PHASE 5: Showdown
     // ============================================
@@ -135,17 +237,44 @@ pub struct Hand {
     /// Player 2 hand rank (ZK verified composite score)
     pub p2_hand_rank: u64,
 
-    /// Tracking: has player 1 revealed hand?
-    pub p1_revealed: bool,
+    /// Hand result (seat winner, split, or none yet)
+    pub winner: HandWinner,
+
+    /// When both revealed ranks decided `winner`; starts the dispute window
+    pub winner_determined_at: i64,
+
+    /// How the hand ended, set when it completes
+    pub completion_reason: CompletionReason,
+
+    // ============================================
+    // RUN IT TWICE
+    // ============================================
+
+    /// Number of boards dealt (1 normally, 2 when both players agreed to run it twice)
+    pub run_count: u8,
+
+    /// Second board (5 cards from deck positions after the first board), 255 = not revealed
+    pub second_board: [u8; 5],
+
+    /// Community cards already revealed when the second run was agreed (0, 3 or 4);
+    /// both boards share these cards
+    pub second_board_shared: u8,
+
+    /// Player 1 hand rank on the second board
+    pub p1_second_rank: u64,
 
-    /// Tracking: has player 2 revealed hand?
-    pub p2_revealed: bool,
+    /// Player 2 hand rank on the second board
+    pub p2_second_rank: u64,
 
-    /// Winner seat (0, 1, or 2 for split)
-    pub winner: u8,
+    /// Result of the second run
+    pub second_winner: HandWinner,
+
+    // ============================================
+    // SETTLEMENT BY AGREEMENT
+    // ============================================
 
-    /// Has pot been claimed?
-    pub pot_claimed: bool,
+    /// Result proposed through `agree_result`; becomes the winner once both seats agree
+    pub proposed_result: HandWinner,
 
     // ============================================
     // BETTING STATE
@@ -157,48 +286,44 @@ pub struct Hand {
     /// Current bet amount to match
     pub current_bet: u64,
 
-    /// Player 1 bet this street
-    pub p1_bet_this_street: u64,
+    /// Size of the last full bet or raise this street (0 = none yet); the
+    /// next raise must add at least this much, and never less than a big blind
+    pub last_raise_size: u64,
 
-    /// Player 2 bet this street
-    pub p2_bet_this_street: u64,
+    /// Per-seat bet this street
+    pub bet_this_street: [u64; 2],
 
-    /// Player 1 total bet this hand
-    pub p1_total_bet: u64,
+    /// Per-seat total bet this hand
+    pub total_bet: [u64; 2],
 
-    /// Player 2 total bet this hand
-    pub p2_total_bet: u64,
+    /// Button's straddle (total preflop bet it posted, 0 = no straddle)
+    pub straddle_amount: u64,
+
+    /// Both stacks plus the pot when the hand began; only rake leaves play
+    pub chips_in_play: u64,
 
     // ============================================
     // GAME STATE
     // ============================================
 
-    /// Whose turn (0 = player_one, 1 = player_two)
+    /// Whose turn (0 = player_one, 1 = player_two); at showdown, who shows first
     pub action_on: u8,
 
     /// Last action timestamp (for timeout tracking)
     pub last_action_at: i64,
 
-    /// Last aggressor (who bet/raised last)
+    /// Last aggressor (who bet/raised last); kept from the river into showdown
     pub last_aggressor: u8,
 
-    /// Has player 1 folded?
-    pub p1_folded: bool,
-
-    /// Has player 2 folded?
-    pub p2_folded: bool,
-
-    /// Is player 1 all-in?
-    pub p1_all_in: bool,
+    /// Proof buffers player_one has open for this hand
+    pub p1_open_buffers: u8,
 
-    /// Is player 2 all-in?
-    pub p2_all_in: bool,
+    /// Proof buffers player_two has open for this hand
+    pub p2_open_buffers: u8,
 
-    /// Has player 1 acted this street?
-    pub p1_acted_this_street: bool,
-
-    /// Has player 2 acted this street?
-    pub p2_acted_this_street: bool,
+    /// Packed tracking flags: seeds, cards, reveals, folds, all-ins,
+    /// street action and pot claim (see `hand_flags`)
+    pub flags: u32,
 
     /// PDA bump seed
     pub bump: u8,
@@ -209,59 +334,68 @@ impl Hand {
     pub const LEN: usize = 8     // discriminator
         + 32                      // table
         + 8                       // hand_number
+        + 32                      // player_one
+        + 32                      // player_two
+        + 32                      // rent_payer
+        + 2                       // circuit_version
         + 1                       // stage
         + 32                      // seed_commit_one
         + 32                      // seed_commit_two
         + 32                      // seed_one
         + 32                      // seed_two
         + 32                      // deck_seed
-        + 1                       // p1_seed_committed
-        + 1                       // p2_seed_committed
-        + 1                       // p1_seed_revealed
-        + 1                       // p2_seed_revealed
+        + 32                      // deck_root
         + 64                      // p1_hole_commits
         + 64                      // p2_hole_commits
-        + 1                       // p1_cards_committed
-        + 1                       // p2_cards_committed
         + 3                       // flop
         + 1                       // turn
         + 1                       // river
-        + 1                       // flop_revealed
-        + 1                       // turn_revealed
-        + 1                       // river_revealed
         + 8                       // p1_hand_rank
         + 8                       // p2_hand_rank
-        + 1                       // p1_revealed
-        + 1                       // p2_revealed
         + 1                       // winner
-        + 1                       // pot_claimed
+        + 8                       // winner_determined_at
+        + 1                       // completion_reason
+        + 1                       // run_count
+        + 5                       // second_board
+        + 1                       // second_board_shared
+        + 8                       // p1_second_rank
+        + 8                       // p2_second_rank
+        + 1                       // second_winner
+        + 1                       // proposed_result
         + 8                       // pot
         + 8                       // current_bet
-        + 8                       // p1_bet_this_street
-        + 8                       // p2_bet_this_street
-        + 8                       // p1_total_bet
-        + 8                       // p2_total_bet
+        + 8                       // last_raise_size
+        + 16                      // bet_this_street
+        + 16                      // total_bet
+        + 8                       // straddle_amount
+        + 8                       // chips_in_play
         + 1                       // action_on
         + 8                       // last_action_at
         + 1                       // last_aggressor
-        + 1                       // p1_folded
-        + 1                       // p2_folded
-        + 1                       // p1_all_in
-        + 1                       // p2_all_in
-        + 1                       // p1_acted_this_street
-        + 1                       // p2_acted_this_street
+        + 1                       // p1_open_buffers
+        + 1                       // p2_open_buffers
+        + 4                       // flags
         + 1;                      // bump
 
     /// Initialize a new hand
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         &mut self,
         table: Pubkey,
         hand_number: u64,
+        player_one: Pubkey,
+        player_two: Pubkey,
+        rent_payer: Pubkey,
+        circuit_version: u16,
         timestamp: i64,
         bump: u8,
     ) {
         self.table = table;
         self.hand_number = hand_number;
+        self.player_one = player_one;
+        self.player_two = player_two;
+        self.rent_payer = rent_payer;
+        self.circuit_version = circuit_version;
         self.stage = HandStage::SeedCommit;
 
         // Initialize seeds as zeros
@@ -270,144 +404,466 @@ impl Hand {
         self.seed_one = [0u8; 32];
         self.seed_two = [0u8; 32];
         self.deck_seed = [0u8; 32];
-        self.p1_seed_committed = false;
-        self.p2_seed_committed = false;
-        self.p1_seed_revealed = false;
-        self.p2_seed_revealed = false;
+        self.deck_root = [0u8; 32];
 
         // Initialize card commits as zeros
         self.p1_hole_commits = [[0u8; 32]; 2];
         self.p2_hole_commits = [[0u8; 32]; 2];
-        self.p1_cards_committed = false;
-        self.p2_cards_committed = false;
 
         // Initialize community cards as not revealed (255)
         self.flop = [255u8; 3];
         self.turn = 255;
         self.river = 255;
-        self.flop_revealed = false;
-        self.turn_revealed = false;
-        self.river_revealed = false;
 
         // Initialize showdown
         self.p1_hand_rank = 0;
         self.p2_hand_rank = 0;
-        self.p1_revealed = false;
-        self.p2_revealed = false;
-        self.winner = 255; // No winner yet
-        self.pot_claimed = false;
+        self.winner = HandWinner::None;
+        self.winner_determined_at = 0;
+        self.completion_reason = CompletionReason::None;
+
+        // Single board unless both players agree to run it twice
+        self.run_count = 1;
+        self.second_board = [255u8; 5];
+        self.second_board_shared = 0;
+        self.p1_second_rank = 0;
+        self.p2_second_rank = 0;
+        self.second_winner = HandWinner::None;
+        self.proposed_result = HandWinner::None;
 
         // Initialize betting
         self.pot = 0;
         self.current_bet = 0;
-        self.p1_bet_this_street = 0;
-        self.p2_bet_this_street = 0;
-        self.p1_total_bet = 0;
-        self.p2_total_bet = 0;
+        self.last_raise_size = 0;
+        self.bet_this_street = [0; 2];
+        self.total_bet = [0; 2];
+        self.straddle_amount = 0;
+        self.chips_in_play = 0;
 
         // Initialize game state
         self.action_on = 0;
         self.last_action_at = timestamp;
         self.last_aggressor = 255; // No aggressor yet
-        self.p1_folded = false;
-        self.p2_folded = false;
-        self.p1_all_in = false;
-        self.p2_all_in = false;
-        self.p1_acted_this_street = false;
-        self.p2_acted_this_street = false;
+        self.p1_open_buffers = 0;
+        self.p2_open_buffers = 0;
+
+        // Clear all tracking flags
+        self.flags = 0;
 
         self.bump = bump;
     }
 
-    /// Check if player has folded
-    pub fn has_folded(&self, seat: u8) -> bool {
+    /// Get seat number for a player dealt into this hand
+    pub fn get_seat(&self, player: &Pubkey) -> Option<u8> {
+        if self.player_one == *player {
+            Some(0)
+        } else if self.player_two == *player {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Get the player dealt into a seat
+    pub fn get_player(&self, seat: u8) -> Pubkey {
         match seat {
-            0 => self.p1_folded,
-            1 => self.p2_folded,
-            _ => true,
+            0 => self.player_one,
+            1 => self.player_two,
+            _ => Pubkey::default(),
         }
     }
 
-    /// Set player folded status
-    pub fn set_folded(&mut self, seat: u8) {
+    /// Open-buffer counter for a player dealt into this hand (None otherwise)
+    fn open_buffers_mut(&mut self, player: &Pubkey) -> Option<&mut u8> {
+        match self.get_seat(player)? {
+            0 => Some(&mut self.p1_open_buffers),
+            _ => Some(&mut self.p2_open_buffers),
+        }
+    }
+
+    /// Count a proof buffer opened by `player`, refusing past
+    /// `MAX_OPEN_BUFFERS`. Buffers from anyone outside the hand are not
+    /// counted; they can never be consumed.
+    pub fn open_buffer(&mut self, player: &Pubkey) -> Result<()> {
+        if let Some(open) = self.open_buffers_mut(player) {
+            require!(*open < MAX_OPEN_BUFFERS, ZkPokerError::TooManyBuffers);
+            *open += 1;
+        }
+        Ok(())
+    }
+
+    /// Free the slot of a proof buffer `player` closed or had consumed
+    pub fn release_buffer(&mut self, player: &Pubkey) {
+        if let Some(open) = self.open_buffers_mut(player) {
+            *open = open.saturating_sub(1);
+        }
+    }
+
+    /// Seat of a signer who was dealt into this hand and still holds that
+    /// seat at the table; the hand's roster, not the live table, decides
+    pub fn player_seat(&self, table: &Table, player: &Pubkey) -> Result<u8> {
+        let seat = self.get_seat(player).ok_or(ZkPokerError::PlayerNotInHand)?;
+        require!(table.get_seat(player) == Some(seat), ZkPokerError::PlayerNotAtTable);
+        Ok(seat)
+    }
+
+    /// Check a single bit in the packed flags
+    pub fn get_flag(&self, flag: u32) -> bool {
+        self.flags & flag != 0
+    }
+
+    /// Set or clear a single bit in the packed flags
+    pub fn set_flag(&mut self, flag: u32, value: bool) {
+        if value {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    /// Resolve a per-seat flag from its seat 0 bit (seat 1 is the next bit up)
+    fn seat_flag(p1_flag: u32, seat: u8) -> Option<u32> {
         match seat {
-            0 => self.p1_folded = true,
-            1 => self.p2_folded = true,
-            _ => {}
+            0 | 1 => Some(p1_flag << seat),
+            _ => None,
+        }
+    }
+
+    /// Check a per-seat flag (false for an invalid seat)
+    fn get_seat_flag(&self, p1_flag: u32, seat: u8) -> bool {
+        Self::seat_flag(p1_flag, seat).is_some_and(|flag| self.get_flag(flag))
+    }
+
+    /// Set or clear a per-seat flag (ignored for an invalid seat)
+    fn set_seat_flag(&mut self, p1_flag: u32, seat: u8, value: bool) {
+        if let Some(flag) = Self::seat_flag(p1_flag, seat) {
+            self.set_flag(flag, value);
         }
     }
 
+    hand_flag! {
+        /// Has player 1 committed seed?
+        p1_seed_committed, set_p1_seed_committed => hand_flags::P1_SEED_COMMITTED;
+        /// Has player 2 committed seed?
+        p2_seed_committed, set_p2_seed_committed => hand_flags::P2_SEED_COMMITTED;
+        /// Has player 1 revealed seed?
+        p1_seed_revealed, set_p1_seed_revealed => hand_flags::P1_SEED_REVEALED;
+        /// Has player 2 revealed seed?
+        p2_seed_revealed, set_p2_seed_revealed => hand_flags::P2_SEED_REVEALED;
+        /// Has player 1 committed cards?
+        p1_cards_committed, set_p1_cards_committed => hand_flags::P1_CARDS_COMMITTED;
+        /// Has player 2 committed cards?
+        p2_cards_committed, set_p2_cards_committed => hand_flags::P2_CARDS_COMMITTED;
+        /// Has the flop been revealed?
+        flop_revealed, set_flop_revealed => hand_flags::FLOP_REVEALED;
+        /// Has the turn been revealed?
+        turn_revealed, set_turn_revealed => hand_flags::TURN_REVEALED;
+        /// Has the river been revealed?
+        river_revealed, set_river_revealed => hand_flags::RIVER_REVEALED;
+        /// Has pot been claimed?
+        pot_claimed, set_pot_claimed => hand_flags::POT_CLAIMED;
+        /// Has the second board been revealed?
+        second_board_revealed, set_second_board_revealed => hand_flags::SECOND_BOARD_REVEALED;
+        /// Has the deck shuffle been proven?
+        deal_verified, set_deal_verified => hand_flags::DEAL_VERIFIED;
+        /// Did betting close with a player all-in? Only reveals and showdown remain
+        fast_showdown, set_fast_showdown => hand_flags::FAST_SHOWDOWN;
+    }
+
+    /// Check if player has folded (an invalid seat counts as folded)
+    pub fn has_folded(&self, seat: u8) -> bool {
+        seat > 1 || self.get_seat_flag(hand_flags::P1_FOLDED, seat)
+    }
+
+    /// Set player folded status
+    pub fn set_folded(&mut self, seat: u8) {
+        self.set_seat_flag(hand_flags::P1_FOLDED, seat, true);
+    }
+
     /// Check if player is all-in
     pub fn is_all_in(&self, seat: u8) -> bool {
-        match seat {
-            0 => self.p1_all_in,
-            1 => self.p2_all_in,
-            _ => false,
-        }
+        self.get_seat_flag(hand_flags::P1_ALL_IN, seat)
     }
 
     /// Set player all-in status
     pub fn set_all_in(&mut self, seat: u8) {
-        match seat {
-            0 => self.p1_all_in = true,
-            1 => self.p2_all_in = true,
-            _ => {}
-        }
+        self.set_seat_flag(hand_flags::P1_ALL_IN, seat, true);
+    }
+
+    /// Is either player all-in?
+    pub fn any_all_in(&self) -> bool {
+        self.is_all_in(0) || self.is_all_in(1)
+    }
+
+    /// Are both players all-in? No more betting can happen this hand
+    pub fn both_all_in(&self) -> bool {
+        self.is_all_in(0) && self.is_all_in(1)
     }
 
     /// Get player's bet this street
     pub fn get_bet_this_street(&self, seat: u8) -> u64 {
+        self.bet_this_street.get(seat as usize).copied().unwrap_or(0)
+    }
+
+    /// Get player's total bet this hand
+    pub fn get_total_bet(&self, seat: u8) -> u64 {
+        self.total_bet.get(seat as usize).copied().unwrap_or(0)
+    }
+
+    /// Chips a seat receives from a pot of `pot` given the current winner
+    /// (seat 0 gets the odd chip on a split). Whatever one seat put in
+    /// beyond the other's total is returned to it first, so a chop never
+    /// pays the smaller contributor more than it matched; only the matched
+    /// part is contested. When the board was run twice the first run
+    /// decides `matched - matched / 2` and the second run the rest.
+    pub fn payout(&self, seat: u8, pot: u64) -> u64 {
+        let (over_seat, excess) = self.unmatched_contribution(pot);
+        let refund = if seat == over_seat { excess } else { 0 };
+        let matched = pot - excess;
+
+        if self.run_count == 2 {
+            let second_half = matched / 2;
+            return refund
+                + Self::run_payout(self.winner, seat, matched - second_half)
+                + Self::run_payout(self.second_winner, seat, second_half);
+        }
+        refund + Self::run_payout(self.winner, seat, matched)
+    }
+
+    /// Seat that contributed more this hand and by how much, capped at
+    /// `pot`; (0, 0) when both contributed equally
+    pub fn unmatched_contribution(&self, pot: u64) -> (u8, u64) {
+        let [one, two] = self.total_bet;
+        if one >= two {
+            (0, (one - two).min(pot))
+        } else {
+            (1, (two - one).min(pot))
+        }
+    }
+
+    /// Chips a seat receives from `pot` for a single run's result
+    fn run_payout(winner: HandWinner, seat: u8, pot: u64) -> u64 {
+        match winner {
+            HandWinner::None => 0,
+            HandWinner::Split => pot / 2 + if seat == 0 { pot % 2 } else { 0 },
+            winner => if winner.seat() == Some(seat) { pot } else { 0 },
+        }
+    }
+
+    /// Check if a seat has agreed to run the board twice
+    pub fn has_run_twice_consent(&self, seat: u8) -> bool {
+        self.get_seat_flag(hand_flags::P1_RUN_TWICE_CONSENT, seat)
+    }
+
+    /// Record a seat's agreement to run the board twice
+    pub fn set_run_twice_consent(&mut self, seat: u8) {
+        self.set_seat_flag(hand_flags::P1_RUN_TWICE_CONSENT, seat, true);
+    }
+
+    /// Check if a seat has revealed its hand rank on the second board
+    pub fn has_second_revealed(&self, seat: u8) -> bool {
+        self.get_seat_flag(hand_flags::P1_SECOND_REVEALED, seat)
+    }
+
+    /// Store a seat's hand rank on the second board
+    pub fn set_second_rank(&mut self, seat: u8, hand_rank: u64) {
         match seat {
-            0 => self.p1_bet_this_street,
-            1 => self.p2_bet_this_street,
-            _ => 0,
+            0 => self.p1_second_rank = hand_rank,
+            1 => self.p2_second_rank = hand_rank,
+            _ => return,
         }
+        self.set_seat_flag(hand_flags::P1_SECOND_REVEALED, seat, true);
     }
 
-    /// Add to player's bet this street
-    pub fn add_bet(&mut self, seat: u8, amount: u64) {
+    /// Check if a seat has agreed to the proposed result
+    pub fn has_agreed_result(&self, seat: u8) -> bool {
+        self.get_seat_flag(hand_flags::P1_RESULT_AGREED, seat)
+    }
+
+    /// Record a seat's agreement to the proposed result
+    pub fn set_agreed_result(&mut self, seat: u8) {
+        self.set_seat_flag(hand_flags::P1_RESULT_AGREED, seat, true);
+    }
+
+    /// Have both seats agreed on the same result?
+    pub fn result_agreed(&self) -> bool {
+        self.has_agreed_result(0) && self.has_agreed_result(1)
+    }
+
+    /// Check if a seat mucked its hand at showdown
+    pub fn has_mucked(&self, seat: u8) -> bool {
+        self.get_seat_flag(hand_flags::P1_MUCKED, seat)
+    }
+
+    /// Record a seat mucking its hand at showdown
+    pub fn set_mucked(&mut self, seat: u8) {
+        self.set_seat_flag(hand_flags::P1_MUCKED, seat, true);
+    }
+
+    /// The seat still to show once its opponent has revealed (None unless
+    /// exactly one seat revealed and the other has not mucked)
+    pub fn unrevealed_seat(&self) -> Option<u8> {
+        match (self.has_revealed(0), self.has_revealed(1)) {
+            (true, false) if !self.has_mucked(1) => Some(1),
+            (false, true) if !self.has_mucked(0) => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Is the first showdown reveal reserved for `action_on` (the last
+    /// aggressor)? True until someone reveals or mucks; with a player
+    /// all-in both hands are tabled in any order.
+    pub fn first_reveal_ordered(&self) -> bool {
+        !self.has_revealed(0)
+            && !self.has_revealed(1)
+            && !self.has_mucked(0)
+            && !self.has_mucked(1)
+            && !self.any_all_in()
+    }
+
+    /// Has a seat revealed its hand at showdown?
+    pub fn has_revealed(&self, seat: u8) -> bool {
+        self.get_seat_flag(hand_flags::P1_REVEALED, seat)
+    }
+
+    /// Record a seat revealing its hand at showdown
+    pub fn set_revealed(&mut self, seat: u8) {
+        self.set_seat_flag(hand_flags::P1_REVEALED, seat, true);
+    }
+
+    /// Have both seats revealed their hands?
+    pub fn both_revealed(&self) -> bool {
+        self.has_revealed(0) && self.has_revealed(1)
+    }
+
+    /// Can the showdown be a split? Only when both ranks were proven with a
+    /// reveal and neither seat mucked
+    pub fn split_proven(&self) -> bool {
+        self.both_revealed() && !self.has_mucked(0) && !self.has_mucked(1)
+    }
+
+    /// The first board in deal order (255 for cards not yet revealed)
+    pub fn first_board(&self) -> [u8; 5] {
+        [self.flop[0], self.flop[1], self.flop[2], self.turn, self.river]
+    }
+
+    /// A seat's hole card commitments, as a SHOWDOWN proof's public inputs
+    pub fn hole_commits(&self, seat: u8) -> [[u8; 32]; 2] {
         match seat {
-            0 => {
-                self.p1_bet_this_street = self.p1_bet_this_street.saturating_add(amount);
-                self.p1_total_bet = self.p1_total_bet.saturating_add(amount);
-            }
-            1 => {
-                self.p2_bet_this_street = self.p2_bet_this_street.saturating_add(amount);
-                self.p2_total_bet = self.p2_total_bet.saturating_add(amount);
-            }
-            _ => {}
+            0 => self.p1_hole_commits,
+            _ => self.p2_hole_commits,
         }
-        self.pot = self.pot.saturating_add(amount);
+    }
+
+    /// Number of community cards revealed so far on the first board
+    pub fn community_cards_revealed(&self) -> u8 {
+        if self.river_revealed() {
+            5
+        } else if self.turn_revealed() {
+            4
+        } else if self.flop_revealed() {
+            3
+        } else {
+            0
+        }
+    }
+
+    /// Whether the current street's community cards are on the board.
+    /// Preflop has none to reveal, so it always counts as revealed.
+    pub fn street_revealed(&self) -> bool {
+        match self.stage {
+            HandStage::Flop => self.flop_revealed(),
+            HandStage::Turn => self.turn_revealed(),
+            HandStage::River => self.river_revealed(),
+            _ => true,
+        }
+    }
+
+    /// Add to player's bet this street, failing rather than saturating on
+    /// overflow so the pot always equals the sum of both players' bets
+    pub fn add_bet(&mut self, seat: u8, amount: u64) -> Result<()> {
+        require!(seat < 2, ZkPokerError::PlayerNotAtTable);
+        let seat = seat as usize;
+        self.bet_this_street[seat] = self.bet_this_street[seat]
+            .checked_add(amount)
+            .ok_or(ZkPokerError::ArithmeticOverflow)?;
+        self.total_bet[seat] = self.total_bet[seat].checked_add(amount).ok_or(ZkPokerError::ArithmeticOverflow)?;
+        self.pot = self.pot.checked_add(amount).ok_or(ZkPokerError::ArithmeticOverflow)?;
+
+        debug_assert_eq!(Some(self.pot), self.total_bet[0].checked_add(self.total_bet[1]));
+        Ok(())
+    }
+
+    /// Take back part of a seat's bet this street (an uncalled excess);
+    /// the caller returns the chips to the seat's stack
+    pub fn return_bet(&mut self, seat: u8, amount: u64) -> Result<()> {
+        require!(seat < 2, ZkPokerError::PlayerNotAtTable);
+        let seat = seat as usize;
+        self.bet_this_street[seat] = self.bet_this_street[seat]
+            .checked_sub(amount)
+            .ok_or(ZkPokerError::ArithmeticOverflow)?;
+        self.total_bet[seat] = self.total_bet[seat].checked_sub(amount).ok_or(ZkPokerError::ArithmeticOverflow)?;
+        self.pot = self.pot.checked_sub(amount).ok_or(ZkPokerError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Take `rake` out of the pot; raked chips leave play for good
+    pub fn take_rake(&mut self, rake: u64) {
+        self.pot -= rake;
+        self.chips_in_play -= rake;
+    }
+
+    /// Verify no chips were created or destroyed since the hand began: both
+    /// stacks plus the pot must still add up to `chips_in_play`. Only
+    /// enforced with the `invariants` feature.
+    pub fn check_chip_conservation(&self, table: &Table) -> Result<()> {
+        if cfg!(feature = "invariants") {
+            let total = table
+                .player_one_chips
+                .checked_add(table.player_two_chips)
+                .and_then(|chips| chips.checked_add(self.pot));
+            require!(total == Some(self.chips_in_play), ZkPokerError::ChipConservationViolated);
+        }
+        Ok(())
     }
 
     /// Check if player has acted this street
     pub fn has_acted_this_street(&self, seat: u8) -> bool {
-        match seat {
-            0 => self.p1_acted_this_street,
-            1 => self.p2_acted_this_street,
-            _ => false,
-        }
+        self.get_seat_flag(hand_flags::P1_ACTED_THIS_STREET, seat)
     }
 
     /// Set player acted this street
     pub fn set_acted_this_street(&mut self, seat: u8) {
-        match seat {
-            0 => self.p1_acted_this_street = true,
-            1 => self.p2_acted_this_street = true,
-            _ => {}
-        }
+        self.set_seat_flag(hand_flags::P1_ACTED_THIS_STREET, seat, true);
+    }
+
+    /// Clear player acted this street (they must act again)
+    pub fn clear_acted_this_street(&mut self, seat: u8) {
+        self.set_seat_flag(hand_flags::P1_ACTED_THIS_STREET, seat, false);
     }
 
     /// Reset street state for new betting round
     pub fn reset_street(&mut self) {
-        self.p1_bet_this_street = 0;
-        self.p2_bet_this_street = 0;
+        self.bet_this_street = [0; 2];
         self.current_bet = 0;
-        self.p1_acted_this_street = false;
-        self.p2_acted_this_street = false;
+        self.last_raise_size = 0;
+        self.clear_acted_this_street(0);
+        self.clear_acted_this_street(1);
         self.last_aggressor = 255;
     }
 
+    /// Smallest amount a raise must add to the current bet
+    pub fn min_raise_increment(&self, big_blind: u64) -> u64 {
+        self.last_raise_size.max(big_blind)
+    }
+
+    /// Whether raising the current bet by `increment` is a full raise that
+    /// reopens the action; a short all-in or capped raise lets the seat that
+    /// already acted only call or fold
+    pub fn reopens_action(&self, increment: u64, big_blind: u64) -> bool {
+        increment >= self.min_raise_increment(big_blind)
+    }
+
     /// Get the other seat
     pub fn other_seat(&self, seat: u8) -> u8 {
         if seat == 0 { 1 } else { 0 }
@@ -421,42 +877,194 @@ impl Hand {
     /// Check if betting round is complete
     pub fn is_betting_complete(&self) -> bool {
         // If someone folded, betting is complete
-        if self.p1_folded || self.p2_folded {
+        if self.has_folded(0) || self.has_folded(1) {
             return true;
         }
 
         // If both players are all-in, betting is complete
-        if self.p1_all_in && self.p2_all_in {
+        if self.both_all_in() {
             return true;
         }
 
         // Both must have acted and bets must be equal
-        let both_acted = self.p1_acted_this_street && self.p2_acted_this_street;
-        let bets_equal = self.p1_bet_this_street == self.p2_bet_this_street;
+        let both_acted = self.has_acted_this_street(0) && self.has_acted_this_street(1);
+        let bets_equal = self.bet_this_street[0] == self.bet_this_street[1];
 
         both_acted && bets_equal
     }
 
+    /// Check if a player is all-in and no more betting can happen, so the
+    /// remaining community cards can be run out together
+    pub fn is_all_in_runout(&self) -> bool {
+        let street_settled = self.bet_this_street[0] == self.bet_this_street[1];
+        let post_flop = matches!(self.stage, HandStage::Flop | HandStage::Turn | HandStage::River);
+
+        self.any_all_in() && street_settled && post_flop && self.remaining_players() == 2
+    }
+
+    /// Refuse to complete or pay out a hand that was already settled. Every
+    /// completion path (fold, claim, timeout) checks this first, so a pot
+    /// can never be credited twice.
+    pub fn require_not_complete(&self) -> Result<()> {
+        require!(
+            self.stage != HandStage::Complete && !self.pot_claimed(),
+            ZkPokerError::HandAlreadyComplete
+        );
+        Ok(())
+    }
+
     /// Count remaining players (not folded)
     pub fn remaining_players(&self) -> u8 {
-        let mut count = 0;
-        if !self.p1_folded {
-            count += 1;
-        }
-        if !self.p2_folded {
-            count += 1;
-        }
-        count
+        (0..2).filter(|&seat| !self.has_folded(seat)).count() as u8
     }
 
     /// Get the non-folded player seat (only valid if exactly one player folded)
     pub fn non_folded_seat(&self) -> Option<u8> {
-        if self.p1_folded && !self.p2_folded {
-            Some(1)
-        } else if !self.p1_folded && self.p2_folded {
-            Some(0)
-        } else {
-            None
+        match (self.has_folded(0), self.has_folded(1)) {
+            (true, false) => Some(1),
+            (false, true) => Some(0),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_FLAGS: [u32; 29] = [
+        hand_flags::P1_SEED_COMMITTED,
+        hand_flags::P2_SEED_COMMITTED,
+        hand_flags::P1_SEED_REVEALED,
+        hand_flags::P2_SEED_REVEALED,
+        hand_flags::P1_CARDS_COMMITTED,
+        hand_flags::P2_CARDS_COMMITTED,
+        hand_flags::P1_REVEALED,
+        hand_flags::P2_REVEALED,
+        hand_flags::P1_FOLDED,
+        hand_flags::P2_FOLDED,
+        hand_flags::P1_ALL_IN,
+        hand_flags::P2_ALL_IN,
+        hand_flags::P1_ACTED_THIS_STREET,
+        hand_flags::P2_ACTED_THIS_STREET,
+        hand_flags::FLOP_REVEALED,
+        hand_flags::TURN_REVEALED,
+        hand_flags::RIVER_REVEALED,
+        hand_flags::POT_CLAIMED,
+        hand_flags::P1_RUN_TWICE_CONSENT,
+        hand_flags::P2_RUN_TWICE_CONSENT,
+        hand_flags::SECOND_BOARD_REVEALED,
+        hand_flags::P1_SECOND_REVEALED,
+        hand_flags::P2_SECOND_REVEALED,
+        hand_flags::P1_RESULT_AGREED,
+        hand_flags::P2_RESULT_AGREED,
+        hand_flags::P1_MUCKED,
+        hand_flags::P2_MUCKED,
+        hand_flags::DEAL_VERIFIED,
+        hand_flags::FAST_SHOWDOWN,
+    ];
+
+    /// A hand as freshly allocated: every byte zero
+    fn blank_hand() -> Hand {
+        Hand::deserialize(&mut &vec![0u8; Hand::LEN - 8][..]).unwrap()
+    }
+
+    #[test]
+    fn flags_are_distinct_bits() {
+        let mut seen = 0u32;
+        for flag in ALL_FLAGS {
+            assert_eq!(flag.count_ones(), 1);
+            assert_eq!(seen & flag, 0);
+            seen |= flag;
+        }
+    }
+
+    #[test]
+    fn every_flag_toggles_independently() {
+        let mut hand = blank_hand();
+        for flag in ALL_FLAGS {
+            // From all clear and from every other bit set
+            for others in [0, u32::MAX & !flag] {
+                hand.flags = others;
+                hand.set_flag(flag, true);
+                assert!(hand.get_flag(flag));
+                assert_eq!(hand.flags, others | flag);
+
+                hand.set_flag(flag, false);
+                assert!(!hand.get_flag(flag));
+                assert_eq!(hand.flags, others);
+            }
         }
     }
+
+    #[test]
+    fn named_accessors_touch_only_their_flag() {
+        type Accessors = (fn(&Hand) -> bool, fn(&mut Hand, bool), u32);
+        let accessors: [Accessors; 13] = [
+            (Hand::p1_seed_committed, Hand::set_p1_seed_committed, hand_flags::P1_SEED_COMMITTED),
+            (Hand::p2_seed_committed, Hand::set_p2_seed_committed, hand_flags::P2_SEED_COMMITTED),
+            (Hand::p1_seed_revealed, Hand::set_p1_seed_revealed, hand_flags::P1_SEED_REVEALED),
+            (Hand::p2_seed_revealed, Hand::set_p2_seed_revealed, hand_flags::P2_SEED_REVEALED),
+            (Hand::p1_cards_committed, Hand::set_p1_cards_committed, hand_flags::P1_CARDS_COMMITTED),
+            (Hand::p2_cards_committed, Hand::set_p2_cards_committed, hand_flags::P2_CARDS_COMMITTED),
+            (Hand::flop_revealed, Hand::set_flop_revealed, hand_flags::FLOP_REVEALED),
+            (Hand::turn_revealed, Hand::set_turn_revealed, hand_flags::TURN_REVEALED),
+            (Hand::river_revealed, Hand::set_river_revealed, hand_flags::RIVER_REVEALED),
+            (Hand::pot_claimed, Hand::set_pot_claimed, hand_flags::POT_CLAIMED),
+            (Hand::second_board_revealed, Hand::set_second_board_revealed, hand_flags::SECOND_BOARD_REVEALED),
+            (Hand::deal_verified, Hand::set_deal_verified, hand_flags::DEAL_VERIFIED),
+            (Hand::fast_showdown, Hand::set_fast_showdown, hand_flags::FAST_SHOWDOWN),
+        ];
+
+        let mut hand = blank_hand();
+        for (get, set, flag) in accessors {
+            set(&mut hand, true);
+            assert!(get(&hand));
+            assert_eq!(hand.flags, flag);
+
+            set(&mut hand, false);
+            assert!(!get(&hand));
+            assert_eq!(hand.flags, 0);
+        }
+    }
+
+    #[test]
+    fn seat_accessors_set_the_seats_own_bit() {
+        type SeatAccessors = (fn(&Hand, u8) -> bool, fn(&mut Hand, u8), u32);
+        let accessors: [SeatAccessors; 8] = [
+            (Hand::has_folded, Hand::set_folded, hand_flags::P1_FOLDED),
+            (Hand::is_all_in, Hand::set_all_in, hand_flags::P1_ALL_IN),
+            (Hand::has_acted_this_street, Hand::set_acted_this_street, hand_flags::P1_ACTED_THIS_STREET),
+            (Hand::has_run_twice_consent, Hand::set_run_twice_consent, hand_flags::P1_RUN_TWICE_CONSENT),
+            (Hand::has_second_revealed, |hand, seat| hand.set_second_rank(seat, 1), hand_flags::P1_SECOND_REVEALED),
+            (Hand::has_agreed_result, Hand::set_agreed_result, hand_flags::P1_RESULT_AGREED),
+            (Hand::has_mucked, Hand::set_mucked, hand_flags::P1_MUCKED),
+            (Hand::has_revealed, Hand::set_revealed, hand_flags::P1_REVEALED),
+        ];
+
+        for (get, set, p1_flag) in accessors {
+            for seat in 0..2u8 {
+                let mut hand = blank_hand();
+                set(&mut hand, seat);
+                assert_eq!(hand.flags, p1_flag << seat);
+                assert!(get(&hand, seat));
+                assert!(!get(&hand, 1 - seat));
+            }
+        }
+
+        // Acted is the one seat flag that is also cleared mid-hand
+        let mut hand = blank_hand();
+        hand.set_acted_this_street(0);
+        hand.set_acted_this_street(1);
+        hand.clear_acted_this_street(1);
+        assert_eq!(hand.flags, hand_flags::P1_ACTED_THIS_STREET);
+    }
+
+    #[test]
+    fn init_clears_all_flags() {
+        let mut hand = blank_hand();
+        hand.flags = u32::MAX;
+        hand.init(Pubkey::new_unique(), 4, Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), 0, 0, 255);
+        assert_eq!(hand.flags, 0);
+    }
 }
@@ -2,8 +2,12 @@ pub mod global;
 pub mod table;
 pub mod hand;
 pub mod proof_buffer;
+pub mod table_stats;
+pub mod player_profile;
 
 pub use global::*;
 pub use table::*;
 pub use hand::*;
 pub use proof_buffer::*;
+pub use table_stats::*;
+pub use player_profile::*;
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::Hand;
+
+/// Lifetime results for a player across every table
+/// Seeds: ["profile", player.key()]
+#[account]
+pub struct PlayerProfile {
+    /// Player this profile belongs to
+    pub player: Pubkey,
+
+    /// Total hands completed while seated
+    pub hands_played: u64,
+
+    /// Hands won outright (splits are not counted)
+    pub hands_won: u64,
+
+    /// Chips won minus chips put into pots
+    pub net_winnings: i64,
+
+    /// Largest single pot won outright
+    pub largest_pot_won: u64,
+}
+
+impl PlayerProfile {
+    /// Account size for rent calculation
+    /// 8 (discriminator) + 32 (player) + 8 + 8 + 8 + 8 = 72 bytes
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8;
+
+    /// Record a completed hand for the player in `seat`. The account is
+    /// created lazily, so the player key is (re)written on every completion.
+    pub fn record_hand(&mut self, player: Pubkey, hand: &Hand, seat: u8, pot: u64) {
+        self.player = player;
+        self.hands_played = self.hands_played.saturating_add(1);
+
+        let payout = hand.payout(seat, pot) as i64;
+        let contributed = hand.get_total_bet(seat) as i64;
+        self.net_winnings = self.net_winnings.saturating_add(payout.saturating_sub(contributed));
+
+        if hand.winner.seat() == Some(seat) {
+            self.hands_won = self.hands_won.saturating_add(1);
+            self.largest_pot_won = self.largest_pot_won.max(pot);
+        }
+    }
+}
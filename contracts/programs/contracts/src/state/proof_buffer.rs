@@ -1,19 +1,29 @@
 use anchor_lang::prelude::*;
+use crate::state::HandStage;
+use crate::constants::{MAX_PROOF_BUFFER_SIZE, MAX_CHUNK_SIZE};
 
 /// Type of ZK proof being stored in the buffer
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum ProofType {
     /// DECK circuit - hole card commitments
+    #[default]
     Deck = 0,
     /// REVEAL circuit - community card reveals
     Reveal = 1,
     /// SHOWDOWN circuit - hand reveal at showdown
     Showdown = 2,
+    /// DEAL circuit - deck shuffle derived from the deck seed
+    Deal = 3,
 }
 
-impl Default for ProofType {
-    fn default() -> Self {
-        ProofType::Deck
+impl ProofType {
+    /// Check if a proof of this type can be consumed in the given hand stage
+    pub fn is_valid_for_stage(&self, stage: HandStage) -> bool {
+        match self {
+            ProofType::Deck | ProofType::Deal => stage == HandStage::CardCommit,
+            ProofType::Reveal => matches!(stage, HandStage::Flop | HandStage::Turn | HandStage::River),
+            ProofType::Showdown => stage == HandStage::Showdown,
+        }
     }
 }
 
@@ -27,7 +37,7 @@ pub struct ProofBuffer {
     /// Player who owns this buffer
     pub player: Pubkey,
 
-    /// Type of proof (Deck/Reveal/Showdown)
+    /// Type of proof (Deck/Reveal/Showdown/Deal)
     pub proof_type: ProofType,
 
     /// Total expected size of proof data
@@ -58,6 +68,13 @@ impl ProofBuffer {
         + 1                            // bump
         + 4;                           // vec length prefix
 
+    /// Validate a requested proof size (non-zero and bounded)
+    pub fn validate_size(proof_size: u16) -> Result<()> {
+        require!(proof_size > 0, ProofBufferError::SizeZero);
+        require!(proof_size <= MAX_PROOF_BUFFER_SIZE, ProofBufferError::SizeTooLarge);
+        Ok(())
+    }
+
     /// Calculate full account size for given proof size
     pub fn space(proof_size: u16) -> usize {
         Self::BASE_LEN + proof_size as usize
@@ -83,12 +100,30 @@ impl ProofBuffer {
         self.data = vec![0u8; size as usize];
     }
 
+    /// Resize the buffer to `size` bytes, keeping uploaded data. The account
+    /// must already have been reallocated to `space(size)`.
+    pub fn resize(&mut self, size: u16) {
+        self.size = size;
+        self.data.resize(size as usize, 0);
+        self.complete = self.uploaded >= self.size;
+    }
+
+    /// Clear uploaded data so the buffer can be refilled at its current size
+    pub fn reset(&mut self) {
+        self.uploaded = 0;
+        self.complete = false;
+        self.data.fill(0);
+    }
+
     /// Upload a chunk of proof data at given offset
     pub fn upload_chunk(&mut self, offset: u16, chunk: &[u8]) -> Result<()> {
         let start = offset as usize;
         let end = start + chunk.len();
 
         // Validate bounds
+        require!(!chunk.is_empty(), ProofBufferError::EmptyChunk);
+        require!(chunk.len() <= MAX_CHUNK_SIZE, ProofBufferError::ChunkTooLarge);
+        require!(offset < self.size, ProofBufferError::OffsetOutOfRange);
         require!(end <= self.size as usize, ProofBufferError::ChunkOverflow);
 
         // Copy data
@@ -122,4 +157,14 @@ pub enum ProofBufferError {
     ChunkOverflow,
     #[msg("Buffer upload not complete")]
     IncompleteBuffer,
+    #[msg("Proof buffer size exceeds the maximum")]
+    SizeTooLarge,
+    #[msg("Proof buffer size must be non-zero")]
+    SizeZero,
+    #[msg("Proof chunk must not be empty")]
+    EmptyChunk,
+    #[msg("Proof chunk exceeds the maximum chunk size")]
+    ChunkTooLarge,
+    #[msg("Chunk offset is past the end of the buffer")]
+    OffsetOutOfRange,
 }
@@ -1,9 +1,18 @@
 use anchor_lang::prelude::*;
+use crate::errors::ZkPokerError;
+use crate::constants::{
+    MAX_ALLOWLIST, MAX_BLIND_LEVELS, BPS_DENOMINATOR, DEFAULT_ACTION_TIMEOUT, MIN_ACTION_TIMEOUT,
+    MAX_ACTION_TIMEOUT, DEFAULT_TIME_BANK, MAX_TIME_BANK, MIN_BLIND_RATIO, MAX_BLIND_RATIO,
+    MAX_DISPUTE_WINDOW, MAX_RAKE_BPS, MAX_REJOIN_COOLDOWN, DECK_SIZE, SHORT_DECK_SIZE,
+    MIN_PROOF_TIMEOUT, MAX_COMMIT_TIMEOUT,
+};
+use crate::state::{Hand, HandStage};
 
 /// Table status enum
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum TableStatus {
     /// Waiting for second player to join
+    #[default]
     Waiting = 0,
     /// Hand in progress
     Playing = 1,
@@ -11,10 +20,239 @@ pub enum TableStatus {
     Between = 2,
 }
 
-impl Default for TableStatus {
-    fn default() -> Self {
-        TableStatus::Waiting
+/// Who may join a table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TableAccess {
+    /// Anyone can join
+    #[default]
+    Public = 0,
+    /// Only players on the creator's allowlist can join
+    Allowlist = 1,
+}
+
+/// Who acts first on each street
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PositionRule {
+    /// Heads-up standard: the button (small blind) first preflop, the big
+    /// blind first on every later street
+    #[default]
+    Standard = 0,
+    /// The button acts first on every street (dealer-first variants)
+    ButtonFirst = 1,
+}
+
+/// What happens to a seat that runs out of time on a betting decision
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TimeoutPolicy {
+    /// The timed-out seat forfeits the pot
+    #[default]
+    Forfeit = 0,
+    /// Disconnect protection: check when there is no bet to face, fold
+    /// only when facing one
+    AutoCheckFold = 1,
+}
+
+/// A single level of an escalating blind schedule
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct BlindLevel {
+    /// How long this level lasts (seconds)
+    pub duration: i64,
+
+    /// Small blind for this level
+    pub small_blind: u64,
+
+    /// Big blind for this level
+    pub big_blind: u64,
+}
+
+impl BlindLevel {
+    /// Serialized size: 8 (duration) + 8 (small_blind) + 8 (big_blind)
+    pub const LEN: usize = 8 + 8 + 8;
+}
+
+/// Options a table is created with (the `create_table` argument)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TableConfig {
+    /// Small blind amount
+    pub small_blind: u64,
+
+    /// Big blind amount
+    pub big_blind: u64,
+
+    /// Minimum buy-in (at least 10 big blinds)
+    pub min_buy_in: u64,
+
+    /// Maximum buy-in
+    pub max_buy_in: u64,
+
+    /// Seconds to act before timing out (None = default)
+    pub action_timeout: Option<i64>,
+
+    /// Time bank per seat in seconds (None = default)
+    pub time_bank: Option<i64>,
+
+    /// Require both players to ready up between hands
+    pub auto_continue: bool,
+
+    /// Escalating blind levels, timed from table creation
+    pub blind_schedule: Vec<BlindLevel>,
+
+    /// Hands after which the table closes (0 = unlimited)
+    pub max_hands: u64,
+
+    /// Who may join the table
+    pub access: TableAccess,
+
+    /// Let the button straddle before the deal
+    pub straddle_allowed: bool,
+
+    /// Buy-ins and top-ups must be whole multiples of the big blind
+    pub require_bb_multiple: bool,
+
+    /// Seconds a showdown result can be challenged before payout
+    pub dispute_window: i64,
+
+    /// Maximum buy-in in big blinds (0 = no cap)
+    pub max_buy_in_bb: u16,
+
+    /// Rake in basis points
+    pub rake_bps: u16,
+
+    /// Most rake per pot (0 = uncapped)
+    pub rake_cap: u64,
+
+    /// Only rake pots that saw a flop
+    pub rake_only_after_flop: bool,
+
+    /// Hands to play before leaving with winnings (0 = leave any time)
+    pub min_hands_before_leave: u64,
+
+    /// Seat the smaller pubkey in seat 0 before the first hand
+    pub deterministic_seating: bool,
+
+    /// Draw the first hand's button from both players' seeds
+    pub random_button: bool,
+
+    /// Treat a seat silent past the action timeout after its opponent
+    /// showed as mucked
+    pub auto_muck: bool,
+
+    /// Most a pot may hold (0 = uncapped)
+    pub pot_cap: u64,
+
+    /// Most a seat may wager per hand in big blinds (0 = uncapped)
+    pub hand_bet_cap_bb: u16,
+
+    /// Which seat opens the action on each street
+    pub first_to_act_rule: PositionRule,
+
+    /// How a timed-out betting decision is resolved
+    pub timeout_policy: TimeoutPolicy,
+
+    /// Seconds a betting action must wait after the hand's last action
+    pub min_action_interval: i64,
+
+    /// Seconds a player who left must wait before rejoining
+    pub rejoin_cooldown: i64,
+
+    /// Cards in the deck: `DECK_SIZE` or `SHORT_DECK_SIZE`
+    pub deck_size: u8,
+
+    /// Seconds to commit hole cards (None = default)
+    pub commit_deadline: Option<i64>,
+
+    /// Both seats pay half of each hand's rent
+    pub split_hand_rent: bool,
+
+    /// The button posts the big blind and the other seat the small blind
+    pub button_is_big_blind: bool,
+}
+
+impl TableConfig {
+    /// Action timeout, defaulted when unset
+    pub fn action_timeout(&self) -> i64 {
+        self.action_timeout.unwrap_or(DEFAULT_ACTION_TIMEOUT)
     }
+
+    /// Time bank per seat, defaulted when unset
+    pub fn time_bank(&self) -> i64 {
+        self.time_bank.unwrap_or(DEFAULT_TIME_BANK)
+    }
+
+    /// Card commits wait on proof generation, so they get at least the action
+    /// timeout and never less than the proof floor; fast tables are bumped up
+    fn min_commit_deadline(&self) -> i64 {
+        self.action_timeout().max(MIN_PROOF_TIMEOUT)
+    }
+
+    /// Hole card commit deadline, defaulted when unset
+    pub fn commit_deadline(&self) -> i64 {
+        self.commit_deadline.unwrap_or(self.min_commit_deadline())
+    }
+
+    /// Check every option is in range and consistent with the others
+    pub fn validate(&self) -> Result<()> {
+        let big_blind = self.big_blind;
+        require!(is_valid_blind_ratio(self.small_blind, big_blind), ZkPokerError::InvalidTableConfig);
+        let min_allowed_buy_in = big_blind.checked_mul(10).ok_or(ZkPokerError::InvalidTableConfig)?;
+        require!(self.min_buy_in >= min_allowed_buy_in, ZkPokerError::InvalidTableConfig); // At least 10 BB
+        require!(self.max_buy_in >= self.min_buy_in, ZkPokerError::InvalidTableConfig);
+        if self.max_buy_in_bb > 0 {
+            let max_allowed_buy_in = big_blind.saturating_mul(self.max_buy_in_bb as u64);
+            require!(self.max_buy_in <= max_allowed_buy_in, ZkPokerError::InvalidTableConfig);
+        }
+
+        let timeout = self.action_timeout();
+        require!((MIN_ACTION_TIMEOUT..=MAX_ACTION_TIMEOUT).contains(&timeout), ZkPokerError::InvalidTimeoutConfig);
+        // The throttle must leave time to act before the seat times out
+        require!((0..timeout).contains(&self.min_action_interval), ZkPokerError::InvalidTimeoutConfig);
+        require!(
+            (self.min_commit_deadline()..=MAX_COMMIT_TIMEOUT).contains(&self.commit_deadline()),
+            ZkPokerError::InvalidTimeoutConfig
+        );
+
+        require!((0..=MAX_TIME_BANK).contains(&self.time_bank()), ZkPokerError::InvalidTimeoutConfig);
+        require!((0..=MAX_DISPUTE_WINDOW).contains(&self.dispute_window), ZkPokerError::InvalidTimeoutConfig);
+        require!((0..=MAX_REJOIN_COOLDOWN).contains(&self.rejoin_cooldown), ZkPokerError::InvalidTimeoutConfig);
+        require!(self.rake_bps <= MAX_RAKE_BPS, ZkPokerError::InvalidTableConfig);
+        require!(
+            self.deck_size == DECK_SIZE || self.deck_size == SHORT_DECK_SIZE,
+            ZkPokerError::InvalidTableConfig
+        );
+        // Deterministic seating fixes the opening button in seat 0
+        require!(!(self.deterministic_seating && self.random_button), ZkPokerError::InvalidTableConfig);
+        // A capped pot splits evenly and must at least hold both big blinds
+        if self.pot_cap > 0 {
+            require!(self.pot_cap % 2 == 0, ZkPokerError::InvalidTableConfig);
+            require!(
+                self.blind_schedule
+                    .iter()
+                    .map(|level| level.big_blind)
+                    .chain([big_blind])
+                    .all(|bb| bb <= self.pot_cap / 2),
+                ZkPokerError::InvalidTableConfig
+            );
+        }
+
+        require!(self.blind_schedule.len() <= MAX_BLIND_LEVELS, ZkPokerError::InvalidTableConfig);
+        for level in &self.blind_schedule {
+            require!(level.duration > 0, ZkPokerError::InvalidTableConfig);
+            require!(
+                is_valid_blind_ratio(level.small_blind, level.big_blind),
+                ZkPokerError::InvalidTableConfig
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Blinds are valid when the small blind is positive and the big blind is
+/// two to four times it
+fn is_valid_blind_ratio(small_blind: u64, big_blind: u64) -> bool {
+    small_blind > 0
+        && small_blind.checked_mul(MIN_BLIND_RATIO).is_some_and(|min| big_blind >= min)
+        && big_blind <= small_blind.saturating_mul(MAX_BLIND_RATIO)
 }
 
 /// Table account representing a poker table
@@ -24,21 +262,118 @@ pub struct Table {
     /// Unique table identifier
     pub table_id: u64,
 
+    /// Wallet that created the table (receives rent on close)
+    pub creator: Pubkey,
+
+    /// Who may join the table
+    pub access: TableAccess,
+
+    /// Players allowed to join an allowlist table
+    pub allowed: [Option<Pubkey>; MAX_ALLOWLIST],
+
+    /// SPL token mint chips are denominated in
+    pub mint: Pubkey,
+
     /// Small blind amount (in USDC base units)
     pub small_blind: u64,
 
     /// Big blind amount (in USDC base units)
     pub big_blind: u64,
 
+    /// Escalating blind levels, timed from table creation (first
+    /// `blind_levels` entries are used)
+    pub blind_schedule: [BlindLevel; MAX_BLIND_LEVELS],
+
+    /// Number of levels in the blind schedule (0 = static blinds)
+    pub blind_levels: u8,
+
     /// Minimum buy-in amount
     pub min_buy_in: u64,
 
     /// Maximum buy-in amount
     pub max_buy_in: u64,
 
+    /// Cap on the maximum buy-in in big blinds (0 = no cap)
+    pub max_buy_in_bb: u16,
+
+    /// Buy-ins and top-ups must be whole multiples of the big blind
+    pub require_bb_multiple: bool,
+
     /// Action timeout in seconds
     pub action_timeout: i64,
 
+    /// Seconds that must pass after the hand's last action before a
+    /// betting action is accepted (0 = no throttle)
+    pub min_action_interval: i64,
+
+    /// Seconds each seat has to commit its hole cards (proof generation
+    /// included) before it can be timed out
+    pub commit_deadline: i64,
+
+    /// Time bank each seat receives on joining (seconds)
+    pub time_bank: i64,
+
+    /// Remaining time bank per seat (seconds)
+    pub time_bank_remaining: [i64; 2],
+
+    /// Seconds after a showdown result during which it can be challenged
+    /// before the pot is paid (0 = instant payout). Ranks are proven at
+    /// reveal, so the window is only a backstop against a faulty verifier.
+    pub dispute_window: i64,
+
+    /// Rake taken from each pot in basis points (0 = no rake)
+    pub rake_bps: u16,
+
+    /// Maximum rake per pot (0 = uncapped)
+    pub rake_cap: u64,
+
+    /// Skip rake on hands that end before the flop (no flop, no drop)
+    pub rake_only_after_flop: bool,
+
+    /// Rake held in the vault that the authority has not collected yet
+    pub rake_owed: u64,
+
+    /// Require both players to ready up between hands; the second
+    /// ready_up starts the next hand
+    pub auto_continue: bool,
+
+    /// Per-seat readiness for the next hand (auto_continue tables)
+    pub ready: [bool; 2],
+
+    /// Let the button straddle to 2x the big blind before the deal
+    pub straddle_allowed: bool,
+
+    /// Before the first hand, seat the smaller pubkey in seat 0 regardless
+    /// of join order, so seating and the button follow from the two keys
+    pub deterministic_seating: bool,
+
+    /// Draw the first hand's button from both players' revealed seeds
+    /// instead of starting it in seat 0; that hand's blinds are posted
+    /// once the draw is made
+    pub random_button: bool,
+
+    /// At showdown, a seat that stays silent for the action timeout after
+    /// its opponent showed is treated as mucked when the pot is claimed
+    pub auto_muck: bool,
+
+    /// Which seat opens the action on each street
+    pub first_to_act_rule: PositionRule,
+
+    /// How a timed-out betting decision is resolved
+    pub timeout_policy: TimeoutPolicy,
+
+    /// Cards in the deck: `DECK_SIZE` or `SHORT_DECK_SIZE`; revealed card
+    /// indices must be below it
+    pub deck_size: u8,
+
+    /// Both seats pay half of each hand's rent and get half back when it is
+    /// closed, instead of the seat that starts the hand paying all of it
+    pub split_hand_rent: bool,
+
+    /// Heads-up variant where the button posts the big blind and the other
+    /// seat the small blind; the button still acts last after the flop
+    pub button_is_big_blind: bool,
+
     /// Player in seat 0 (None if empty)
     pub player_one: Option<Pubkey>,
 
@@ -51,6 +386,18 @@ pub struct Table {
     /// Player two chip stack
     pub player_two_chips: u64,
 
+    /// Per-seat chips brought to the table (buy-in plus top-ups)
+    pub bought_in: [u64; 2],
+
+    /// Per-seat value of `hands_played` when the player sat down
+    pub joined_at_hand: [u64; 2],
+
+    /// Per-seat flag for players whose stack fell below the big blind;
+    /// cleared by adding chips or leaving. No hand is dealt while a seat
+    /// sits out, and a returning seat owes the big blind (see
+    /// `sit_in_if_covered`)
+    pub sitting_out: [bool; 2],
+
     /// Dealer button position (0 or 1)
     pub button: u8,
 
@@ -63,6 +410,34 @@ pub struct Table {
     /// Total hands played at this table
     pub hands_played: u64,
 
+    /// Hand limit after which the table closes (0 = unlimited)
+    pub max_hands: u64,
+
+    /// Hands a player must sit through before leaving with more chips
+    /// than they brought (0 = leave any time)
+    pub min_hands_before_leave: u64,
+
+    /// Seconds a player who left must wait before rejoining (0 = no wait)
+    pub rejoin_cooldown: i64,
+
+    /// Last player to leave from each seat, indexed by the seat they left
+    pub left_by: [Pubkey; 2],
+
+    /// When the player in `left_by` left (0 = nobody yet)
+    pub left_at: [i64; 2],
+
+    /// Largest pot a hand may build (0 = uncapped); each seat's share of a
+    /// hand stops at half the cap and reaching it counts as all-in
+    pub pot_cap: u64,
+
+    /// Cap game: most a seat may wager in one hand, in big blinds
+    /// (0 = no cap); reaching it counts as all-in
+    pub hand_bet_cap_bb: u16,
+
+    /// Set once `max_hands` is reached; no new hands or players, only
+    /// leaving until the table is closed
+    pub closing: bool,
+
     /// Table creation timestamp
     pub created_at: i64,
 
@@ -72,39 +447,114 @@ pub struct Table {
 
 impl Table {
     /// Account size for rent calculation
-    /// 8 (discriminator) + 8 + 8 + 8 + 8 + 8 + 8 + 33 + 33 + 8 + 8 + 1 + 1 + 33 + 8 + 8 + 1 = 180 bytes
-    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 33 + 33 + 8 + 8 + 1 + 1 + 33 + 8 + 8 + 1;
+    /// 8 (discriminator) + 8 + 32 + 1 + (33 * 4) + 32 + 8 + 8 + (24 * 8) + 1 + 8 + 8 + 2 + 1 + 8 + 8 + 8 + 8 + 16 + 8 + 2 + 8 + 1 + 8 + 1 + 2 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 33 + 33 + 8 + 8 + 16 + 16 + 2 + 1 + 1 + 33 + 8 + 8 + 8 + 8 + 64 + 16 + 8 + 2 + 1 + 8 + 1 = 801 bytes
+    pub const LEN: usize = 8 + 8 + 32 + 1 + (33 * MAX_ALLOWLIST) + 32 + 8 + 8 + (BlindLevel::LEN * MAX_BLIND_LEVELS) + 1
+        + 8 + 8 + 2 + 1 + 8 + 8 + 8 + 8 + 16 + 8 + 2 + 8 + 1 + 8 + 1 + 2 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 33 + 33 + 8 + 8 + 16 + 16 + 2 + 1 + 1 + 33 + 8 + 8 + 8 + 8 + 64 + 16 + 8 + 2 + 1 + 8 + 1;
 
-    /// Initialize a new table
+    /// Initialize a new table from a validated config
     pub fn init(
         &mut self,
         table_id: u64,
-        small_blind: u64,
-        big_blind: u64,
-        min_buy_in: u64,
-        max_buy_in: u64,
-        action_timeout: i64,
+        creator: Pubkey,
+        mint: Pubkey,
+        config: &TableConfig,
         created_at: i64,
         bump: u8,
     ) {
+        let blind_schedule = &config.blind_schedule;
         self.table_id = table_id;
-        self.small_blind = small_blind;
-        self.big_blind = big_blind;
-        self.min_buy_in = min_buy_in;
-        self.max_buy_in = max_buy_in;
-        self.action_timeout = action_timeout;
+        self.creator = creator;
+        self.access = config.access;
+        self.allowed = [None; MAX_ALLOWLIST];
+        self.mint = mint;
+        self.small_blind = config.small_blind;
+        self.big_blind = config.big_blind;
+        self.blind_schedule = [BlindLevel::default(); MAX_BLIND_LEVELS];
+        self.blind_schedule[..blind_schedule.len()].copy_from_slice(blind_schedule);
+        self.blind_levels = blind_schedule.len() as u8;
+        self.min_buy_in = config.min_buy_in;
+        self.max_buy_in = config.max_buy_in;
+        self.max_buy_in_bb = config.max_buy_in_bb;
+        self.require_bb_multiple = config.require_bb_multiple;
+        self.action_timeout = config.action_timeout();
+        self.min_action_interval = config.min_action_interval;
+        self.commit_deadline = config.commit_deadline();
+        self.time_bank = config.time_bank();
+        self.time_bank_remaining = [0; 2];
+        self.dispute_window = config.dispute_window;
+        self.rake_bps = config.rake_bps;
+        self.rake_cap = config.rake_cap;
+        self.rake_only_after_flop = config.rake_only_after_flop;
+        self.rake_owed = 0;
+        self.auto_continue = config.auto_continue;
+        self.ready = [false; 2];
+        self.straddle_allowed = config.straddle_allowed;
+        self.deterministic_seating = config.deterministic_seating;
+        self.random_button = config.random_button;
+        self.auto_muck = config.auto_muck;
+        self.first_to_act_rule = config.first_to_act_rule;
+        self.timeout_policy = config.timeout_policy;
+        self.deck_size = config.deck_size;
+        self.split_hand_rent = config.split_hand_rent;
+        self.button_is_big_blind = config.button_is_big_blind;
         self.player_one = None;
         self.player_two = None;
         self.player_one_chips = 0;
         self.player_two_chips = 0;
+        self.bought_in = [0; 2];
+        self.joined_at_hand = [0; 2];
+        self.sitting_out = [false; 2];
         self.button = 0;
         self.status = TableStatus::Waiting;
         self.current_hand = None;
         self.hands_played = 0;
+        self.max_hands = config.max_hands;
+        self.min_hands_before_leave = config.min_hands_before_leave;
+        self.rejoin_cooldown = config.rejoin_cooldown;
+        self.left_by = [Pubkey::default(); 2];
+        self.left_at = [0; 2];
+        self.pot_cap = config.pot_cap;
+        self.hand_bet_cap_bb = config.hand_bet_cap_bb;
+        self.closing = false;
         self.created_at = created_at;
         self.bump = bump;
     }
 
+    /// Most a seat may wager in one hand: half the pot cap and the cap
+    /// game's big blinds, whichever is lower (unlimited when neither is set)
+    pub fn seat_wager_limit(&self) -> u64 {
+        let pot_share = if self.pot_cap > 0 { self.pot_cap / 2 } else { u64::MAX };
+        let bet_cap = if self.hand_bet_cap_bb > 0 {
+            self.big_blind.saturating_mul(self.hand_bet_cap_bb as u64)
+        } else {
+            u64::MAX
+        };
+        pot_share.min(bet_cap)
+    }
+
+    /// Is the current (first) hand's button still drawn from its seeds?
+    pub fn draws_opening_button(&self) -> bool {
+        self.random_button && self.hands_played == 0
+    }
+
+    /// Blinds in effect at `now`: the scheduled level by time since creation
+    /// (the last level persists), or the static blinds without a schedule
+    pub fn current_blinds(&self, now: i64) -> (u64, u64) {
+        let levels = &self.blind_schedule[..self.blind_levels as usize];
+
+        let mut level_end = self.created_at;
+        for level in levels {
+            level_end = level_end.saturating_add(level.duration);
+            if now < level_end {
+                return (level.small_blind, level.big_blind);
+            }
+        }
+
+        levels
+            .last()
+            .map_or((self.small_blind, self.big_blind), |level| (level.small_blind, level.big_blind))
+    }
+
     /// Check if table has an empty seat
     pub fn has_empty_seat(&self) -> bool {
         self.player_one.is_none() || self.player_two.is_none()
@@ -126,6 +576,27 @@ impl Table {
         }
     }
 
+    /// Get the player key in a seat (default key if the seat is empty)
+    pub fn get_player(&self, seat: u8) -> Pubkey {
+        match seat {
+            0 => self.player_one.unwrap_or_default(),
+            1 => self.player_two.unwrap_or_default(),
+            _ => Pubkey::default(),
+        }
+    }
+
+    /// Mark a seat ready (or not) for the next hand
+    pub fn set_ready(&mut self, seat: u8, ready: bool) {
+        if let Some(slot) = self.ready.get_mut(seat as usize) {
+            *slot = ready;
+        }
+    }
+
+    /// Check if both seats have readied up
+    pub fn both_ready(&self) -> bool {
+        self.ready[0] && self.ready[1]
+    }
+
     /// Get player chips by seat number
     pub fn get_chips(&self, seat: u8) -> u64 {
         match seat {
@@ -158,28 +629,300 @@ impl Table {
         removed
     }
 
+    /// Get a seat's remaining time bank
+    pub fn get_time_bank(&self, seat: u8) -> i64 {
+        match seat {
+            0 | 1 => self.time_bank_remaining[seat as usize],
+            _ => 0,
+        }
+    }
+
+    /// Set a seat's remaining time bank
+    pub fn set_time_bank(&mut self, seat: u8, remaining: i64) {
+        if let 0 | 1 = seat {
+            self.time_bank_remaining[seat as usize] = remaining;
+        }
+    }
+
+    /// Seconds `seat` has to act at `stage` before it can be timed out:
+    /// betting decisions may run into the seat's time bank, card commits
+    /// get the commit deadline, other setup and showdown steps get the
+    /// action timeout alone
+    pub fn stage_timeout(&self, stage: HandStage, seat: u8) -> i64 {
+        match stage {
+            HandStage::CardCommit => self.commit_deadline,
+            _ if stage.is_betting_stage() => self.action_timeout.saturating_add(self.get_time_bank(seat)),
+            _ => self.action_timeout,
+        }
+    }
+
+    /// Charge time spent past the action timeout against a seat's time bank.
+    /// Fails if the seat has used up both the action timeout and its bank.
+    pub fn consume_time_bank(&mut self, seat: u8, elapsed: i64) -> Result<()> {
+        let overage = elapsed.saturating_sub(self.action_timeout);
+        if overage <= 0 {
+            return Ok(());
+        }
+
+        let remaining = self.get_time_bank(seat);
+        require!(overage <= remaining, ZkPokerError::ActionTimedOut);
+        self.set_time_bank(seat, remaining - overage);
+
+        Ok(())
+    }
+
     /// Get the other player's seat
     pub fn other_seat(&self, seat: u8) -> u8 {
         if seat == 0 { 1 } else { 0 }
     }
 
+    /// Seat a player with their buy-in, starting their time bank and the
+    /// hand count used by the hit-and-run rule
+    pub fn seat_player(&mut self, seat: u8, player: Pubkey, buy_in: u64) {
+        match seat {
+            0 => self.player_one = Some(player),
+            1 => self.player_two = Some(player),
+            _ => return,
+        }
+        self.set_chips(seat, buy_in);
+        self.set_time_bank(seat, self.time_bank);
+        self.bought_in[seat as usize] = buy_in;
+        self.joined_at_hand[seat as usize] = self.hands_played;
+    }
+
+    /// With deterministic seating, put the smaller pubkey in seat 0 once
+    /// both players are present and before the first hand, swapping every
+    /// per-seat field. The button starts on seat 0.
+    pub fn order_seats_by_pubkey(&mut self) {
+        if !self.deterministic_seating || self.hands_played > 0 {
+            return;
+        }
+        let (Some(one), Some(two)) = (self.player_one, self.player_two) else {
+            return;
+        };
+
+        if two < one {
+            self.swap_seats();
+        }
+        self.button = 0;
+    }
+
+    /// Exchange the two seats along with everything tracked per seat. The
+    /// button moves with its player, so the rotation is unchanged.
+    pub fn swap_seats(&mut self) {
+        std::mem::swap(&mut self.player_one, &mut self.player_two);
+        std::mem::swap(&mut self.player_one_chips, &mut self.player_two_chips);
+        self.time_bank_remaining.swap(0, 1);
+        self.bought_in.swap(0, 1);
+        self.joined_at_hand.swap(0, 1);
+        self.sitting_out.swap(0, 1);
+        self.ready.swap(0, 1);
+        self.button = 1 - self.button;
+    }
+
+    /// Refuse to let a seat leave with winnings before it has played the
+    /// table's minimum number of hands. Busted or losing players may
+    /// always leave.
+    pub fn require_can_leave(&self, seat: u8) -> Result<()> {
+        let Some(&bought_in) = self.bought_in.get(seat as usize) else {
+            return Ok(());
+        };
+        let up_money = self.get_chips(seat) > bought_in;
+        let hands_seated = self.hands_played.saturating_sub(self.joined_at_hand[seat as usize]);
+        require!(
+            !up_money || hands_seated >= self.min_hands_before_leave,
+            ZkPokerError::HitAndRunBlocked
+        );
+        Ok(())
+    }
+
+    /// Refuse a player who left this table less than `rejoin_cooldown`
+    /// seconds ago, so a loser can't instantly sit back down at max buy-in
+    pub fn require_rejoin_allowed(&self, player: &Pubkey, now: i64) -> Result<()> {
+        for (left_by, left_at) in self.left_by.iter().zip(self.left_at) {
+            require!(
+                left_by != player || now.saturating_sub(left_at) >= self.rejoin_cooldown,
+                ZkPokerError::RejoinCooldown
+            );
+        }
+        Ok(())
+    }
+
+    /// Remember who is leaving `seat` and when, for the rejoin cooldown
+    pub fn record_leave(&mut self, seat: u8, now: i64) {
+        let player = match seat {
+            0 => self.player_one,
+            1 => self.player_two,
+            _ => None,
+        };
+        if let Some(player) = player {
+            self.left_by[seat as usize] = player;
+            self.left_at[seat as usize] = now;
+        }
+    }
+
+    /// Vacate a seat and clear everything tied to it, leaving the table
+    /// ready for a new player. The button stays where the last completed
+    /// hand rotated it: a newcomer takes the vacated seat's place in the
+    /// rotation, and the remaining player posts the other blind from the
+    /// one they posted last hand, so nobody posts the same blind twice in
+    /// a row across a change of opponent.
+    pub fn reset_seat_for_rejoin(&mut self, seat: u8) {
+        match seat {
+            0 => {
+                self.player_one = None;
+                self.player_one_chips = 0;
+            }
+            1 => {
+                self.player_two = None;
+                self.player_two_chips = 0;
+            }
+            _ => return,
+        }
+        self.set_time_bank(seat, 0);
+        self.bought_in[seat as usize] = 0;
+        self.joined_at_hand[seat as usize] = 0;
+        self.sitting_out[seat as usize] = false;
+        self.ready = [false; 2];
+        self.current_hand = None;
+        self.status = TableStatus::Waiting;
+
+        if self.is_empty() {
+            self.button = 0;
+        }
+    }
+
     /// Rotate the dealer button
     pub fn rotate_button(&mut self) {
         self.button = self.other_seat(self.button);
     }
 
-    /// Get small blind seat (button in heads-up)
+    /// Get small blind seat (button in heads-up, unless the button posts
+    /// the big blind)
     pub fn small_blind_seat(&self) -> u8 {
-        self.button
+        if self.button_is_big_blind {
+            self.other_seat(self.button)
+        } else {
+            self.button
+        }
     }
 
-    /// Get big blind seat (opposite of button in heads-up)
+    /// Get big blind seat (opposite of the small blind)
     pub fn big_blind_seat(&self) -> u8 {
-        self.other_seat(self.button)
+        self.other_seat(self.small_blind_seat())
+    }
+
+    /// Seat that opens the action on `stage` under the table's position
+    /// rule (at showdown, who shows first when the river checked through).
+    /// The small blind always opens preflop; after the flop position
+    /// follows the button, whichever blind it posted.
+    pub fn first_to_act(&self, stage: HandStage) -> u8 {
+        match (self.first_to_act_rule, stage) {
+            (_, HandStage::Preflop) => self.small_blind_seat(),
+            (PositionRule::ButtonFirst, _) => self.button,
+            (PositionRule::Standard, _) => self.other_seat(self.button),
+        }
     }
 
-    /// Increment hands played counter
+    /// Sit out every seated player whose stack can no longer cover the big
+    /// blind, holding the table in `Waiting` until they rebuy. Returns which
+    /// seats were newly sat out.
+    pub fn sit_out_busted(&mut self) -> [bool; 2] {
+        let mut busted = [false; 2];
+        for seat in 0..2u8 {
+            let seated = match seat {
+                0 => self.player_one.is_some(),
+                _ => self.player_two.is_some(),
+            };
+            if seated && !self.sitting_out[seat as usize] && self.get_chips(seat) < self.big_blind {
+                self.sitting_out[seat as usize] = true;
+                busted[seat as usize] = true;
+            }
+        }
+        if self.sitting_out.contains(&true) {
+            self.status = TableStatus::Waiting;
+        }
+        busted
+    }
+
+    /// Bring a sat-out seat back once its stack covers the big blind again.
+    /// Heads-up dead-blind rule: the returning seat posts the big blind on
+    /// the next hand, so the button moves to match. Sitting out can
+    /// never skip a big blind, and the seat that kept playing gets the
+    /// cheaper blind, as a newcomer's opponent does.
+    pub fn sit_in_if_covered(&mut self, seat: u8) {
+        if self.sitting_out[seat as usize] && self.get_chips(seat) >= self.big_blind {
+            self.sitting_out[seat as usize] = false;
+            self.button = if self.button_is_big_blind { seat } else { self.other_seat(seat) };
+            if self.is_full() && !self.sitting_out.contains(&true) {
+                self.status = TableStatus::Between;
+            }
+        }
+    }
+
+    /// Increment hands played counter, starting to close the table once
+    /// the hand limit is reached
     pub fn increment_hands_played(&mut self) {
         self.hands_played = self.hands_played.saturating_add(1);
+        if self.max_hands > 0 && self.hands_played >= self.max_hands {
+            self.closing = true;
+        }
+    }
+
+    /// Whether `player` may join under the table's access rules
+    pub fn can_join(&self, player: &Pubkey) -> bool {
+        match self.access {
+            TableAccess::Public => true,
+            TableAccess::Allowlist => self.allowed.contains(&Some(*player)),
+        }
+    }
+
+    /// Add a player to the allowlist (no-op if already present)
+    pub fn add_allowed(&mut self, player: Pubkey) -> Result<()> {
+        if self.allowed.contains(&Some(player)) {
+            return Ok(());
+        }
+        let slot = self
+            .allowed
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(ZkPokerError::AllowlistFull)?;
+        *slot = Some(player);
+        Ok(())
+    }
+
+    /// Remove a player from the allowlist
+    pub fn remove_allowed(&mut self, player: &Pubkey) -> Result<()> {
+        let slot = self
+            .allowed
+            .iter_mut()
+            .find(|slot| slot.as_ref() == Some(player))
+            .ok_or(ZkPokerError::NotAllowlisted)?;
+        *slot = None;
+        Ok(())
+    }
+
+    /// Whether `amount` is an acceptable buy-in or top-up size
+    pub fn is_valid_chip_amount(&self, amount: u64) -> bool {
+        !self.require_bb_multiple || amount % self.big_blind == 0
+    }
+
+    /// Rake owed on `pot` at the end of `hand`: `rake_bps` of the pot up to
+    /// `rake_cap`, or nothing before the flop on no-flop-no-drop tables
+    pub fn rake_for(&self, hand: &Hand, pot: u64) -> u64 {
+        if self.rake_only_after_flop && !hand.flop_revealed() {
+            return 0;
+        }
+        let rake = (pot as u128 * self.rake_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        if self.rake_cap > 0 {
+            rake.min(self.rake_cap)
+        } else {
+            rake
+        }
+    }
+
+    /// No players seated
+    pub fn is_empty(&self) -> bool {
+        self.player_one.is_none() && self.player_two.is_none()
     }
 }
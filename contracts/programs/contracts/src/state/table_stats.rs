@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::Hand;
+
+/// Lifetime statistics for a table, updated as each hand completes
+/// Seeds: ["table_stats", table.key()]
+#[account]
+pub struct TableStats {
+    /// Table these statistics belong to
+    pub table: Pubkey,
+
+    /// Total hands completed at this table
+    pub total_hands: u64,
+
+    /// Total chips awarded across all completed hands
+    pub total_volume: u64,
+
+    /// Largest single pot awarded
+    pub biggest_pot: u64,
+
+    /// Total rake taken from pots
+    pub rake_collected: u64,
+
+    /// Hands won outright per seat (splits are not counted)
+    pub hands_won: [u64; 2],
+}
+
+impl TableStats {
+    /// Account size for rent calculation
+    /// 8 (discriminator) + 32 (table) + 8 + 8 + 8 + 8 + 16 (hands_won) = 88 bytes
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 16;
+
+    /// Record a completed hand. The account is created lazily, so the
+    /// table key is (re)written here on every completion.
+    pub fn record_hand(&mut self, hand: &Hand, pot: u64, rake: u64) {
+        self.table = hand.table;
+        self.total_hands = self.total_hands.saturating_add(1);
+        self.total_volume = self.total_volume.saturating_add(pot);
+        self.biggest_pot = self.biggest_pot.max(pot);
+        self.rake_collected = self.rake_collected.saturating_add(rake);
+
+        if let Some(seat) = hand.winner.seat() {
+            self.hands_won[seat as usize] = self.hands_won[seat as usize].saturating_add(1);
+        }
+    }
+}
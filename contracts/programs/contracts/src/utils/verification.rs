@@ -1,28 +1,195 @@
+//! ZK proof verification via the per-circuit verifier programs
+//!
+//! Proof buffers hold `proof || public_witness`, where the public witness is
+//! a 12 byte header followed by 32 byte big-endian field elements. The DECK,
+//! DEAL, REVEAL and SHOWDOWN circuits must declare `hand_id` as their first
+//! public input so it is always the first element after the header:
+//!
+//! ```text
+//! [0..388)      Groth16 proof
+//! [388..400)    witness header
+//! [400..432)    hand_id = circuit_version * 2^128 + table_id * 2^64 + hand_number
+//! [432..)       remaining public inputs/outputs of the circuit
+//! ```
+//!
+//! Each proof is checked against the verifier registered in `GlobalConfig`
+//! (replaceable with `update_verifiers`), not a compiled-in program ID.
+//!
+//! Binding every proof to a hand stops a proof from one hand being replayed
+//! in another (e.g. a rematch that happens to reuse the same seeds).
+
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::program::invoke;
 use crate::constants::{
-    DECK_VERIFIER_PROGRAM_ID,
-    REVEAL_VERIFIER_PROGRAM_ID,
-    SHOWDOWN_VERIFIER_PROGRAM_ID,
-    PROOF_SIZE
+    DECK_PUBLIC_INPUT_LEN,
+    DEAL_PUBLIC_INPUT_LEN,
+    REVEAL_PUBLIC_INPUT_LEN,
+    SHOWDOWN_PUBLIC_INPUT_LEN,
+    PROOF_SIZE,
+    PUBLIC_WITNESS_HEADER_SIZE,
+    FIELD_ELEMENT_SIZE,
 };
 use crate::errors::ZkPokerError;
 
+/// Public input value binding a proof to one hand and circuit version:
+/// `circuit_version * 2^128 + table_id * 2^64 + hand_number` as a
+/// big-endian field element. A proof made for another version's circuits
+/// fails the binding check.
+pub fn hand_binding(table_id: u64, hand_number: u64, circuit_version: u16) -> [u8; FIELD_ELEMENT_SIZE] {
+    let mut binding = [0u8; FIELD_ELEMENT_SIZE];
+    binding[14..16].copy_from_slice(&circuit_version.to_be_bytes());
+    binding[16..24].copy_from_slice(&table_id.to_be_bytes());
+    binding[24..32].copy_from_slice(&hand_number.to_be_bytes());
+    binding
+}
+
+/// Encode a small value (card index, deck position, seed byte) as a
+/// big-endian field element
+fn u64_to_field(value: u64) -> [u8; FIELD_ELEMENT_SIZE] {
+    let mut element = [0u8; FIELD_ELEMENT_SIZE];
+    element[FIELD_ELEMENT_SIZE - 8..].copy_from_slice(&value.to_be_bytes());
+    element
+}
+
+/// The public inputs a proof must carry, in the order its circuit declares
+/// them. Each constructor below is the program's side of the layout
+/// contract with one circuit; a circuit change has to be mirrored there.
+/// Every element records the error reported when a proof disagrees with it.
+pub struct PublicInputs {
+    elements: Vec<([u8; FIELD_ELEMENT_SIZE], ZkPokerError)>,
+}
+
+impl PublicInputs {
+    /// Start with `hand_id`, the first public input of every circuit
+    fn new(hand_binding: &[u8; FIELD_ELEMENT_SIZE]) -> Self {
+        Self { elements: vec![(*hand_binding, ZkPokerError::ProofHandMismatch)] }
+    }
+
+    fn field(mut self, element: &[u8; FIELD_ELEMENT_SIZE], error: ZkPokerError) -> Self {
+        self.elements.push((*element, error));
+        self
+    }
+
+    fn small(self, value: u64, error: ZkPokerError) -> Self {
+        self.field(&u64_to_field(value), error)
+    }
+
+    /// DECK: `hand_id || deck_root || positions[0..2] || commitments[0..2]`
+    pub fn deck(
+        hand_binding: &[u8; FIELD_ELEMENT_SIZE],
+        deck_root: &[u8; FIELD_ELEMENT_SIZE],
+        positions: [u8; 2],
+        commitments: &[[u8; FIELD_ELEMENT_SIZE]; 2],
+    ) -> Self {
+        let inputs = Self::new(hand_binding).field(deck_root, ZkPokerError::DeckSeedMismatch);
+        let inputs = positions
+            .iter()
+            .fold(inputs, |inputs, position| inputs.small(*position as u64, ZkPokerError::HolePositionMismatch));
+        commitments
+            .iter()
+            .fold(inputs, |inputs, commitment| inputs.field(commitment, ZkPokerError::InvalidCommitment))
+    }
+
+    /// DEAL: `hand_id || deck_seed[0..32]` (one byte per element), followed
+    /// by the `deck_root` output
+    pub fn deal(hand_binding: &[u8; FIELD_ELEMENT_SIZE], deck_seed: &[u8; 32]) -> Self {
+        deck_seed
+            .iter()
+            .fold(Self::new(hand_binding), |inputs, byte| inputs.small(*byte as u64, ZkPokerError::DeckSeedMismatch))
+    }
+
+    /// REVEAL: `hand_id`; the cards are checked against the hand's state
+    pub fn reveal(hand_binding: &[u8; FIELD_ELEMENT_SIZE]) -> Self {
+        Self::new(hand_binding)
+    }
+
+    /// SHOWDOWN: `hand_id || commitments[0..2] || community_cards[0..5]`,
+    /// followed by the hand rank output
+    pub fn showdown(
+        hand_binding: &[u8; FIELD_ELEMENT_SIZE],
+        commitments: &[[u8; FIELD_ELEMENT_SIZE]; 2],
+        community_cards: &[u8; 5],
+    ) -> Self {
+        let inputs = commitments
+            .iter()
+            .fold(Self::new(hand_binding), |inputs, commitment| inputs.field(commitment, ZkPokerError::ProofCardsMismatch));
+        community_cards
+            .iter()
+            .fold(inputs, |inputs, card| inputs.small(*card as u64, ZkPokerError::ProofCardsMismatch))
+    }
+
+    /// Canonical bytes of these inputs as they follow the witness header
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.elements.iter().flat_map(|(element, _)| *element).collect()
+    }
+
+    /// Check a proof's witness starts with these inputs, reporting the first
+    /// element that differs. Returns the circuit's output elements that follow.
+    pub fn check<'a>(&self, proof_and_witness: &'a [u8]) -> Result<&'a [u8]> {
+        let start = PROOF_SIZE + PUBLIC_WITNESS_HEADER_SIZE;
+        let end = start + self.elements.len() * FIELD_ELEMENT_SIZE;
+        let witness = proof_and_witness.get(start..end).ok_or(ZkPokerError::InvalidProofFormat)?;
+
+        for ((expected, error), proven) in self.elements.iter().zip(witness.chunks_exact(FIELD_ELEMENT_SIZE)) {
+            if proven != expected {
+                return Err((*error).into());
+            }
+        }
+
+        Ok(&proof_and_witness[end..])
+    }
+}
+
+/// First output element following a circuit's public inputs
+fn first_output(outputs: &[u8]) -> Result<[u8; FIELD_ELEMENT_SIZE]> {
+    outputs
+        .get(..FIELD_ELEMENT_SIZE)
+        .and_then(|element| element.try_into().ok())
+        .ok_or(ZkPokerError::InvalidProofFormat.into())
+}
+
+/// Read a field element holding a small value (card index or hand rank)
+fn field_to_u64(element: &[u8]) -> Result<u64> {
+    let (high, low) = element.split_at(FIELD_ELEMENT_SIZE - 8);
+    require!(high.iter().all(|b| *b == 0), ZkPokerError::InvalidProofFormat);
+    let low: [u8; 8] = low.try_into().map_err(|_| ZkPokerError::InvalidProofFormat)?;
+    Ok(u64::from_be_bytes(low))
+}
+
+/// Check a proof carries exactly the circuit's public witness, so a client
+/// building the wrong inputs fails here rather than opaquely in the verifier
+fn check_witness_len(proof_and_witness: &[u8], expected_public_input_len: usize) -> Result<()> {
+    let expected = PROOF_SIZE + PUBLIC_WITNESS_HEADER_SIZE + expected_public_input_len * FIELD_ELEMENT_SIZE;
+    if proof_and_witness.len() != expected {
+        msg!(
+            "Proof + witness is {} bytes, expected {} ({} public inputs)",
+            proof_and_witness.len(),
+            expected,
+            expected_public_input_len
+        );
+        return err!(ZkPokerError::InvalidProofFormat);
+    }
+    Ok(())
+}
+
 /// Verify a Groth16 proof using the deployed verifier program
 ///
 /// # Arguments
 /// * `verifier_program` - The verifier program account
 /// * `expected_verifier_id` - The expected verifier program ID for this circuit
 /// * `proof_and_witness` - The proof + public witness bytes (proof is 388 bytes + variable witness size)
+/// * `expected_public_input_len` - Public witness elements the circuit declares
 ///
 /// # Returns
 /// * `Ok(())` if proof is valid
+/// * `Err(ZkPokerError::InvalidProofFormat)` if the witness has the wrong number of elements
 /// * `Err(ZkPokerError::ProofVerificationFailed)` if proof is invalid
 pub fn verify_groth16_proof(
     verifier_program: &AccountInfo,
     expected_verifier_id: &Pubkey,
     proof_and_witness: &[u8],
+    expected_public_input_len: usize,
 ) -> Result<()> {
     // Verify verifier program ID matches expected circuit verifier
     require!(
@@ -30,11 +197,7 @@ pub fn verify_groth16_proof(
         ZkPokerError::ProofVerificationFailed
     );
 
-    // Verify minimum size (proof must be at least PROOF_SIZE bytes)
-    require!(
-        proof_and_witness.len() >= PROOF_SIZE,
-        ZkPokerError::InvalidProofFormat
-    );
+    check_witness_len(proof_and_witness, expected_public_input_len)?;
 
     msg!("Verifying ZK proof via CPI");
     msg!("Verifier program: {}", verifier_program.key());
@@ -49,7 +212,7 @@ pub fn verify_groth16_proof(
     };
 
     // Execute CPI call to verifier program
-    invoke(&verify_ix, &[verifier_program.clone()])
+    invoke(&verify_ix, std::slice::from_ref(verifier_program))
         .map_err(|_| ZkPokerError::ProofVerificationFailed)?;
 
     msg!("✓ ZK Proof verified successfully");
@@ -59,17 +222,85 @@ pub fn verify_groth16_proof(
 
 /// Verify hole card commitments (uses DECK circuit)
 ///
-/// Verifies that the commitments are valid for cards at the specified positions
-/// in the shuffled deck derived from deck_seed.
+/// Verifies that the commitments are to the cards at `positions` of the
+/// shuffled deck proven by the DEAL circuit. After `hand_id` the public
+/// witness holds:
+///
+/// ```text
+/// [432..464)    deck_root
+/// [464..528)    positions[0..2]
+/// [528..592)    commitments[0..2] (circuit output)
+/// ```
 ///
 /// # Arguments
 /// * `verifier_program` - The verifier program account (must be DECK verifier)
+/// * `expected_verifier_id` - The registered DECK verifier from `GlobalConfig`
 /// * `proof_and_witness` - The proof + public witness from Sunspot
+/// * `hand_binding` - Expected `hand_id` public input (see [`hand_binding`])
+/// * `deck_root` - The hand's deck root from `verify_deal`
+/// * `positions` - Deck positions dealt to the committing seat
+/// * `commitments` - The commitments the player is storing
 pub fn verify_hole_card_commitments(
     verifier_program: &AccountInfo,
+    expected_verifier_id: &Pubkey,
     proof_and_witness: &[u8],
+    hand_binding: &[u8; FIELD_ELEMENT_SIZE],
+    deck_root: &[u8; FIELD_ELEMENT_SIZE],
+    positions: [u8; 2],
+    commitments: &[[u8; FIELD_ELEMENT_SIZE]; 2],
 ) -> Result<()> {
-    verify_groth16_proof(verifier_program, &DECK_VERIFIER_PROGRAM_ID, proof_and_witness)
+    PublicInputs::deck(hand_binding, deck_root, positions, commitments).check(proof_and_witness)?;
+
+    verify_groth16_proof(verifier_program, expected_verifier_id, proof_and_witness, DECK_PUBLIC_INPUT_LEN)
+}
+
+/// Verify the deck shuffle (uses DEAL circuit)
+///
+/// Verifies that the shuffled deck is a permutation of all 52 cards derived
+/// from deck_seed. After `hand_id` the public witness holds the seed, one
+/// byte per field element, then the circuit's output:
+///
+/// ```text
+/// [432..1456)   deck_seed[0..32]
+/// [1456..1488)  deck_root = Poseidon2(shuffled deck)
+/// ```
+///
+/// # Arguments
+/// * `verifier_program` - The verifier program account (must be DEAL verifier)
+/// * `expected_verifier_id` - The registered DEAL verifier from `GlobalConfig`
+/// * `proof_and_witness` - The proof + public witness from Sunspot
+/// * `hand_binding` - Expected `hand_id` public input (see [`hand_binding`])
+/// * `deck_seed` - The hand's deck seed the proof must be derived from
+///
+/// # Returns
+/// * The deck root committing to the shuffled deck
+pub fn verify_deal(
+    verifier_program: &AccountInfo,
+    expected_verifier_id: &Pubkey,
+    proof_and_witness: &[u8],
+    hand_binding: &[u8; FIELD_ELEMENT_SIZE],
+    deck_seed: &[u8; 32],
+) -> Result<[u8; FIELD_ELEMENT_SIZE]> {
+    let outputs = PublicInputs::deal(hand_binding, deck_seed).check(proof_and_witness)?;
+    let deck_root = first_output(outputs)?;
+
+    verify_groth16_proof(verifier_program, expected_verifier_id, proof_and_witness, DEAL_PUBLIC_INPUT_LEN)?;
+
+    Ok(deck_root)
+}
+
+/// Verify a player can cover the chips an action puts in
+///
+/// Stacks are public on the table, so the balance is checked directly against
+/// the on-chain stack; no BET circuit proof is needed and the program keeps
+/// no bet verifier.
+///
+/// # Arguments
+/// * `stack` - The acting player's chips behind
+/// * `amount` - Chips the action moves into the pot
+pub fn verify_bet_balance(stack: u64, amount: u64) -> Result<()> {
+    require!(amount > 0 && amount <= stack, ZkPokerError::InsufficientChips);
+    Ok(())
 }
 
 /// Verify community card reveal (uses REVEAL circuit)
@@ -79,26 +310,158 @@ pub fn verify_hole_card_commitments(
 ///
 /// # Arguments
 /// * `verifier_program` - The verifier program account (must be REVEAL verifier)
+/// * `expected_verifier_id` - The registered REVEAL verifier from `GlobalConfig`
 /// * `proof_and_witness` - The proof + public witness from Sunspot
+/// * `hand_binding` - Expected `hand_id` public input (see [`hand_binding`])
 pub fn verify_community_cards(
     verifier_program: &AccountInfo,
+    expected_verifier_id: &Pubkey,
     proof_and_witness: &[u8],
+    hand_binding: &[u8; FIELD_ELEMENT_SIZE],
 ) -> Result<()> {
-    verify_groth16_proof(verifier_program, &REVEAL_VERIFIER_PROGRAM_ID, proof_and_witness)
+    PublicInputs::reveal(hand_binding).check(proof_and_witness)?;
+    verify_groth16_proof(verifier_program, expected_verifier_id, proof_and_witness, REVEAL_PUBLIC_INPUT_LEN)
 }
 
-/// Verify hand reveal at showdown (uses SHOWDOWN circuit)
+/// Verify a showdown proof against a hand and return the rank it proves
+/// (uses SHOWDOWN circuit)
+///
+/// The proof's public inputs must match the seat's hole card commitments
+/// and the board, so the rank is bound to the cards actually dealt. After
+/// `hand_id` the witness holds:
 ///
-/// Verifies that:
-/// 1. The revealed cards match the commitments
-/// 2. The hand rank is correctly computed
+/// ```text
+/// [432..496)    commitment1, commitment2
+/// [496..656)    community_cards[0..5]
+/// [656..688)    hand rank
+/// ```
 ///
 /// # Arguments
 /// * `verifier_program` - The verifier program account (must be SHOWDOWN verifier)
+/// * `expected_verifier_id` - The registered SHOWDOWN verifier from `GlobalConfig`
 /// * `proof_and_witness` - The proof + public witness from Sunspot
-pub fn verify_hand_reveal(
+/// * `hand_binding` - Expected `hand_id` public input (see [`hand_binding`])
+/// * `commitments` - The seat's hole card commitments
+/// * `community_cards` - The revealed board
+pub fn verify_showdown_rank(
     verifier_program: &AccountInfo,
+    expected_verifier_id: &Pubkey,
     proof_and_witness: &[u8],
-) -> Result<()> {
-    verify_groth16_proof(verifier_program, &SHOWDOWN_VERIFIER_PROGRAM_ID, proof_and_witness)
+    hand_binding: &[u8; FIELD_ELEMENT_SIZE],
+    commitments: &[[u8; 32]; 2],
+    community_cards: &[u8; 5],
+) -> Result<u64> {
+    let outputs = PublicInputs::showdown(hand_binding, commitments, community_cards).check(proof_and_witness)?;
+    let rank = field_to_u64(&first_output(outputs)?)?;
+
+    verify_groth16_proof(verifier_program, expected_verifier_id, proof_and_witness, SHOWDOWN_PUBLIC_INPUT_LEN)?;
+
+    Ok(rank)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Golden element: big-endian field element with `tail` in its last bytes
+    fn element(tail: &[u8]) -> Vec<u8> {
+        let mut element = vec![0u8; FIELD_ELEMENT_SIZE - tail.len()];
+        element.extend_from_slice(tail);
+        element
+    }
+
+    fn golden(elements: &[Vec<u8>]) -> Vec<u8> {
+        elements.concat()
+    }
+
+    // table 7, hand 3
+    fn binding() -> [u8; FIELD_ELEMENT_SIZE] {
+        hand_binding(7, 3, 0)
+    }
+
+    fn hand_id() -> Vec<u8> {
+        element(&[0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0, 3])
+    }
+
+    #[test]
+    fn deck_layout() {
+        let inputs = PublicInputs::deck(&binding(), &[0xaa; 32], [4, 9], &[[0xc1; 32], [0xc2; 32]]);
+
+        assert_eq!(
+            inputs.to_bytes(),
+            golden(&[hand_id(), vec![0xaa; 32], element(&[4]), element(&[9]), vec![0xc1; 32], vec![0xc2; 32]])
+        );
+    }
+
+    #[test]
+    fn deal_layout() {
+        let seed: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let mut expected = vec![hand_id()];
+        expected.extend(seed.iter().map(|byte| element(&[*byte])));
+
+        assert_eq!(PublicInputs::deal(&binding(), &seed).to_bytes(), golden(&expected));
+    }
+
+    #[test]
+    fn reveal_layout() {
+        assert_eq!(PublicInputs::reveal(&binding()).to_bytes(), hand_id());
+    }
+
+    #[test]
+    fn showdown_layout() {
+        let inputs = PublicInputs::showdown(&binding(), &[[0xc1; 32], [0xc2; 32]], &[0, 12, 25, 38, 51]);
+
+        assert_eq!(
+            inputs.to_bytes(),
+            golden(&[
+                hand_id(),
+                vec![0xc1; 32],
+                vec![0xc2; 32],
+                element(&[0]),
+                element(&[12]),
+                element(&[25]),
+                element(&[38]),
+                element(&[51]),
+            ])
+        );
+    }
+
+    #[test]
+    fn check_reports_first_mismatch_and_returns_outputs() {
+        let inputs = PublicInputs::showdown(&binding(), &[[0xc1; 32], [0xc2; 32]], &[0, 12, 25, 38, 51]);
+        let mut witness = vec![0u8; PROOF_SIZE + PUBLIC_WITNESS_HEADER_SIZE];
+        witness.extend(inputs.to_bytes());
+        witness.extend(element(&[5]));
+
+        let outputs = inputs.check(&witness).unwrap();
+        assert_eq!(field_to_u64(&first_output(outputs).unwrap()).unwrap(), 5);
+
+        let card = PROOF_SIZE + PUBLIC_WITNESS_HEADER_SIZE + 4 * FIELD_ELEMENT_SIZE - 1;
+        witness[card] = 13;
+        assert_eq!(inputs.check(&witness).unwrap_err(), ZkPokerError::ProofCardsMismatch.into());
+    }
+
+    #[test]
+    fn witness_len_matches_each_circuit() {
+        for count in [DECK_PUBLIC_INPUT_LEN, DEAL_PUBLIC_INPUT_LEN, REVEAL_PUBLIC_INPUT_LEN, SHOWDOWN_PUBLIC_INPUT_LEN] {
+            let exact = PROOF_SIZE + PUBLIC_WITNESS_HEADER_SIZE + count * FIELD_ELEMENT_SIZE;
+
+            assert!(check_witness_len(&vec![0u8; exact], count).is_ok());
+            for len in [exact - FIELD_ELEMENT_SIZE, exact - 1, exact + FIELD_ELEMENT_SIZE, PROOF_SIZE] {
+                assert_eq!(
+                    check_witness_len(&vec![0u8; len], count).unwrap_err(),
+                    ZkPokerError::InvalidProofFormat.into()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn binding_carries_circuit_version() {
+        let upgraded = hand_binding(7, 3, 2);
+
+        assert_eq!(upgraded[14..16], [0, 2]);
+        assert_eq!(upgraded[16..], binding()[16..]);
+        assert_ne!(upgraded, binding());
+    }
 }
@@ -0,0 +1,117 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  seatProfiles,
+} from "./utils/game";
+
+const BUY_IN = 500_000000;
+const BIG_BLIND = 20_000000;
+
+describe("Admin Abort", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+  const authority = provider.wallet;
+  const globalConfig = findGlobalConfig(program);
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+
+  const setPaused = (paused: boolean) =>
+    program.methods[paused ? "pause" : "unpause"]()
+      .accounts({ authority: authority.publicKey, globalConfig })
+      .rpc();
+
+  const abortHand = () =>
+    program.methods
+      .adminAbortHand()
+      .accountsPartial({
+        authority: authority.publicKey,
+        globalConfig,
+        table: testTable.table,
+        hand,
+      })
+      .rpc();
+
+  before("Raise preflop so the seats have put in different amounts", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(globalConfig);
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    ({ hand } = await playToPreflop(program, testTable, players));
+
+    const raiser = await playerToAct(program, testTable, hand, players);
+    await program.methods
+      .raiseTo(new anchor.BN(3 * BIG_BLIND))
+      .accountsPartial({
+        player: raiser.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([raiser.keypair])
+      .rpc();
+  });
+
+  after("Leave the protocol unpaused", async () => {
+    if ((await program.account.globalConfig.fetch(globalConfig)).isPaused) {
+      await setPaused(false);
+    }
+  });
+
+  it("Rejects an abort while the protocol is running", async () => {
+    console.log("🧪 Testing: admin_abort_hand without a pause");
+
+    await expectError(abortHand(), "NotPaused");
+
+    console.log("   ✅ Correctly rejected with NotPaused");
+  });
+
+  it("Refunds each seat's contribution and voids the hand", async () => {
+    console.log("🧪 Testing: admin_abort_hand while paused");
+
+    const before = await program.account.hand.fetch(hand);
+    assert.deepEqual(before.totalBet.map((b) => b.toNumber()).sort(), [BIG_BLIND, 3 * BIG_BLIND]);
+
+    await setPaused(true);
+    await abortHand();
+    await setPaused(false);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerOneChips.toNumber(), BUY_IN);
+    assert.equal(tableAccount.playerTwoChips.toNumber(), BUY_IN);
+    assert.deepEqual(tableAccount.status, { between: {} });
+    assert.isNull(tableAccount.currentHand);
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { complete: {} });
+    assert.deepEqual(handAccount.completionReason, { aborted: {} });
+    assert.deepEqual(handAccount.winner, { none: {} });
+    assert.equal(handAccount.pot.toNumber(), 0);
+    assert.deepEqual(handAccount.totalBet.map((b) => b.toNumber()), [0, 0]);
+
+    console.log("   ✅ Both stacks back to the buy-in, table between hands");
+  });
+});
@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+
+const BUY_IN = 500_000000;
+
+describe("Settle by Agreement", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const agree = (testTable: TestTable, hand: PublicKey, player: TestPlayer, result: object) =>
+    program.methods
+      .agreeResult(result as any)
+      .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+      .signers([player.keypair])
+      .rpc();
+
+  const claimPot = async (testTable: TestTable, hand: PublicKey, player: TestPlayer) =>
+    program.methods
+      .claimPot()
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** New table where both players are all-in preflop */
+  async function allInHand(): Promise<{ testTable: TestTable; hand: PublicKey }> {
+    const testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player, new anchor.BN(BUY_IN));
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "allIn");
+
+    return { testTable, hand };
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Splits the pot once both players agree", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: agree_result split");
+
+    const { testTable, hand } = await allInHand();
+
+    for (const player of players) {
+      await agree(testTable, hand, player, { split: {} });
+    }
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P1_RESULT_AGREED));
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P2_RESULT_AGREED));
+    assert.deepEqual(handAccount.winner, { split: {} });
+
+    await claimPot(testTable, hand, players[0]);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerOneChips.toNumber(), BUY_IN);
+    assert.equal(tableAccount.playerTwoChips.toNumber(), BUY_IN);
+
+    console.log("   ✅ Pot split without showdown proofs");
+  });
+
+  it("Rejects a claim on a result only one player signed", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: unilateral agree_result");
+
+    const { testTable, hand } = await allInHand();
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const [first, second] = tableAccount.playerOne.equals(players[0].keypair.publicKey)
+      ? [players[0], players[1]]
+      : [players[1], players[0]];
+
+    await agree(testTable, hand, first, { seat0: {} });
+    await expectError(claimPot(testTable, hand, first), "ResultNotAgreed");
+    await expectError(agree(testTable, hand, second, { seat1: {} }), "ResultMismatch");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.winner, { none: {} });
+    assert.isFalse(hasFlag(handAccount, HAND_FLAGS.POT_CLAIMED));
+
+    console.log("   ✅ Pot stays locked until both players sign the same result");
+  });
+});
@@ -0,0 +1,88 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+describe("Private Tables", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let creator: TestPlayer;
+  let friend: TestPlayer;
+  let stranger: TestPlayer;
+  let testTable: TestTable;
+
+  const manage = (method: "addAllowed" | "removeAllowed", signer: TestPlayer, player: PublicKey) =>
+    program.methods[method](player)
+      .accounts({ creator: signer.keypair.publicKey, table: testTable.table })
+      .signers([signer.keypair])
+      .rpc();
+
+  before("Create an allowlist table", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    creator = await createPlayer(provider, config.usdcMint);
+    friend = await createPlayer(provider, config.usdcMint);
+    stranger = await createPlayer(provider, config.usdcMint);
+
+    testTable = await createTable(program, provider, {
+      creator: creator.keypair,
+      access: { allowlist: {} },
+    });
+  });
+
+  it("Only the creator manages the allowlist", async () => {
+    console.log("🧪 Testing: add_allowed by a non-creator");
+
+    await expectError(manage("addAllowed", stranger, stranger.keypair.publicKey), "Unauthorized");
+    await manage("addAllowed", creator, friend.keypair.publicKey);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.deepEqual(tableAccount.access, { allowlist: {} });
+    assert.isTrue(tableAccount.allowed.some((p) => p?.equals(friend.keypair.publicKey)));
+
+    console.log("   ✅ Creator added a player, stranger rejected");
+  });
+
+  it("Rejects a player not on the allowlist", async () => {
+    console.log("🧪 Testing: stranger joins a private table");
+
+    await expectError(joinTable(program, testTable, stranger), "NotAllowlisted");
+
+    console.log("   ✅ Correctly rejected with NotAllowlisted");
+  });
+
+  it("Lets an allowlisted player join", async () => {
+    console.log("🧪 Testing: allowlisted player joins");
+
+    await joinTable(program, testTable, friend);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.isTrue(tableAccount.playerOne.equals(friend.keypair.publicKey));
+
+    console.log("   ✅ Allowlisted player seated");
+  });
+
+  it("Removes a player from the allowlist", async () => {
+    console.log("🧪 Testing: remove_allowed");
+
+    await manage("removeAllowed", creator, friend.keypair.publicKey);
+    await expectError(manage("removeAllowed", creator, friend.keypair.publicKey), "NotAllowlisted");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.isFalse(tableAccount.allowed.some((p) => p?.equals(friend.keypair.publicKey)));
+
+    console.log("   ✅ Player removed from the allowlist");
+  });
+});
@@ -0,0 +1,137 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  HoleCardSecrets,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  sleep,
+  seatProfiles,
+  SHOWDOWN_VERIFIER,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+import { shuffleDeck, getCommunityCards } from "./utils/deck";
+import { generateShowdownProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+const ACTION_TIMEOUT = 10;
+
+describe("Auto Muck", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+  let secrets: HoleCardSecrets[];
+
+  const claimPot = async (player: TestPlayer) =>
+    program.methods
+      .claimPot()
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  before("Check a hand down to showdown on an auto-muck table", async function () {
+    this.timeout(300000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider, {
+      autoMuck: true,
+      actionTimeout: new anchor.BN(ACTION_TIMEOUT),
+    });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    ({ hand, secrets } = await playToPreflop(program, testTable, players));
+
+    // Limp preflop, then check every street
+    const limper = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, limper, "call");
+    await act(program, testTable, hand, players.find((p) => p !== limper), "check");
+    for (let street = 0; street < 3; street++) {
+      for (let i = 0; i < 2; i++) {
+        await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "check");
+      }
+    }
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { showdown: {} });
+  });
+
+  it("Pays the first revealer once the other seat stays silent", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: claim_pot with one hand shown on an auto-muck table");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    const shower = await playerToAct(program, testTable, hand, players);
+    const showerIndex = players.indexOf(shower);
+    const showerSeat = tableAccount.playerOne.equals(shower.keypair.publicKey) ? 0 : 1;
+
+    const { flop, turn, river } = getCommunityCards(shuffleDeck(Buffer.from(handAccount.deckSeed)));
+    const { proof, handRank } = await generateShowdownProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+      commitment1: secrets[showerIndex].commitments[0],
+      commitment2: secrets[showerIndex].commitments[1],
+      communityCards: [...flop, turn, river],
+      holeCard1: secrets[showerIndex].cards[0],
+      holeCard2: secrets[showerIndex].cards[1],
+      salt1: secrets[showerIndex].salts[0],
+      salt2: secrets[showerIndex].salts[1],
+    });
+    const proofBuffer = await uploadProofToBuffer(program, proof, hand, shower.keypair, ProofType.Showdown);
+    await program.methods
+      .revealHand(new anchor.BN(handRank.toString()))
+      .accountsPartial({
+        player: shower.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer,
+        verifierProgram: SHOWDOWN_VERIFIER,
+      })
+      .signers([shower.keypair])
+      .rpc();
+
+    // The other seat still has the action timeout to show
+    await expectError(claimPot(shower), "PlayersNotRevealed");
+
+    await sleep((ACTION_TIMEOUT + 2) * 1000);
+    const pot = (await program.account.hand.fetch(hand)).pot.toNumber();
+    const chipsBefore = showerSeat === 0 ? tableAccount.playerOneChips : tableAccount.playerTwoChips;
+    await claimPot(shower);
+
+    const after = await program.account.table.fetch(testTable.table);
+    const settled = await program.account.hand.fetch(hand);
+    const chipsAfter = showerSeat === 0 ? after.playerOneChips : after.playerTwoChips;
+    assert.equal(chipsAfter.toNumber() - chipsBefore.toNumber(), pot);
+    assert.isTrue(hasFlag(settled, showerSeat === 0 ? HAND_FLAGS.P2_MUCKED : HAND_FLAGS.P1_MUCKED));
+    assert.isFalse(hasFlag(settled, showerSeat === 0 ? HAND_FLAGS.P2_REVEALED : HAND_FLAGS.P1_REVEALED));
+    assert.deepEqual(settled.winner, showerSeat === 0 ? { seat0: {} } : { seat1: {} });
+
+    console.log("   ✅ Silent seat auto-mucked, one proof settled the pot");
+  });
+});
@@ -0,0 +1,260 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  revealStreet,
+} from "./utils/game";
+
+describe("Betting", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+
+  before("Create a table and reach preflop", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    ({ hand } = await playToPreflop(program, testTable, players));
+  });
+
+  it("Rejects a check while facing a bet with CannotCheck", async () => {
+    console.log("🧪 Testing: check facing the big blind");
+
+    // Small blind acts first preflop and still owes the rest of the big blind
+    const actor = await playerToAct(program, testTable, hand, players);
+    await expectError(act(program, testTable, hand, actor, "check"), "CannotCheck");
+
+    console.log("   ✅ Check rejected with CannotCheck");
+  });
+
+  it("Rejects a call with nothing to call with NothingToCall", async () => {
+    console.log("🧪 Testing: call with nothing owed");
+
+    // Small blind completes, leaving the big blind with nothing to call
+    const smallBlind = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, smallBlind, "call");
+
+    const bigBlind = await playerToAct(program, testTable, hand, players);
+    await expectError(act(program, testTable, hand, bigBlind, "call"), "NothingToCall");
+
+    console.log("   ✅ Call rejected with NothingToCall");
+  });
+
+  it("Rejects a raise too large for any stack instead of saturating the pot", async () => {
+    console.log("🧪 Testing: raise to u64::MAX");
+
+    const actor = await playerToAct(program, testTable, hand, players);
+    const before = await program.account.hand.fetch(hand);
+
+    await expectError(
+      program.methods
+        .raiseTo(new anchor.BN("18446744073709551615"))
+        .accountsPartial({
+          player: actor.keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([actor.keypair])
+        .rpc(),
+      "InsufficientChips"
+    );
+
+    const after = await program.account.hand.fetch(hand);
+    assert.equal(after.pot.toString(), before.pot.toString());
+    assert.equal(after.pot.toString(), after.totalBet[0].add(after.totalBet[1]).toString());
+
+    console.log("   ✅ Oversized raise rejected, pot still equals both players' bets");
+  });
+
+  it("Keeps both stacks plus the pot equal to the chips in play", async () => {
+    console.log("🧪 Testing: chip conservation mid-hand");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    const total = tableAccount.playerOneChips.add(tableAccount.playerTwoChips).add(handAccount.pot);
+
+    assert.equal(total.toString(), handAccount.chipsInPlay.toString());
+    assert.equal(handAccount.chipsInPlay.toNumber(), 2 * 500_000000);
+
+    console.log("   ✅ No chips created or destroyed");
+  });
+
+  it("Checks raises against the acting player's public stack", async () => {
+    console.log("🧪 Testing: raise just over and exactly at the stack");
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    assert.notProperty(config, "betVerifier");
+
+    const actor = await playerToAct(program, testTable, hand, players);
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    const seat = tableAccount.playerOne.equals(actor.keypair.publicKey) ? 0 : 1;
+    const stack = seat === 0 ? tableAccount.playerOneChips : tableAccount.playerTwoChips;
+    const betThisStreet = handAccount.betThisStreet[seat];
+
+    const raiseTo = async (amount: anchor.BN) =>
+      program.methods
+        .raiseTo(amount)
+        .accountsPartial({
+          player: actor.keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([actor.keypair])
+        .rpc();
+
+    await expectError(raiseTo(betThisStreet.add(stack).addn(1)), "InsufficientChips");
+    await raiseTo(betThisStreet.add(stack));
+
+    const after = await program.account.table.fetch(testTable.table);
+    assert.equal((seat === 0 ? after.playerOneChips : after.playerTwoChips).toNumber(), 0);
+
+    console.log("   ✅ One chip over the stack rejected, the whole stack accepted");
+  });
+
+  it("Rejects a raise against an all-in with CannotRaise", async () => {
+    console.log("🧪 Testing: raise facing a shove");
+
+    // The previous test shoved, so the other seat may only call or fold
+    const actor = await playerToAct(program, testTable, hand, players);
+    const handAccount = await program.account.hand.fetch(hand);
+    await expectError(
+      program.methods
+        .raiseTo(handAccount.currentBet.muln(2))
+        .accountsPartial({
+          player: actor.keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([actor.keypair])
+        .rpc(),
+      "CannotRaise"
+    );
+
+    console.log("   ✅ Raise rejected with CannotRaise");
+  });
+
+  it("Rejects any action once both players are all-in with BettingClosed", async () => {
+    console.log("🧪 Testing: check after both stacks are in");
+
+    // The previous test shoved; calling with an equal stack puts both all-in
+    await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "call");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.notDeepEqual(handAccount.stage, { complete: {} });
+    for (const player of players) {
+      await expectError(act(program, testTable, hand, player, "check"), "BettingClosed");
+    }
+
+    console.log("   ✅ Both seats rejected with BettingClosed");
+  });
+
+  it("Requires a post-flop raise to add at least the opening bet", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: 3bb flop bet, raise to just under and exactly 6bb");
+
+    const bigBlind = 20_000000;
+    const freshTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, freshTable, player);
+    }
+    const { hand: freshHand } = await playToPreflop(program, freshTable, players);
+    const smallBlind = await playerToAct(program, freshTable, freshHand, players);
+    await act(program, freshTable, freshHand, smallBlind, "call");
+    await act(program, freshTable, freshHand, players.find((p) => p !== smallBlind), "check");
+
+    const wager = async (method: "bet" | "raiseTo", player: TestPlayer, amount: number) =>
+      program.methods[method](new anchor.BN(amount))
+        .accountsPartial({
+          player: player.keypair.publicKey,
+          table: freshTable.table,
+          hand: freshHand,
+          ...(await seatProfiles(program, freshTable)),
+        })
+        .signers([player.keypair])
+        .rpc();
+
+    const bettor = await playerToAct(program, freshTable, freshHand, players);
+    const raiser = players.find((p) => p !== bettor);
+    await revealStreet(program, freshTable, freshHand, bettor);
+    await wager("bet", bettor, 3 * bigBlind);
+    assert.equal((await program.account.hand.fetch(freshHand)).lastRaiseSize.toNumber(), 3 * bigBlind);
+
+    await expectError(wager("raiseTo", raiser, 6 * bigBlind - 1), "RaiseTooSmall");
+    await wager("raiseTo", raiser, 6 * bigBlind);
+
+    const handAccount = await program.account.hand.fetch(freshHand);
+    assert.equal(handAccount.currentBet.toNumber(), 6 * bigBlind);
+    assert.equal(handAccount.lastRaiseSize.toNumber(), 3 * bigBlind);
+
+    console.log("   ✅ Raise below 6bb rejected with RaiseTooSmall, 6bb accepted");
+  });
+
+  it("Lets a player who checked raise the bet behind them", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: check-raise on the flop");
+
+    const bigBlind = 20_000000;
+    const freshTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, freshTable, player);
+    }
+    const { hand: freshHand } = await playToPreflop(program, freshTable, players);
+    const smallBlind = await playerToAct(program, freshTable, freshHand, players);
+    await act(program, freshTable, freshHand, smallBlind, "call");
+    await act(program, freshTable, freshHand, players.find((p) => p !== smallBlind), "check");
+
+    const wager = async (method: "bet" | "raiseTo", player: TestPlayer, amount: number) =>
+      program.methods[method](new anchor.BN(amount))
+        .accountsPartial({
+          player: player.keypair.publicKey,
+          table: freshTable.table,
+          hand: freshHand,
+          ...(await seatProfiles(program, freshTable)),
+        })
+        .signers([player.keypair])
+        .rpc();
+
+    const checker = await playerToAct(program, freshTable, freshHand, players);
+    const bettor = players.find((p) => p !== checker);
+    await act(program, freshTable, freshHand, checker, "check");
+    await wager("bet", bettor, 2 * bigBlind);
+    await wager("raiseTo", checker, 6 * bigBlind);
+
+    const handAccount = await program.account.hand.fetch(freshHand);
+    assert.deepEqual(handAccount.stage, { flop: {} });
+    assert.equal(handAccount.currentBet.toNumber(), 6 * bigBlind);
+
+    console.log("   ✅ Check, bet, raise accepted");
+  });
+});
@@ -0,0 +1,155 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  runSeedProtocol,
+  verifyDeal,
+  findGlobalConfig,
+  seatProfiles,
+  expectError,
+  sleep,
+  DECK_VERIFIER,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+import { shuffleDeck, getHoleCards } from "./utils/deck";
+import { generateSalt } from "./utils/crypto";
+import { generateDeckProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+import { commitmentToBytes } from "./utils/commitments";
+
+describe("Proof Hand Binding", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  const commit = async (hand: PublicKey, player: TestPlayer, proof: Buffer, commitments: [bigint, bigint]) => {
+    const proofBuffer = await uploadProofToBuffer(program, proof, hand, player.keypair, ProofType.Deck);
+    await program.methods
+      .commitHoleCards([commitmentToBytes(commitments[0]), commitmentToBytes(commitments[1])])
+      .accounts({
+        player: player.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer,
+        verifierProgram: DECK_VERIFIER,
+      })
+      .signers([player.keypair])
+      .rpc();
+  };
+
+  before("Create a full table", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider, { actionTimeout: new anchor.BN(10) });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+  });
+
+  it("Rejects a proof from hand N when replayed in hand N+1", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: proofs are bound to one hand");
+
+    // Hand N: commit a valid DECK proof
+    const handN = await startHand(program, testTable, players[0]);
+    await runSeedProtocol(program, testTable, handN, players);
+    await verifyDeal(program, testTable, handN, players[0]);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(handN);
+    const seat = tableAccount.playerOne.equals(players[0].keypair.publicKey) ? 0 : 1;
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const [card1, card2] = getHoleCards(shuffleDeck(deckSeed), seat);
+
+    const { proof, commitments } = await generateDeckProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+      deckSeed,
+      playerSeat: seat,
+      card1,
+      card2,
+      salt1: generateSalt(),
+      salt2: generateSalt(),
+    });
+    await commit(handN, players[0], proof, commitments);
+    assert.isTrue(
+      hasFlag(await program.account.hand.fetch(handN), seat === 0 ? HAND_FLAGS.P1_CARDS_COMMITTED : HAND_FLAGS.P2_CARDS_COMMITTED)
+    );
+
+    // End hand N by timing out the other player's card commit
+    await sleep(61_000);
+    await program.methods
+      .timeout()
+      .accountsPartial({
+        caller: players[0].keypair.publicKey,
+        table: testTable.table,
+        hand: handN,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([players[0].keypair])
+      .rpc();
+
+    // Hand N+1: the identical proof must be rejected
+    const handN1 = await startHand(program, testTable, players[0]);
+    await runSeedProtocol(program, testTable, handN1, players);
+    await verifyDeal(program, testTable, handN1, players[0]);
+
+    await expectError(commit(handN1, players[0], proof, commitments), "ProofHandMismatch");
+
+    console.log("   ✅ Replayed proof rejected for the next hand");
+  });
+
+  it("Rejects a proof for the other seat's hole cards", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: proofs are bound to the committing seat");
+
+    // Hand N+1 is still waiting on hole card commitments; drop the
+    // buffer left behind by the rejected replay
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const hand = tableAccount.currentHand;
+    const handAccount = await program.account.hand.fetch(hand);
+    const seat = tableAccount.playerOne.equals(players[0].keypair.publicKey) ? 0 : 1;
+    const otherSeat = 1 - seat;
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const [card1, card2] = getHoleCards(shuffleDeck(deckSeed), otherSeat);
+    const [staleBuffer] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proof_buffer"), hand.toBuffer(), players[0].keypair.publicKey.toBuffer(), Buffer.from([ProofType.Deck])],
+      program.programId
+    );
+    await program.methods
+      .closeProofBuffer()
+      .accounts({ player: players[0].keypair.publicKey, proofBuffer: staleBuffer, hand })
+      .signers([players[0].keypair])
+      .rpc();
+
+    const { proof, commitments } = await generateDeckProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+      deckSeed,
+      playerSeat: seat,
+      card1,
+      card2,
+      salt1: generateSalt(),
+      salt2: generateSalt(),
+      positions: [otherSeat * 2, otherSeat * 2 + 1],
+    });
+    await expectError(commit(hand, players[0], proof, commitments), "HolePositionMismatch");
+
+    console.log("   ✅ Other seat's cards rejected");
+  });
+});
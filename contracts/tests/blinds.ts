@@ -0,0 +1,121 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  findGlobalConfig,
+  seatProfiles,
+  expectError,
+  sleep,
+} from "./utils/game";
+
+describe("Blind Schedule", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Posts the next level's blinds once a level boundary is crossed", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: blinds escalate with the schedule");
+
+    const testTable: TestTable = await createTable(program, provider, {
+      actionTimeout: new anchor.BN(10),
+      blindSchedule: [
+        { duration: new anchor.BN(15), smallBlind: new anchor.BN(10_000000), bigBlind: new anchor.BN(20_000000) },
+        { duration: new anchor.BN(600), smallBlind: new anchor.BN(20_000000), bigBlind: new anchor.BN(40_000000) },
+      ],
+    });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+
+    // First level
+    const firstHand = await startHand(program, testTable, players[0]);
+    assert.equal((await program.account.hand.fetch(firstHand)).pot.toNumber(), 30_000000);
+
+    // Let the seed commit time out and the first level expire
+    await sleep(16_000);
+    await program.methods
+      .timeout()
+      .accountsPartial({
+        caller: players[1].keypair.publicKey,
+        table: testTable.table,
+        hand: firstHand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([players[1].keypair])
+      .rpc();
+
+    // Second level
+    const secondHand = await startHand(program, testTable, players[0]);
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal((await program.account.hand.fetch(secondHand)).pot.toNumber(), 60_000000);
+    assert.equal(tableAccount.smallBlind.toNumber(), 20_000000);
+    assert.equal(tableAccount.bigBlind.toNumber(), 40_000000);
+
+    console.log("   ✅ Second hand posted 20/40 after the 10/20 level expired");
+  });
+
+  it("Rejects a level with a big blind below the small blind", async () => {
+    console.log("🧪 Testing: reject invalid blind level");
+
+    await expectError(
+      createTable(program, provider, {
+        blindSchedule: [
+          { duration: new anchor.BN(60), smallBlind: new anchor.BN(20_000000), bigBlind: new anchor.BN(10_000000) },
+        ],
+      }),
+      "InvalidTableConfig"
+    );
+
+    console.log("   ✅ Correctly rejected inverted blind level");
+  });
+
+  it("Rejects equal blinds and extreme ratios", async () => {
+    console.log("🧪 Testing: reject degenerate blind ratios");
+
+    for (const [smallBlind, bigBlind] of [
+      [20_000000, 20_000000],
+      [1_000000, 20_000000],
+    ]) {
+      await expectError(
+        createTable(program, provider, {
+          smallBlind: new anchor.BN(smallBlind),
+          bigBlind: new anchor.BN(bigBlind),
+        }),
+        "InvalidTableConfig"
+      );
+    }
+
+    console.log("   ✅ Big blind must be 2-4x the small blind");
+  });
+
+  it("Rejects blinds so large the minimum buy-in overflows", async () => {
+    console.log("🧪 Testing: reject overflowing big blind");
+
+    const smallBlind = new anchor.BN(2).pow(new anchor.BN(62));
+    await expectError(
+      createTable(program, provider, { smallBlind, bigBlind: smallBlind.muln(2) }),
+      "InvalidTableConfig"
+    );
+
+    console.log("   ✅ Correctly rejected without overflowing");
+  });
+});
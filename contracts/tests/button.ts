@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import { keccak256 } from "js-sha3";
+import * as crypto from "crypto";
+import {
+  TestPlayer,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  runSeedProtocol,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+const SMALL_BLIND = 10_000000;
+const MAX_TABLES = 10;
+
+describe("Random Opening Button", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  /** keccak("opening_button" || keccak(seat 0 seed || seat 1 seed)), low bit of the first byte */
+  const expectedButton = (seeds: Buffer[]) => {
+    const deckSeed = Buffer.from(keccak256(Buffer.concat(seeds)), "hex");
+    return Buffer.from(keccak256(Buffer.concat([Buffer.from("opening_button"), deckSeed])), "hex")[0] & 1;
+  };
+
+  /** Fresh random-button table through the seed protocol with `seeds` (by seat) */
+  async function drawWithSeeds(seeds: Buffer[]) {
+    const testTable = await createTable(program, provider, { randomButton: true });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const hand = await startHand(program, testTable, players[0]);
+
+    // Nobody can know the button yet, so no blinds are in
+    const started = await program.account.hand.fetch(hand);
+    assert.deepEqual(started.totalBet.map((bet) => bet.toNumber()), [0, 0]);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const bySeat = tableAccount.playerOne.equals(players[0].keypair.publicKey) ? seeds : [seeds[1], seeds[0]];
+    await runSeedProtocol(program, testTable, hand, players, bySeat);
+
+    return {
+      button: (await program.account.table.fetch(testTable.table)).button,
+      handAccount: await program.account.hand.fetch(hand),
+    };
+  }
+
+  it("Draws the first button from both players' seeds", async function () {
+    this.timeout(600000);
+    console.log("🧪 Testing: opening button across fresh tables");
+
+    const seen = new Set<number>();
+    for (let i = 0; i < MAX_TABLES && seen.size < 2; i++) {
+      const seeds = [crypto.randomBytes(32), crypto.randomBytes(32)];
+      const { button, handAccount } = await drawWithSeeds(seeds);
+
+      const expected = expectedButton(seeds);
+      assert.equal(button, expected);
+      assert.equal(handAccount.totalBet[expected].toNumber(), SMALL_BLIND);
+      assert.equal(handAccount.totalBet[1 - expected].toNumber(), 2 * SMALL_BLIND);
+      seen.add(expected);
+    }
+
+    assert.equal(seen.size, 2);
+
+    console.log("   ✅ Button matched the draw and landed in both seats");
+  });
+
+  it("Draws the same button from the same seeds", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: opening button is deterministic given the seeds");
+
+    const seeds = [crypto.randomBytes(32), crypto.randomBytes(32)];
+    const first = await drawWithSeeds(seeds);
+    const second = await drawWithSeeds(seeds);
+
+    assert.equal(first.button, expectedButton(seeds));
+    assert.equal(second.button, first.button);
+
+    console.log(`   ✅ Both tables drew seat ${first.button}`);
+  });
+
+  it("Rejects combining the draw with deterministic seating", async () => {
+    console.log("🧪 Testing: randomButton with deterministicSeating");
+
+    await expectError(
+      createTable(program, provider, { randomButton: true, deterministicSeating: true }),
+      "InvalidTableConfig"
+    );
+
+    console.log("   ✅ Correctly rejected with InvalidTableConfig");
+  });
+});
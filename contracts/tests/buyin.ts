@@ -0,0 +1,107 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+// 250.5 big blinds of 20
+const ODD_BUY_IN = new anchor.BN(501_000000);
+
+describe("Buy-in Sizing", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let player: TestPlayer;
+
+  const addChips = (testTable: TestTable, amount: anchor.BN, who: TestPlayer = player) =>
+    program.methods
+      .addChips(amount)
+      .accounts({
+        player: who.keypair.publicKey,
+        table: testTable.table,
+        playerTokenAccount: who.ata,
+        vault: testTable.vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([who.keypair])
+      .rpc();
+
+  before("Create a player", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    player = await createPlayer(provider, config.usdcMint);
+  });
+
+  it("Rejects buy-ins and top-ups that are not big blind multiples when required", async () => {
+    console.log("🧪 Testing: require_bb_multiple on");
+
+    const testTable = await createTable(program, provider, { requireBbMultiple: true });
+    await expectError(joinTable(program, testTable, player, ODD_BUY_IN), "InvalidBuyIn");
+
+    await joinTable(program, testTable, player, new anchor.BN(500_000000));
+    await expectError(addChips(testTable, new anchor.BN(10_000000)), "InvalidBuyIn");
+    await addChips(testTable, new anchor.BN(20_000000));
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerOneChips.toNumber(), 520_000000);
+
+    console.log("   ✅ Only whole big blinds accepted");
+  });
+
+  it("Accepts any amount in range when not required", async () => {
+    console.log("🧪 Testing: require_bb_multiple off");
+
+    const testTable = await createTable(program, provider);
+    await joinTable(program, testTable, player, ODD_BUY_IN);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerOneChips.toNumber(), ODD_BUY_IN.toNumber());
+
+    console.log("   ✅ Odd buy-in accepted");
+  });
+
+  it("Rejects a maximum buy-in above the big blind cap", async () => {
+    console.log("🧪 Testing: max_buy_in_bb cap of 100bb");
+
+    await expectError(
+      createTable(program, provider, { maxBuyIn: new anchor.BN(3000_000000), maxBuyInBb: 100 }),
+      "InvalidTableConfig"
+    );
+
+    const testTable = await createTable(program, provider, { maxBuyIn: new anchor.BN(2000_000000), maxBuyInBb: 100 });
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.maxBuyInBb, 100);
+
+    console.log("   ✅ 150bb rejected, 100bb accepted");
+  });
+
+  it("Rejects a join or top-up the token account cannot cover", async () => {
+    console.log("🧪 Testing: underfunded join_table and add_chips");
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    const underfunded = await createPlayer(provider, config.usdcMint, 300_000000);
+    const testTable = await createTable(program, provider);
+
+    await expectError(
+      joinTable(program, testTable, underfunded, new anchor.BN(500_000000)),
+      "InsufficientTokenBalance"
+    );
+    await joinTable(program, testTable, underfunded, new anchor.BN(300_000000));
+    await expectError(addChips(testTable, new anchor.BN(20_000000), underfunded), "InsufficientTokenBalance");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerOneChips.toNumber(), 300_000000);
+
+    console.log("   ✅ Correctly rejected with InsufficientTokenBalance");
+  });
+});
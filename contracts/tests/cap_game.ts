@@ -0,0 +1,121 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  HAND_FLAGS,
+  hasFlag,
+  revealStreet,
+} from "./utils/game";
+
+const BUY_IN = 500_000000;
+const BIG_BLIND = 20_000000;
+const CAP_BB = 5;
+const CAP = CAP_BB * BIG_BLIND;
+
+describe("Cap Game", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const wager = async (
+    method: "bet" | "raiseTo",
+    testTable: TestTable,
+    hand: PublicKey,
+    player: TestPlayer,
+    amount: number
+  ) =>
+    program.methods[method](new anchor.BN(amount))
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Seat both players at a fresh cap-game table and reach preflop */
+  async function newHand() {
+    const testTable = await createTable(program, provider, { handBetCapBb: CAP_BB });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+    const smallBlind = await playerToAct(program, testTable, hand, players);
+    const bigBlind = players.find((p) => p !== smallBlind);
+    return { testTable, hand, smallBlind, bigBlind };
+  }
+
+  /** Both seats wagered exactly the cap, are capped, and kept the rest of their stacks */
+  async function assertCapped(testTable: TestTable, hand: PublicKey) {
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.totalBet.map((b) => b.toNumber()), [CAP, CAP]);
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P1_ALL_IN));
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P2_ALL_IN));
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.FAST_SHOWDOWN));
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerOneChips.toNumber(), BUY_IN - CAP);
+    assert.equal(tableAccount.playerTwoChips.toNumber(), BUY_IN - CAP);
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Truncates a preflop raise at the cap", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: raise to 8bb with a 5bb cap");
+
+    const { testTable, hand, smallBlind, bigBlind } = await newHand();
+    await wager("raiseTo", testTable, hand, smallBlind, 8 * BIG_BLIND);
+    assert.equal((await program.account.hand.fetch(hand)).currentBet.toNumber(), CAP);
+
+    await act(program, testTable, hand, bigBlind, "call");
+    await assertCapped(testTable, hand);
+
+    // Neither capped seat may wager again
+    await expectError(act(program, testTable, hand, bigBlind, "check"), "BettingClosed");
+
+    console.log("   ✅ Raise cut to 5bb, stacks kept the rest");
+  });
+
+  it("Truncates a flop bet to what is left under the cap", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: 10bb flop bet after a limped pot");
+
+    const { testTable, hand, smallBlind, bigBlind } = await newHand();
+    await act(program, testTable, hand, smallBlind, "call");
+    await act(program, testTable, hand, bigBlind, "check");
+
+    const bettor = await playerToAct(program, testTable, hand, players);
+    await revealStreet(program, testTable, hand, bettor);
+    await wager("bet", testTable, hand, bettor, 10 * BIG_BLIND);
+    assert.equal((await program.account.hand.fetch(hand)).currentBet.toNumber(), CAP - BIG_BLIND);
+
+    await act(program, testTable, hand, players.find((p) => p !== bettor), "call");
+    await assertCapped(testTable, hand);
+
+    console.log("   ✅ Bet cut to 4bb, the cap less the big blind already in");
+  });
+});
@@ -0,0 +1,121 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+} from "./utils/game";
+
+const MAX_BUY_IN = 1000_000000;
+const MIN_BUY_IN = 200_000000;
+
+describe("Cash Out Excess", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let deepStack: TestPlayer;
+  let shortStack: TestPlayer;
+  let testTable: TestTable;
+
+  const cashOutExcess = (player: TestPlayer) =>
+    program.methods
+      .cashOutExcess()
+      .accounts({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        playerTokenAccount: player.ata,
+        vault: testTable.vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  const tokenBalance = async (player: TestPlayer) =>
+    Number((await provider.connection.getTokenAccountBalance(player.ata)).value.amount);
+
+  before("Seat a max buy-in against a min buy-in", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    deepStack = await createPlayer(provider, config.usdcMint);
+    shortStack = await createPlayer(provider, config.usdcMint);
+
+    testTable = await createTable(program, provider);
+    await joinTable(program, testTable, deepStack, new anchor.BN(MAX_BUY_IN));
+    await joinTable(program, testTable, shortStack, new anchor.BN(MIN_BUY_IN));
+  });
+
+  it("Rejects cashing out during a hand or without excess chips", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: cash_out_excess guards");
+
+    await expectError(cashOutExcess(deepStack), "NoExcessChips");
+
+    // Short stack shoves, deep stack calls, and both agree the short stack lost
+    const players = [deepStack, shortStack];
+    const { hand } = await playToPreflop(program, testTable, players);
+    await expectError(cashOutExcess(deepStack), "HandInProgress");
+
+    for (;;) {
+      const handAccount = await program.account.hand.fetch(hand);
+      if (!("preflop" in handAccount.stage)) break;
+      const actor = await playerToAct(program, testTable, hand, players);
+      await act(program, testTable, hand, actor, actor === shortStack ? "allIn" : "call");
+    }
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const result = tableAccount.playerOne.equals(deepStack.keypair.publicKey) ? { seat0: {} } : { seat1: {} };
+    for (const player of players) {
+      await program.methods
+        .agreeResult(result as any)
+        .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+        .signers([player.keypair])
+        .rpc();
+    }
+    await program.methods
+      .claimPot()
+      .accountsPartial({
+        player: deepStack.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([deepStack.keypair])
+      .rpc();
+
+    console.log("   ✅ Guards hold until the hand is over");
+  });
+
+  it("Tops a 120% stack down to exactly the maximum buy-in", async () => {
+    console.log("🧪 Testing: cash_out_excess between hands");
+
+    let tableAccount = await program.account.table.fetch(testTable.table);
+    const seat = tableAccount.playerOne.equals(deepStack.keypair.publicKey) ? 0 : 1;
+    const chipsOf = (account: typeof tableAccount) =>
+      (seat === 0 ? account.playerOneChips : account.playerTwoChips).toNumber();
+    assert.equal(chipsOf(tableAccount), MAX_BUY_IN + MIN_BUY_IN);
+
+    const balanceBefore = await tokenBalance(deepStack);
+    await cashOutExcess(deepStack);
+
+    tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(chipsOf(tableAccount), MAX_BUY_IN);
+    assert.equal((await tokenBalance(deepStack)) - balanceBefore, MIN_BUY_IN);
+    await expectError(cashOutExcess(deepStack), "NoExcessChips");
+
+    console.log("   ✅ Excess returned, stack at max buy-in");
+  });
+});
@@ -0,0 +1,167 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  startHand,
+  findGlobalConfig,
+  expectError,
+  act,
+} from "./utils/game";
+
+describe("Table Close", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  const leave = (player: TestPlayer, { table, vault }: TestTable = testTable) =>
+    program.methods
+      .leaveTable()
+      .accounts({
+        player: player.keypair.publicKey,
+        table,
+        hand: null,
+        playerTokenAccount: player.ata,
+        vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Close as the global authority unless `closer` is given */
+  const closeTable = (
+    { table, vault }: TestTable = testTable,
+    closer?: TestPlayer,
+    creator: PublicKey = provider.wallet.publicKey
+  ) =>
+    program.methods
+      .closeTable()
+      .accountsPartial({
+        authority: closer?.keypair.publicKey ?? provider.wallet.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table,
+        vault,
+        creator,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers(closer ? [closer.keypair] : [])
+      .rpc();
+
+  before("Create a one-hand table", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider, { maxHands: new anchor.BN(1) });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+  });
+
+  it("Starts closing once the hand limit is reached", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: max_hands cap");
+
+    const { hand } = await playToPreflop(program, testTable, players);
+    await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "fold");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.handsPlayed.toNumber(), 1);
+    assert.isTrue(tableAccount.closing);
+
+    await expectError(startHand(program, testTable, players[0]), "TableClosing");
+
+    console.log("   ✅ Table is closing and no new hand can start");
+  });
+
+  it("Cannot close while players are seated", async () => {
+    console.log("🧪 Testing: close_table with seated players");
+
+    await leave(players[0]);
+    await expectError(closeTable(), "TableNotEmpty");
+
+    console.log("   ✅ Correctly rejected with TableNotEmpty");
+  });
+
+  it("Closes the table and vault once both players have left", async () => {
+    console.log("🧪 Testing: clean close");
+
+    await leave(players[1]);
+    await closeTable();
+
+    assert.isNull(await provider.connection.getAccountInfo(testTable.table));
+    assert.isNull(await provider.connection.getAccountInfo(testTable.vault));
+
+    console.log("   ✅ Table and vault closed");
+  });
+
+  it("Cannot close during a hand", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: close_table mid-hand");
+
+    const liveTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, liveTable, player);
+    }
+    await playToPreflop(program, liveTable, players);
+
+    await expectError(closeTable(liveTable), "HandInProgress");
+
+    console.log("   ✅ Correctly rejected with HandInProgress");
+  });
+
+  it("Closes a table that never reached a hand limit once it is empty", async () => {
+    console.log("🧪 Testing: close_table without max_hands");
+
+    const idleTable = await createTable(program, provider);
+    await joinTable(program, idleTable, players[0]);
+    await expectError(closeTable(idleTable), "TableNotEmpty");
+
+    await leave(players[0], idleTable);
+    const rentBefore = await provider.connection.getBalance(provider.wallet.publicKey);
+    await closeTable(idleTable);
+
+    assert.isNull(await provider.connection.getAccountInfo(idleTable.table));
+    assert.isNull(await provider.connection.getAccountInfo(idleTable.vault));
+    assert.isAbove(await provider.connection.getBalance(provider.wallet.publicKey), rentBefore);
+
+    const tableAccount = await program.account.table.fetchNullable(idleTable.table);
+    assert.isNull(tableAccount);
+
+    console.log("   ✅ Rent refunded to the creator");
+  });
+
+  it("Records the creator and only lets them (or the authority) close", async () => {
+    console.log("🧪 Testing: creator-gated close");
+
+    const [creator, outsider] = players;
+    const ownTable = await createTable(program, provider, { creator: creator.keypair });
+
+    const tableAccount = await program.account.table.fetch(ownTable.table);
+    assert.isTrue(tableAccount.creator.equals(creator.keypair.publicKey));
+
+    await expectError(closeTable(ownTable, outsider, creator.keypair.publicKey), "Unauthorized");
+    await closeTable(ownTable, creator, creator.keypair.publicKey);
+
+    assert.isNull(await provider.connection.getAccountInfo(ownTable.table));
+
+    console.log("   ✅ Non-creator rejected, creator closed the table");
+  });
+});
@@ -13,8 +13,9 @@ import * as crypto from "crypto";
 import { keccak256 } from "js-sha3";
 import { shuffleDeck, getHoleCards, getFlopCards } from "./utils/deck";
 import { generateSalt } from "./utils/crypto";
-import { generateDeckProof, generateRevealProof, generateShowdownProof, proofToBytes, uploadProofToBuffer, ProofType } from "./utils/prover";
+import { generateDeckProof, generateDealProof, generateRevealProof, generateShowdownProof, proofToBytes, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
 import { commitmentToBytes } from "./utils/commitments";
+import { HAND_FLAGS, hasFlag, tableConfig } from "./utils/game";
 
 
 describe("ZkPoker Contracts - Comprehensive Tests", () => {
@@ -186,6 +187,63 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
 
       console.log("   ✅ Protocol unpaused");
     });
+
+    it("Rejects a second initialize", async () => {
+      console.log("🧪 Testing: initialize twice");
+
+      try {
+        await program.methods
+          .initialize()
+          .accounts({
+            authority: authority.publicKey,
+            globalConfig,
+            usdcMint,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.include(err.message, "AlreadyInitialized");
+        console.log("   ✅ Correctly rejected - already initialized");
+      }
+    });
+
+    it("Updates the mint (authority only)", async () => {
+      console.log("🧪 Testing: update_config");
+
+      const newMint = await createMint(provider.connection, authority.payer, authority.publicKey, null, 6);
+
+      try {
+        await program.methods
+          .updateConfig()
+          .accounts({ authority: player1.publicKey, globalConfig, usdcMint: newMint })
+          .signers([player1])
+          .rpc();
+
+        assert.fail("Should have thrown error");
+      } catch (err) {
+        assert.include(err.message, "Unauthorized");
+        console.log("   ✅ Correctly rejected - not the authority");
+      }
+
+      await program.methods
+        .updateConfig()
+        .accounts({ authority: authority.publicKey, globalConfig, usdcMint: newMint })
+        .rpc();
+      let config = await program.account.globalConfig.fetch(globalConfig);
+      assert.equal(config.usdcMint.toBase58(), newMint.toBase58());
+
+      // Restore the mint the remaining tests fund players with
+      await program.methods
+        .updateConfig()
+        .accounts({ authority: authority.publicKey, globalConfig, usdcMint })
+        .rpc();
+      config = await program.account.globalConfig.fetch(globalConfig);
+      assert.equal(config.usdcMint.toBase58(), usdcMint.toBase58());
+
+      console.log("   ✅ Mint updated and restored");
+    });
   });
 
   describe("Table Module", () => {
@@ -214,19 +272,45 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       );
 
       await program.methods
-        .createTable(
+        .createTable({
           smallBlind,
           bigBlind,
           minBuyIn,
           maxBuyIn,
-          new anchor.BN(30) // 30 second timeout
-        )
+          actionTimeout: new anchor.BN(30), // 30 second timeout
+          timeBank: null, // default time bank
+          autoContinue: false, // no auto-continue
+          blindSchedule: [], // static blinds
+          maxHands: new anchor.BN(0), // no hand limit
+          access: { public: {} },
+          straddleAllowed: false, // no straddle
+          requireBbMultiple: false, // any buy-in size
+          disputeWindow: new anchor.BN(0), // instant showdown payout
+          maxBuyInBb: 0, // no buy-in cap in big blinds
+          rakeBps: 0, // no rake
+          rakeCap: new anchor.BN(0), // uncapped rake
+          rakeOnlyAfterFlop: true, // no flop, no drop
+          minHandsBeforeLeave: new anchor.BN(0), // leave any time
+          deterministicSeating: false, // seats in join order
+          randomButton: false, // first button in seat 0
+          potCap: new anchor.BN(0), // uncapped pot
+          autoMuck: false, // silent seats must muck or time out
+          handBetCapBb: 0, // no cap game
+          firstToActRule: { standard: {} }, // heads-up action order
+          timeoutPolicy: { forfeit: {} }, // a timed-out seat loses the pot
+          minActionInterval: new anchor.BN(0), // no action throttle
+          rejoinCooldown: new anchor.BN(0), // rejoin straight after leaving
+          deckSize: 52, // full deck
+          commitDeadline: null, // card commits get the action timeout, at least 60s
+          splitHandRent: false, // starter pays the whole hand rent
+          buttonIsBigBlind: false, // button posts the small blind
+        })
         .accounts({
           creator: authority.publicKey,
           globalConfig,
           table,
           vault,
-          usdcMint: configUsdcMint,
+          mint: configUsdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -405,6 +489,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
           globalConfig,
           table,
           hand,
+          coPayer: null,
           systemProgram: SystemProgram.programId,
         })
         .signers([player1])
@@ -434,7 +519,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
         .rpc();
 
       const handAccount = await program.account.hand.fetch(hand);
-      assert.equal(handAccount.p1SeedCommitted, true);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P1_SEED_COMMITTED));
 
       console.log("   ✅ Player 1 committed seed");
     });
@@ -453,7 +538,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
         .rpc();
 
       const handAccount = await program.account.hand.fetch(hand);
-      assert.equal(handAccount.p2SeedCommitted, true);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P2_SEED_COMMITTED));
       assert.deepEqual(handAccount.stage, { seedReveal: {} }); // SeedReveal
 
       console.log("   ✅ Player 2 committed seed");
@@ -474,7 +559,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
         .rpc();
 
       const handAccount = await program.account.hand.fetch(hand);
-      assert.equal(handAccount.p1SeedRevealed, true);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P1_SEED_REVEALED));
 
       console.log("   ✅ Player 1 revealed seed");
     });
@@ -493,7 +578,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
         .rpc();
 
       const handAccount = await program.account.hand.fetch(hand);
-      assert.equal(handAccount.p2SeedRevealed, true);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P2_SEED_REVEALED));
       assert.deepEqual(handAccount.stage, { cardCommit: {} }); // CardCommit
       assert.isNotNull(handAccount.deckSeed);
 
@@ -526,9 +611,11 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
     let player2Salt1: bigint, player2Salt2: bigint;
     let player1Commitments: [bigint, bigint];
     let player2Commitments: [bigint, bigint];
+    let proofHandId: bigint;
 
     before("Derive shuffled deck from deck_seed", async () => {
       const handAccount = await program.account.hand.fetch(hand);
+      proofHandId = handId(BigInt(tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion);
       deckSeed = Buffer.from(handAccount.deckSeed);
       shuffledDeck = shuffleDeck(deckSeed);
 
@@ -536,6 +623,37 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       console.log(`   First 10 cards: [${shuffledDeck.slice(0, 10).join(", ")}]`);
     });
 
+    it("Player 1 proves the deck shuffle with ZK proof", async function () {
+      this.timeout(30000);
+      console.log("🧪 Testing: verify_deal with ZK proof");
+
+      const proof = await generateDealProof({ handId: proofHandId, deckSeed, shuffledDeck });
+      console.log(`   Proof: ${proof.length} bytes`);
+
+      const proofBuffer = await uploadProofToBuffer(program, proof, hand, player1, ProofType.Deal);
+
+      // Verifier program from constants
+      const dealVerifier = new PublicKey("DewUCARGDNMyp2yWwn69VF5upEuchW7pfUMAAznFiJzy");
+
+      await program.methods
+        .verifyDeal()
+        .accounts({
+          player: player1.publicKey,
+          globalConfig,
+          table,
+          hand,
+          proofBuffer,
+          verifierProgram: dealVerifier,
+        })
+        .signers([player1])
+        .rpc();
+
+      const handAccount = await program.account.hand.fetch(hand);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.DEAL_VERIFIED));
+
+      console.log("   ✅ Deck shuffle verified, deck root stored");
+    });
+
     it("Player 1 commits hole cards with ZK proof", async function () {
       this.timeout(30000);
       console.log("🧪 Testing: commit_hole_cards (Player 1) with ZK proof");
@@ -550,6 +668,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       console.log(`   Player 1 cards: ${card1}, ${card2}`);
 
       const { proof, commitments } = await generateDeckProof({
+        handId: proofHandId,
         deckSeed,
         playerSeat,
         card1,
@@ -594,7 +713,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
         .rpc();
 
       const handAccount = await program.account.hand.fetch(hand);
-      assert.equal(handAccount.p1CardsCommitted, true);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P1_CARDS_COMMITTED));
 
       console.log("   ✅ Player 1 hole cards committed with ZK proof");
     });
@@ -613,6 +732,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       console.log(`   Player 2 cards: ${card1}, ${card2}`);
 
       const { proof, commitments } = await generateDeckProof({
+        handId: proofHandId,
         deckSeed,
         playerSeat,
         card1,
@@ -656,7 +776,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
         .rpc();
 
       const handAccount = await program.account.hand.fetch(hand);
-      assert.equal(handAccount.p2CardsCommitted, true);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P2_CARDS_COMMITTED));
       assert.deepEqual(handAccount.stage, { preFlop: {} });
 
       console.log("   ✅ Player 2 hole cards committed with ZK proof");
@@ -671,6 +791,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       console.log(`   Flop cards: [${flopCards.join(", ")}]`);
 
       const proof = await generateRevealProof({
+        handId: proofHandId,
         deckSeed,
         cards: Array.from(flopCards),
         numCards: 3,
@@ -722,6 +843,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       console.log(`   Turn card: ${turnCard}`);
 
       const proof = await generateRevealProof({
+        handId: proofHandId,
         deckSeed,
         cards,
         numCards: 4,
@@ -774,6 +896,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       console.log(`   River card: ${riverCard}`);
 
       const proof = await generateRevealProof({
+        handId: proofHandId,
         deckSeed,
         cards,
         numCards: 5,
@@ -835,6 +958,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       console.log(`   Community: [${communityCards.join(", ")}]`);
 
       const { proof, handRank } = await generateShowdownProof({
+        handId: proofHandId,
         commitment1: player1Commitments[0],
         commitment2: player1Commitments[1],
         communityCards,
@@ -872,7 +996,7 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
         .rpc();
 
       const updatedHandAccount = await program.account.hand.fetch(hand);
-      assert.equal(updatedHandAccount.p1HandRevealed, true);
+      assert.isTrue(hasFlag(updatedHandAccount, HAND_FLAGS.P1_REVEALED));
 
       console.log("   ✅ Player 1 hand revealed with ZK proof");
     });
@@ -916,19 +1040,13 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
       );
 
       await program.methods
-        .createTable(
-          new anchor.BN(10_000000),
-          new anchor.BN(20_000000),
-          new anchor.BN(200_000000),
-          new anchor.BN(1000_000000),
-          new anchor.BN(30)
-        )
+        .createTable(tableConfig())
         .accounts({
           creator: authority.publicKey,
           globalConfig,
           table: newTable,
           vault: newVault,
-          usdcMint,
+          mint: usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -1005,19 +1123,13 @@ describe("ZkPoker Contracts - Comprehensive Tests", () => {
 
       try {
         await program.methods
-          .createTable(
-            new anchor.BN(10_000000),
-            new anchor.BN(20_000000),
-            new anchor.BN(200_000000),
-            new anchor.BN(1000_000000),
-            new anchor.BN(30)
-          )
+          .createTable(tableConfig())
           .accounts({
             creator: authority.publicKey,
             globalConfig,
             table: pausedTable,
             vault: pausedVault,
-            usdcMint,
+            mint: usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
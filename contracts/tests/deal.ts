@@ -0,0 +1,159 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import * as crypto from "crypto";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  runSeedProtocol,
+  verifyDeal,
+  commitHoleCards,
+  findGlobalConfig,
+  expectError,
+  DEAL_VERIFIER,
+  DECK_VERIFIER,
+} from "./utils/game";
+import { shuffleDeck, commitDeck } from "./utils/deck";
+import { generateDealProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+describe("Deal Verification", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+
+  const findBuffer = (player: TestPlayer, proofType: ProofType) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proof_buffer"),
+        hand.toBuffer(),
+        player.keypair.publicKey.toBuffer(),
+        Buffer.from([proofType]),
+      ],
+      program.programId
+    )[0];
+
+  /** Reclaim a buffer left behind by a rejected proof */
+  const closeBuffer = (player: TestPlayer, proofType: ProofType) =>
+    program.methods
+      .closeProofBuffer()
+      .accounts({ player: player.keypair.publicKey, proofBuffer: findBuffer(player, proofType), hand })
+      .signers([player.keypair])
+      .rpc();
+
+  before("Play a hand to the card commit stage", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    hand = await startHand(program, testTable, players[0]);
+    await runSeedProtocol(program, testTable, hand, players);
+  });
+
+  it("Rejects hole card commitments before the shuffle is proven", async () => {
+    console.log("🧪 Testing: commit_hole_cards without verify_deal");
+
+    const proofBuffer = await uploadProofToBuffer(program, Buffer.alloc(400), hand, players[0].keypair, ProofType.Deck);
+
+    await expectError(
+      program.methods
+        .commitHoleCards([Array(32).fill(1), Array(32).fill(2)])
+        .accounts({
+          player: players[0].keypair.publicKey,
+          globalConfig: findGlobalConfig(program),
+          table: testTable.table,
+          hand,
+          proofBuffer,
+          verifierProgram: DECK_VERIFIER,
+        })
+        .signers([players[0].keypair])
+        .rpc(),
+      "DealNotVerified"
+    );
+    await closeBuffer(players[0], ProofType.Deck);
+
+    console.log("   ✅ Correctly rejected with DealNotVerified");
+  });
+
+  it("Rejects a permutation shuffled from a different seed", async function () {
+    this.timeout(60000);
+    console.log("🧪 Testing: verify_deal with another seed's deck");
+
+    await expectError(verifyDeal(program, testTable, hand, players[0], crypto.randomBytes(32)), "DeckSeedMismatch");
+    await closeBuffer(players[0], ProofType.Deal);
+
+    console.log("   ✅ Correctly rejected with DeckSeedMismatch");
+  });
+
+  it("Rejects a tampered proof", async function () {
+    this.timeout(60000);
+    console.log("🧪 Testing: verify_deal with a corrupted proof");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const proof = await generateDealProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+      deckSeed,
+      shuffledDeck: shuffleDeck(deckSeed),
+    });
+    proof[0] ^= 0xff;
+
+    const proofBuffer = await uploadProofToBuffer(program, proof, hand, players[1].keypair, ProofType.Deal);
+    await expectError(
+      program.methods
+        .verifyDeal()
+        .accounts({
+          player: players[1].keypair.publicKey,
+          globalConfig: findGlobalConfig(program),
+          table: testTable.table,
+          hand,
+          proofBuffer,
+          verifierProgram: DEAL_VERIFIER,
+        })
+        .signers([players[1].keypair])
+        .rpc(),
+      "ProofVerificationFailed"
+    );
+    await closeBuffer(players[1], ProofType.Deal);
+
+    console.log("   ✅ Correctly rejected with ProofVerificationFailed");
+  });
+
+  it("Stores the deck root and then accepts commitments", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: verify_deal with a valid proof");
+
+    await verifyDeal(program, testTable, hand, players[0]);
+
+    const handAccount = await program.account.hand.fetch(hand);
+    const expectedRoot = commitDeck(shuffleDeck(Buffer.from(handAccount.deckSeed)));
+    assert.equal(BigInt("0x" + Buffer.from(handAccount.deckRoot).toString("hex")), expectedRoot);
+    await expectError(verifyDeal(program, testTable, hand, players[1]), "DealAlreadyVerified");
+    await closeBuffer(players[1], ProofType.Deal);
+
+    for (const player of players) {
+      await commitHoleCards(program, testTable, hand, player);
+    }
+    assert.deepEqual((await program.account.hand.fetch(hand)).stage, { preflop: {} });
+
+    console.log("   ✅ Deck root matches the seed's shuffle, hand dealt");
+  });
+});
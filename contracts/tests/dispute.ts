@@ -0,0 +1,204 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  HoleCardSecrets,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  sleep,
+  REVEAL_VERIFIER,
+  SHOWDOWN_VERIFIER,
+} from "./utils/game";
+import { shuffleDeck, getCommunityCards } from "./utils/deck";
+import { generateRevealProof, generateShowdownProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+const DISPUTE_WINDOW = 10;
+
+describe("Showdown Dispute Window", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+  let secrets: HoleCardSecrets[];
+  let proofs: { proof: Buffer; handRank: bigint }[];
+  let seats: number[];
+
+  const showdownMethod = (player: TestPlayer, proof: Buffer) =>
+    uploadProofToBuffer(program, proof, hand, player.keypair, ProofType.Showdown).then((proofBuffer) => ({
+      player: player.keypair.publicKey,
+      globalConfig: findGlobalConfig(program),
+      table: testTable.table,
+      hand,
+      proofBuffer,
+      verifierProgram: SHOWDOWN_VERIFIER,
+    }));
+
+  const claimPot = async (player: TestPlayer) =>
+    program.methods
+      .claimPot()
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  const revealHand = async (i: number, rank: bigint, accounts?: Awaited<ReturnType<typeof showdownMethod>>) =>
+    program.methods
+      .revealHand(new anchor.BN(rank.toString()))
+      .accountsPartial(accounts ?? (await showdownMethod(players[i], proofs[i].proof)))
+      .signers([players[i].keypair])
+      .rpc();
+
+  before("Run an all-in hand to showdown and prove both hands", async function () {
+    this.timeout(600000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider, { disputeWindow: new anchor.BN(DISPUTE_WINDOW) });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    ({ hand, secrets } = await playToPreflop(program, testTable, players));
+
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "call");
+    // The aggressor shows first, so keep them as players[0]
+    if (shover !== players[0]) {
+      players.reverse();
+      secrets.reverse();
+    }
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    const proofHandId = handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion);
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const shuffledDeck = shuffleDeck(deckSeed);
+    const { flop, turn, river } = getCommunityCards(shuffledDeck);
+    const board: [number, number, number, number, number] = [...flop, turn, river];
+
+    const revealProof = await generateRevealProof({ handId: proofHandId, deckSeed, cards: board, numCards: 5, shuffledDeck });
+    await program.methods
+      .revealAllCommunity(board)
+      .accountsPartial({
+        player: shover.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer: await uploadProofToBuffer(program, revealProof, hand, shover.keypair, ProofType.Reveal),
+        verifierProgram: REVEAL_VERIFIER,
+      })
+      .signers([shover.keypair])
+      .rpc();
+
+    seats = players.map((p) => (tableAccount.playerOne.equals(p.keypair.publicKey) ? 0 : 1));
+    proofs = [];
+    for (const secret of secrets) {
+      proofs.push(
+        await generateShowdownProof({
+          handId: proofHandId,
+          commitment1: secret.commitments[0],
+          commitment2: secret.commitments[1],
+          communityCards: board,
+          holeCard1: secret.cards[0],
+          holeCard2: secret.cards[1],
+          salt1: secret.salts[0],
+          salt2: secret.salts[1],
+        })
+      );
+    }
+  });
+
+  it("Rejects a claimed rank the proof does not output", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: inflated rank at reveal");
+
+    // players[0] claims one rank above what their proof outputs
+    const accounts = await showdownMethod(players[0], proofs[0].proof);
+    await expectError(revealHand(0, proofs[0].handRank + 1n, accounts), "RankMismatch");
+
+    await revealHand(0, proofs[0].handRank, accounts);
+    await revealHand(1, proofs[1].handRank);
+
+    const handAccount = await program.account.hand.fetch(hand);
+    const ranks = seats[0] === 0 ? [proofs[0].handRank, proofs[1].handRank] : [proofs[1].handRank, proofs[0].handRank];
+    assert.equal(handAccount.p1HandRank.toString(), ranks[0].toString());
+    assert.equal(handAccount.p2HandRank.toString(), ranks[1].toString());
+
+    console.log("   ✅ Only the proven ranks were stored");
+  });
+
+  it("Blocks the payout while the result can be challenged", async () => {
+    console.log("🧪 Testing: claim_pot inside the dispute window");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.notDeepEqual(handAccount.winner, { none: {} });
+    assert.isAbove(handAccount.winnerDeterminedAt.toNumber(), 0);
+
+    await expectError(claimPot(players[0]), "DisputeWindowOpen");
+
+    console.log("   ✅ Correctly rejected with DisputeWindowOpen");
+  });
+
+  it("Leaves ranks proven at reveal with nothing to challenge", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: challenge_showdown as a backstop");
+
+    // Each seat's own proof re-proves the rank already stored
+    for (const [i, opponent] of [1, 0].entries()) {
+      await expectError(
+        program.methods
+          .challengeShowdown(seats[i])
+          .accountsPartial(await showdownMethod(players[opponent], proofs[i].proof))
+          .signers([players[opponent].keypair])
+          .rpc(),
+        "RankNotDisputed"
+      );
+    }
+
+    const handAccount = await program.account.hand.fetch(hand);
+    const ranks = seats[0] === 0 ? [proofs[0].handRank, proofs[1].handRank] : [proofs[1].handRank, proofs[0].handRank];
+    const expected = ranks[0] > ranks[1] ? { seat0: {} } : ranks[1] > ranks[0] ? { seat1: {} } : { split: {} };
+    assert.deepEqual(handAccount.winner, expected);
+
+    console.log("   ✅ Challenges of proven ranks rejected, winner unchanged");
+  });
+
+  it("Pays out once the window has elapsed", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: claim_pot after the dispute window");
+
+    await sleep((DISPUTE_WINDOW + 2) * 1000);
+
+    const handAccount = await program.account.hand.fetch(hand);
+    const winnerSeat = "seat0" in handAccount.winner ? 0 : "seat1" in handAccount.winner ? 1 : seats[0];
+    await claimPot(players[seats.indexOf(winnerSeat)]);
+
+    const after = await program.account.hand.fetch(hand);
+    assert.deepEqual(after.stage, { complete: {} });
+
+    console.log("   ✅ Pot paid after the window closed");
+  });
+});
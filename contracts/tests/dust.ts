@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getOrCreateAssociatedTokenAccount, transfer } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+const DUST = 7;
+
+describe("Vault Dust", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let destination: PublicKey;
+
+  const sweep = (authority: PublicKey) =>
+    program.methods.sweepVaultDust().accounts({
+      authority,
+      globalConfig: findGlobalConfig(program),
+      table: testTable.table,
+      destination,
+      vault: testTable.vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+  const balance = async (account: PublicKey) =>
+    Number((await provider.connection.getTokenAccountBalance(account)).value.amount);
+
+  before("Seat two players and leave stray tokens in the vault", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+    destination = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        (provider.wallet as anchor.Wallet).payer,
+        config.usdcMint,
+        provider.wallet.publicKey
+      )
+    ).address;
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+
+    // Tokens sent straight to the vault belong to no stack
+    await transfer(provider.connection, players[0].keypair, players[0].ata, testTable.vault, players[0].keypair, DUST);
+  });
+
+  it("Sweeps exactly the surplus and leaves the stacks alone", async () => {
+    console.log("🧪 Testing: sweep_vault_dust");
+
+    await expectError(sweep(players[0].keypair.publicKey).signers([players[0].keypair]).rpc(), "Unauthorized");
+
+    const before = await program.account.table.fetch(testTable.table);
+    const destinationBefore = await balance(destination);
+    await sweep(provider.wallet.publicKey).rpc();
+
+    const after = await program.account.table.fetch(testTable.table);
+    assert.equal((await balance(destination)) - destinationBefore, DUST);
+    assert.equal(after.playerOneChips.toString(), before.playerOneChips.toString());
+    assert.equal(after.playerTwoChips.toString(), before.playerTwoChips.toString());
+    assert.equal(await balance(testTable.vault), after.playerOneChips.add(after.playerTwoChips).toNumber());
+
+    await expectError(sweep(provider.wallet.publicKey).rpc(), "NoVaultDust");
+
+    console.log("   ✅ Dust swept, vault matches stacks");
+  });
+
+  it("Rejects sweeping while a hand is in progress", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: sweep_vault_dust during a hand");
+
+    await playToPreflop(program, testTable, players);
+    await expectError(sweep(provider.wallet.publicKey).rpc(), "HandInProgress");
+
+    console.log("   ✅ Correctly rejected with HandInProgress");
+  });
+});
@@ -0,0 +1,129 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  HAND_FLAGS,
+  hasFlag,
+  revealStreet,
+} from "./utils/game";
+
+const SHORT_STACK = 300_000000;
+const BIG_BLIND = 20_000000;
+
+describe("Fast Showdown", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  /** Seat both players at a fresh table (players[0] short-stacked) and reach preflop */
+  async function newHand(): Promise<{ testTable: TestTable; hand: PublicKey }> {
+    const testTable = await createTable(program, provider);
+    await joinTable(program, testTable, players[0], new anchor.BN(SHORT_STACK));
+    await joinTable(program, testTable, players[1]);
+    const { hand } = await playToPreflop(program, testTable, players);
+    return { testTable, hand };
+  }
+
+  const fastShowdown = async (hand: PublicKey) =>
+    hasFlag(await program.account.hand.fetch(hand), HAND_FLAGS.FAST_SHOWDOWN);
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Sets fast_showdown when an all-in is called", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: short stack shoves, big stack calls");
+
+    const { testTable, hand } = await newHand();
+    const [shortStack, bigStack] = players;
+
+    // The big stack limps if it acts first, then calls the shove
+    while ((await program.account.hand.fetch(hand)).stage.preflop) {
+      const actor = await playerToAct(program, testTable, hand, players);
+      await act(program, testTable, hand, actor, actor === shortStack ? "allIn" : "call");
+      if (actor === shortStack) {
+        assert.isFalse(await fastShowdown(hand));
+      }
+    }
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { flop: {} });
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.FAST_SHOWDOWN));
+
+    // The caller still has chips but no betting turns are left
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const bigStackSeat = tableAccount.playerOne.equals(bigStack.keypair.publicKey) ? 0 : 1;
+    assert.isAbove((bigStackSeat === 0 ? tableAccount.playerOneChips : tableAccount.playerTwoChips).toNumber(), 0);
+    await expectError(act(program, testTable, hand, bigStack, "check"), "BettingClosed");
+
+    console.log("   ✅ fast_showdown set, further betting rejected with BettingClosed");
+  });
+
+  it("Leaves fast_showdown clear when an all-in is folded to", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: shove and fold");
+
+    const { testTable, hand } = await newHand();
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "fold");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { complete: {} });
+    assert.isFalse(hasFlag(handAccount, HAND_FLAGS.FAST_SHOWDOWN));
+
+    console.log("   ✅ Folded all-in left the flag clear");
+  });
+
+  it("Leaves fast_showdown clear when no one is all-in", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: limp, check, then a called flop bet");
+
+    const { testTable, hand } = await newHand();
+    const smallBlind = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, smallBlind, "call");
+    await act(program, testTable, hand, players.find((p) => p !== smallBlind), "check");
+    assert.isFalse(await fastShowdown(hand));
+
+    const bettor = await playerToAct(program, testTable, hand, players);
+    await revealStreet(program, testTable, hand, bettor);
+    await program.methods
+      .bet(new anchor.BN(2 * BIG_BLIND))
+      .accountsPartial({
+        player: bettor.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([bettor.keypair])
+      .rpc();
+    await act(program, testTable, hand, players.find((p) => p !== bettor), "call");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { turn: {} });
+    assert.isFalse(hasFlag(handAccount, HAND_FLAGS.FAST_SHOWDOWN));
+
+    console.log("   ✅ Ordinary streets left the flag clear");
+  });
+});
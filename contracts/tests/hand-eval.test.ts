@@ -0,0 +1,74 @@
+/**
+ * Hand rank encoding: scores must order hands exactly, with equal scores
+ * only for true chops (mirrors the zkpoker_lib circuit tests)
+ */
+
+import { describe, it } from "mocha";
+import { expect } from "chai";
+
+import {
+  evaluateHandFromIndices,
+  encodeHandScore,
+  compareHands,
+  RANK_TWO_PAIR,
+  RANK_FLUSH,
+  RANK_FULL_HOUSE,
+  RANK_STRAIGHT,
+} from "./utils/hand-eval";
+
+describe("Hand Rank Encoding", () => {
+  it("Breaks two pair ties on the kicker", () => {
+    // Board Ad Ah Kc Ks 2d
+    const board = [25, 38, 11, 50, 13];
+    const queenKicker = evaluateHandFromIndices([10, 42, ...board]); // Qc 5s
+    const jackKicker = evaluateHandFromIndices([9, 43, ...board]); // Jc 6s
+
+    expect(queenKicker).to.equal(encodeHandScore(RANK_TWO_PAIR, [12, 12, 11, 11, 10]));
+    expect(compareHands(queenKicker, jackKicker)).to.equal(1);
+  });
+
+  it("Breaks two pair ties on the second pair", () => {
+    // Board Ac Ad 7h 4s 2c: aces and kings beat aces and queens
+    const board = [12, 25, 31, 41, 0];
+    const kings = evaluateHandFromIndices([11, 24, ...board]); // Kc Kd
+    const queens = evaluateHandFromIndices([10, 23, ...board]); // Qc Qd
+
+    expect(compareHands(kings, queens)).to.equal(1);
+  });
+
+  it("Chops when the board plays", () => {
+    // Broadway on board: Tc Jd Qh Ks Ac
+    const board = [8, 22, 36, 50, 12];
+    const first = evaluateHandFromIndices([0, 14, ...board]); // 2c 3d
+    const second = evaluateHandFromIndices([28, 42, ...board]); // 4h 5s
+
+    expect(first).to.equal(encodeHandScore(RANK_STRAIGHT, [12, 11, 10, 9, 8]));
+    expect(compareHands(first, second)).to.equal(0);
+  });
+
+  it("Ranks the wheel below a six-high straight", () => {
+    const board = [13, 27, 41, 22, 10]; // 2d 3h 4s Jd Qc
+    const wheel = evaluateHandFromIndices([12, 29, ...board]); // Ac 5h
+    const sixHigh = evaluateHandFromIndices([4, 29, ...board]); // 6c 5h
+
+    expect(wheel).to.equal(encodeHandScore(RANK_STRAIGHT, [3, 2, 1, 0, 12]));
+    expect(compareHands(sixHigh, wheel)).to.equal(1);
+  });
+
+  it("Compares flushes on all five cards", () => {
+    // Four clubs on board: 2c 3c 4c 6c with 9d
+    const board = [0, 1, 2, 4, 20];
+    const aceEight = evaluateHandFromIndices([12, 6, ...board]); // Ac 8c
+    const aceSeven = evaluateHandFromIndices([12, 5, ...board]); // Ac 7c
+
+    expect(aceEight).to.equal(encodeHandScore(RANK_FLUSH, [12, 6, 4, 2, 1]));
+    expect(compareHands(aceEight, aceSeven)).to.equal(1);
+  });
+
+  it("Uses the best pair in a full house with two sets of trips", () => {
+    // Kc Kd Kh 5c 5d 5h As: kings full of fives
+    const rank = evaluateHandFromIndices([11, 24, 37, 3, 16, 29, 51]);
+
+    expect(rank).to.equal(encodeHandScore(RANK_FULL_HOUSE, [11, 11, 11, 3, 3]));
+  });
+});
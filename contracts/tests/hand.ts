@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  findGlobalConfig,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+import * as crypto from "crypto";
+import { keccak256 } from "js-sha3";
+
+describe("Hand State", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: anchor.web3.PublicKey;
+
+  before("Create a full table", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+  });
+
+  describe("HandWinner", () => {
+    it("Round-trips every winner state through serialization", () => {
+      console.log("🧪 Testing: HandWinner (de)serialization");
+
+      const states = [{ none: {} }, { seat0: {} }, { seat1: {} }, { split: {} }];
+      for (const [index, state] of states.entries()) {
+        const encoded = program.coder.types.encode("HandWinner", state);
+        assert.equal(encoded.length, 1);
+        assert.equal(encoded[0], index);
+        assert.deepEqual(program.coder.types.decode("HandWinner", encoded), state);
+      }
+
+      console.log("   ✅ None/Seat0/Seat1/Split encode as single-byte variants");
+    });
+
+    it("Starts a new hand with no winner", async () => {
+      console.log("🧪 Testing: new hand winner is None");
+
+      hand = await startHand(program, testTable, players[0]);
+      const handAccount = await program.account.hand.fetch(hand);
+
+      assert.deepEqual(handAccount.winner, { none: {} });
+
+      console.log("   ✅ Hand winner decodes as None");
+    });
+  });
+
+  describe("Packed flags", () => {
+    it("Clears every flag when a hand is initialized", async () => {
+      console.log("🧪 Testing: init clears flags");
+
+      const handAccount = await program.account.hand.fetch(hand);
+      assert.equal(handAccount.flags, 0);
+      for (const flag of Object.values(HAND_FLAGS)) {
+        assert.isFalse(hasFlag(handAccount, flag));
+      }
+
+      console.log("   ✅ All flags clear on a fresh hand");
+    });
+
+    it("Toggles each player's seed flag independently", async () => {
+      console.log("🧪 Testing: independent seed flags");
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      const [first, second] = tableAccount.playerOne.equals(players[0].keypair.publicKey)
+        ? [players[0], players[1]]
+        : [players[1], players[0]];
+
+      for (const [player, flag] of [
+        [first, HAND_FLAGS.P1_SEED_COMMITTED],
+        [second, HAND_FLAGS.P2_SEED_COMMITTED],
+      ] as [TestPlayer, number][]) {
+        const before = await program.account.hand.fetch(hand);
+        const seedHash = Buffer.from(keccak256(crypto.randomBytes(32)), "hex");
+
+        await program.methods
+          .commitSeed(Array.from(seedHash))
+          .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+          .signers([player.keypair])
+          .rpc();
+
+        const after = await program.account.hand.fetch(hand);
+        assert.equal(after.flags, before.flags | flag);
+      }
+
+      console.log("   ✅ Seat 0 and seat 1 flags set separate bits");
+    });
+  });
+});
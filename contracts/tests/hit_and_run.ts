@@ -0,0 +1,127 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+} from "./utils/game";
+
+const MIN_HANDS = 2;
+
+describe("Hit and Run", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const leaveTable = (testTable: TestTable, player: TestPlayer) =>
+    program.methods
+      .leaveTable()
+      .accounts({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand: null,
+        playerTokenAccount: player.ata,
+        vault: testTable.vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Play a hand where the first player to act folds; returns the winner */
+  async function foldPreflop(testTable: TestTable): Promise<TestPlayer> {
+    const { hand } = await playToPreflop(program, testTable, players);
+    const folder = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, folder, "fold");
+    return players.find((p) => p !== folder);
+  }
+
+  /** Seat both players at a fresh table with the hit-and-run rule */
+  async function newTable() {
+    const testTable = await createTable(program, provider, { minHandsBeforeLeave: new anchor.BN(MIN_HANDS) });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    return testTable;
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Holds a winning player until the minimum hands are played", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: leave_table while up before the threshold");
+
+    const testTable = await newTable();
+    const winner = await foldPreflop(testTable);
+    await expectError(leaveTable(testTable, winner), "HitAndRunBlocked");
+
+    // The other player folds the second hand too, reaching the threshold
+    const loser = players.find((p) => p !== winner);
+    const { hand } = await playToPreflop(program, testTable, players);
+    if ((await playerToAct(program, testTable, hand, players)) === winner) {
+      await act(program, testTable, hand, winner, "call");
+    }
+    await act(program, testTable, hand, loser, "fold");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.handsPlayed.toNumber(), MIN_HANDS);
+    await leaveTable(testTable, winner);
+
+    console.log("   ✅ Blocked with HitAndRunBlocked, allowed after the threshold");
+  });
+
+  it("Lets a busted player leave at any time", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: leave_table after busting");
+
+    const testTable = await newTable();
+    const { hand } = await playToPreflop(program, testTable, players);
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "call");
+
+    // Equal stacks, so the agreed loser has nothing left
+    for (const player of players) {
+      await program.methods
+        .agreeResult({ seat0: {} } as any)
+        .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+        .signers([player.keypair])
+        .rpc();
+    }
+    await program.methods
+      .claimPot()
+      .accountsPartial({
+        player: players[0].keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([players[0].keypair])
+      .rpc();
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerTwoChips.toNumber(), 0);
+    await leaveTable(testTable, players.find((p) => p.keypair.publicKey.equals(tableAccount.playerTwo)));
+
+    console.log("   ✅ Busted player left before the threshold");
+  });
+});
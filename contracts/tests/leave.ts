@@ -0,0 +1,102 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+const BUY_IN = 500_000000;
+const SMALL_BLIND = 10_000000;
+const BIG_BLIND = 20_000000;
+
+describe("Leave Table", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  const leaveTable = (player: TestPlayer) =>
+    program.methods
+      .leaveTable()
+      .accounts({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand: null,
+        playerTokenAccount: player.ata,
+        vault: testTable.vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  const balance = async (player: TestPlayer) =>
+    Number((await provider.connection.getTokenAccountBalance(player.ata)).value.amount);
+
+  before("Seat one player", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    await joinTable(program, testTable, players[0]);
+  });
+
+  it("Leaves the seated player untouched through join-leave churn", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: repeated join and leave by the second seat");
+
+    const walletBefore = await balance(players[1]);
+    for (let i = 0; i < 3; i++) {
+      await joinTable(program, testTable, players[1]);
+      assert.deepEqual((await program.account.table.fetch(testTable.table)).status, { between: {} });
+      await leaveTable(players[1]);
+    }
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.deepEqual(tableAccount.status, { waiting: {} });
+    assert.isNull(tableAccount.currentHand);
+    assert.equal(tableAccount.handsPlayed.toNumber(), 0);
+    const stacks = [tableAccount.playerOneChips.toNumber(), tableAccount.playerTwoChips.toNumber()];
+    assert.deepEqual(stacks.sort(), [0, BUY_IN]);
+    assert.equal(await balance(players[1]), walletBefore);
+
+    console.log("   ✅ Stack, status and wallet unchanged after 3 round trips");
+  });
+
+  it("Keeps both blinds in the hand when leaving during setup", async function () {
+    this.timeout(60000);
+    console.log("🧪 Testing: leave_table after the blinds are posted");
+
+    await joinTable(program, testTable, players[1]);
+    const hand = await startHand(program, testTable, players[0]);
+
+    for (const player of players) {
+      await expectError(leaveTable(player), "HandInProgress");
+    }
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { seedCommit: {} });
+    assert.deepEqual(handAccount.totalBet.map((b) => b.toNumber()).sort(), [SMALL_BLIND, BIG_BLIND]);
+    assert.equal(
+      tableAccount.playerOneChips.toNumber() + tableAccount.playerTwoChips.toNumber() + handAccount.pot.toNumber(),
+      2 * BUY_IN
+    );
+
+    console.log("   ✅ Correctly rejected with HandInProgress, blinds accounted for");
+  });
+});
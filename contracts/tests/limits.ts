@@ -0,0 +1,85 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  createPlayer,
+  createTable,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+describe("Table Limit", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let outsider: TestPlayer;
+
+  const setMaxTables = (maxTables: number) =>
+    program.methods
+      .setMaxTables(new anchor.BN(maxTables))
+      .accounts({ authority: provider.wallet.publicKey, globalConfig: findGlobalConfig(program) })
+      .rpc();
+
+  before("Create a non-admin", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    outsider = await createPlayer(provider, config.usdcMint);
+  });
+
+  // Other suites share the global config, so always lift the cap again
+  after("Remove the cap", async () => {
+    await setMaxTables(0);
+  });
+
+  it("Only the authority can set the cap", async () => {
+    console.log("🧪 Testing: set_max_tables by a non-admin");
+
+    await expectError(
+      program.methods
+        .setMaxTables(new anchor.BN(1))
+        .accounts({ authority: outsider.keypair.publicKey, globalConfig: findGlobalConfig(program) })
+        .signers([outsider.keypair])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    console.log("   ✅ Correctly rejected with Unauthorized");
+  });
+
+  it("Rejects a new table at the cap and frees a slot on close", async () => {
+    console.log("🧪 Testing: max_tables boundary");
+
+    let config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    const active = config.activeTables.toNumber();
+
+    // Room for exactly one more table
+    await setMaxTables(active + 1);
+    const lastTable = await createTable(program, provider);
+
+    config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    assert.equal(config.activeTables.toNumber(), active + 1);
+    await expectError(createTable(program, provider), "TableLimitReached");
+
+    await program.methods
+      .closeTable()
+      .accountsPartial({
+        authority: provider.wallet.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: lastTable.table,
+        vault: lastTable.vault,
+        creator: provider.wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    assert.equal(config.activeTables.toNumber(), active);
+    await createTable(program, provider);
+
+    console.log("   ✅ Cap enforced, closing a table frees capacity");
+  });
+});
@@ -0,0 +1,82 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { createMint, getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+describe("Table Mints", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let usdcMint: PublicKey;
+  let altMint: PublicKey;
+  let usdcTable: TestTable;
+  let altTable: TestTable;
+  let usdcPlayer: TestPlayer;
+  let altPlayer: TestPlayer;
+
+  before("Create a second mint and one table per mint", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    usdcMint = config.usdcMint;
+    altMint = await createMint(
+      provider.connection,
+      (provider.wallet as anchor.Wallet).payer,
+      provider.wallet.publicKey,
+      null,
+      6
+    );
+
+    usdcPlayer = await createPlayer(provider, usdcMint);
+    altPlayer = await createPlayer(provider, altMint);
+
+    usdcTable = await createTable(program, provider);
+    altTable = await createTable(program, provider, { mint: altMint });
+  });
+
+  it("Records each table's mint and vault mint", async () => {
+    console.log("🧪 Testing: per-table mint");
+
+    const usdcAccount = await program.account.table.fetch(usdcTable.table);
+    const altAccount = await program.account.table.fetch(altTable.table);
+    assert.ok(usdcAccount.mint.equals(usdcMint));
+    assert.ok(altAccount.mint.equals(altMint));
+
+    const altVault = await getAccount(provider.connection, altTable.vault);
+    assert.ok(altVault.mint.equals(altMint));
+
+    console.log("   ✅ Tables and vaults use their own mints");
+  });
+
+  it("Rejects joining with a token account for another mint", async () => {
+    console.log("🧪 Testing: join with the wrong mint");
+
+    await expectError(joinTable(program, altTable, usdcPlayer), "InvalidMint");
+    await expectError(joinTable(program, usdcTable, altPlayer), "InvalidMint");
+
+    console.log("   ✅ Correctly rejected mismatched token accounts");
+  });
+
+  it("Buys in with the table's mint", async () => {
+    console.log("🧪 Testing: join with the table mint");
+
+    const buyIn = 500_000000;
+    await joinTable(program, altTable, altPlayer, new anchor.BN(buyIn));
+
+    const altVault = await getAccount(provider.connection, altTable.vault);
+    assert.equal(Number(altVault.amount), buyIn);
+
+    console.log("   ✅ Buy-in deposited in the alternate mint vault");
+  });
+});
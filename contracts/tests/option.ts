@@ -0,0 +1,114 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  act,
+  seatProfiles,
+} from "./utils/game";
+
+const BIG_BLIND = 20_000000;
+
+describe("Big Blind Option", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const raiseTo = async (testTable: TestTable, hand: PublicKey, player: TestPlayer, amount: number) =>
+    program.methods
+      .raiseTo(new anchor.BN(amount))
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Seat both players at a fresh table and return the hand with both blinds */
+  async function newHand() {
+    const testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+    const smallBlind = await playerToAct(program, testTable, hand, players);
+    const bigBlind = players.find((p) => p !== smallBlind);
+    return { testTable, hand, smallBlind, bigBlind };
+  }
+
+  const stage = async (hand: PublicKey) => Object.keys((await program.account.hand.fetch(hand)).stage)[0];
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Closes preflop when the big blind checks behind a limp", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: SB limps, BB checks");
+
+    const { testTable, hand, smallBlind, bigBlind } = await newHand();
+    await act(program, testTable, hand, smallBlind, "call");
+    assert.equal(await stage(hand), "preflop");
+    assert.equal(await playerToAct(program, testTable, hand, players), bigBlind);
+
+    await act(program, testTable, hand, bigBlind, "check");
+    assert.equal(await stage(hand), "flop");
+
+    console.log("   ✅ Big blind checked the option, flop dealt");
+  });
+
+  it("Lets the big blind raise after a limp", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: SB limps, BB raises its option");
+
+    const { testTable, hand, smallBlind, bigBlind } = await newHand();
+    await act(program, testTable, hand, smallBlind, "call");
+    await raiseTo(testTable, hand, bigBlind, 3 * BIG_BLIND);
+
+    assert.equal(await stage(hand), "preflop");
+    assert.equal(await playerToAct(program, testTable, hand, players), smallBlind);
+    assert.equal((await program.account.hand.fetch(hand)).currentBet.toNumber(), 3 * BIG_BLIND);
+
+    await act(program, testTable, hand, smallBlind, "call");
+    assert.equal(await stage(hand), "flop");
+
+    console.log("   ✅ Option raise reopened the action, call closed it");
+  });
+
+  it("Lets the big blind re-raise a small blind raise", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: SB raises, BB re-raises");
+
+    const { testTable, hand, smallBlind, bigBlind } = await newHand();
+    await raiseTo(testTable, hand, smallBlind, 3 * BIG_BLIND);
+    await raiseTo(testTable, hand, bigBlind, 9 * BIG_BLIND);
+
+    assert.equal(await stage(hand), "preflop");
+    assert.equal(await playerToAct(program, testTable, hand, players), smallBlind);
+
+    await act(program, testTable, hand, smallBlind, "call");
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { flop: {} });
+    assert.equal(handAccount.pot.toNumber(), 18 * BIG_BLIND);
+
+    console.log("   ✅ Re-raise kept preflop open until the small blind called");
+  });
+});
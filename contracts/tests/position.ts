@@ -0,0 +1,94 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TableOptions,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  act,
+} from "./utils/game";
+
+const SMALL_BLIND = 10_000000;
+const BIG_BLIND = 20_000000;
+
+describe("First To Act", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  /**
+   * Limp and check a hand to the flop; returns the button, the blinds each
+   * seat posted and who acts first on each street
+   */
+  async function openingSeats(options: TableOptions) {
+    const testTable = await createTable(program, provider, options);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+    const { button } = await program.account.table.fetch(testTable.table);
+
+    const dealt = await program.account.hand.fetch(hand);
+    const blinds = dealt.totalBet.map((bet) => bet.toNumber());
+    const preflop = dealt.actionOn;
+    const limper = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, limper, "call");
+    await act(program, testTable, hand, players.find((p) => p !== limper), "check");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { flop: {} });
+    return { button, blinds, preflop, flop: handAccount.actionOn };
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Opens the flop with the big blind by default", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: standard heads-up action order");
+
+    const { button, blinds, preflop, flop } = await openingSeats({ firstToActRule: { standard: {} } });
+    assert.deepEqual([blinds[button], blinds[1 - button]], [SMALL_BLIND, BIG_BLIND]);
+    assert.equal(preflop, button);
+    assert.equal(flop, 1 - button);
+
+    console.log("   ✅ Button first preflop, big blind first on the flop");
+  });
+
+  it("Keeps the button first on the flop under buttonFirst", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: buttonFirst action order");
+
+    const { button, preflop, flop } = await openingSeats({ firstToActRule: { buttonFirst: {} } });
+    assert.equal(preflop, button);
+    assert.equal(flop, button);
+
+    console.log("   ✅ Button opened both preflop and the flop");
+  });
+
+  it("Has the button post the big blind and act last after the flop", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: button-is-big-blind heads-up");
+
+    const { button, blinds, preflop, flop } = await openingSeats({ buttonIsBigBlind: true });
+    assert.deepEqual([blinds[button], blinds[1 - button]], [BIG_BLIND, SMALL_BLIND]);
+    assert.equal(preflop, 1 - button);
+    assert.equal(flop, 1 - button);
+
+    console.log("   ✅ Small blind opened preflop and the flop, button acted last");
+  });
+});
@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  act,
+  seatProfiles,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+
+const BUY_IN = 500_000000;
+const BIG_BLIND = 20_000000;
+const POT_CAP = 10 * BIG_BLIND;
+
+describe("Pot Cap", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const raiseTo = async (testTable: TestTable, hand: PublicKey, player: TestPlayer, amount: number) =>
+    program.methods
+      .raiseTo(new anchor.BN(amount))
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Seat both players at a fresh capped table; returns the preflop hand and blinds */
+  async function newHand() {
+    const testTable = await createTable(program, provider, { potCap: new anchor.BN(POT_CAP) });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+    const smallBlind = await playerToAct(program, testTable, hand, players);
+    const bigBlind = players.find((p) => p !== smallBlind);
+    return { testTable, hand, smallBlind, bigBlind };
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Cuts a raise to the cap and closes betting once it is called", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: raise past the pot cap");
+
+    const { testTable, hand, smallBlind, bigBlind } = await newHand();
+    await raiseTo(testTable, hand, smallBlind, 8 * BIG_BLIND);
+
+    let handAccount = await program.account.hand.fetch(hand);
+    assert.equal(handAccount.currentBet.toNumber(), POT_CAP / 2);
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P1_ALL_IN) || hasFlag(handAccount, HAND_FLAGS.P2_ALL_IN));
+
+    await act(program, testTable, hand, bigBlind, "call");
+
+    handAccount = await program.account.hand.fetch(hand);
+    assert.equal(handAccount.pot.toNumber(), POT_CAP);
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P1_ALL_IN));
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.P2_ALL_IN));
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.FAST_SHOWDOWN));
+    assert.deepEqual(handAccount.stage, { flop: {} });
+
+    // Everything past the cap stayed in the stacks
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerOneChips.toNumber(), BUY_IN - POT_CAP / 2);
+    assert.equal(tableAccount.playerTwoChips.toNumber(), BUY_IN - POT_CAP / 2);
+
+    console.log("   ✅ Pot stopped at the cap with both seats capped");
+  });
+
+  it("Leaves betting open below the cap", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: raise and call under the pot cap");
+
+    const { testTable, hand, smallBlind, bigBlind } = await newHand();
+    await raiseTo(testTable, hand, smallBlind, 3 * BIG_BLIND);
+    await act(program, testTable, hand, bigBlind, "call");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.equal(handAccount.pot.toNumber(), 6 * BIG_BLIND);
+    assert.isFalse(hasFlag(handAccount, HAND_FLAGS.P1_ALL_IN));
+    assert.isFalse(hasFlag(handAccount, HAND_FLAGS.P2_ALL_IN));
+    assert.isFalse(hasFlag(handAccount, HAND_FLAGS.FAST_SHOWDOWN));
+    assert.deepEqual(handAccount.stage, { flop: {} });
+
+    console.log("   ✅ Hand moved to the flop with betting still open");
+  });
+});
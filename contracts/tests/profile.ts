@@ -0,0 +1,93 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  findPlayerProfile,
+  seatProfiles,
+} from "./utils/game";
+
+describe("Player Profiles", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  const fetchProfile = (player: TestPlayer) =>
+    program.account.playerProfile.fetchNullable(findPlayerProfile(program, player.keypair.publicKey));
+
+  before("Create a full table", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+  });
+
+  it("Moves net winnings from the folder to the winner", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: profile net winnings across hands");
+
+    assert.isNull(await fetchProfile(players[0]));
+    assert.isNull(await fetchProfile(players[1]));
+
+    const expected = new Map<TestPlayer, { net: number; won: number }>(
+      players.map((p) => [p, { net: 0, won: 0 }])
+    );
+
+    for (let i = 0; i < 2; i++) {
+      const { hand } = await playToPreflop(program, testTable, players);
+      const folder = await playerToAct(program, testTable, hand, players);
+      const winner = players.find((p) => p !== folder);
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      const handAccount = await program.account.hand.fetch(hand);
+      const folderSeat = tableAccount.playerOne.equals(folder.keypair.publicKey) ? 0 : 1;
+      const lost = handAccount.totalBet[folderSeat].toNumber();
+
+      await program.methods
+        .fold()
+        .accountsPartial({
+          player: folder.keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([folder.keypair])
+        .rpc();
+
+      expected.get(folder).net -= lost;
+      expected.get(winner).net += lost;
+      expected.get(winner).won += 1;
+    }
+
+    for (const player of players) {
+      const profile = await fetchProfile(player);
+      assert.ok(profile.player.equals(player.keypair.publicKey));
+      assert.equal(profile.handsPlayed.toNumber(), 2);
+      assert.equal(profile.handsWon.toNumber(), expected.get(player).won);
+      assert.equal(profile.netWinnings.toNumber(), expected.get(player).net);
+    }
+
+    const [one, two] = await Promise.all(players.map(fetchProfile));
+    assert.equal(one.netWinnings.toNumber() + two.netWinnings.toNumber(), 0);
+
+    console.log("   ✅ Winner's net increased and loser's decreased by the same amount");
+  });
+});
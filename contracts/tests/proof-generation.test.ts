@@ -16,7 +16,7 @@ import {
   verifyDeckIntegrity,
 } from "./utils/deck";
 import { generateSalt } from "./utils/crypto";
-import { generateDeckProof, generateRevealProof, generateShowdownProof, verifyProofSize } from "./utils/prover";
+import { generateDeckProof, generateRevealProof, generateShowdownProof, verifyProofSize, handId } from "./utils/prover";
 import { evaluateHandFromIndices, createSevenCardHand } from "./utils/hand-eval";
 
 describe("ZK Proof Generation Tests", () => {
@@ -58,6 +58,7 @@ describe("ZK Proof Generation Tests", () => {
       console.log(`  Player 0 cards: ${card1}, ${card2}`);
 
       const result = await generateDeckProof({
+        handId: handId(0n, 0n),
         deckSeed,
         playerSeat: 0,
         card1,
@@ -84,6 +85,7 @@ describe("ZK Proof Generation Tests", () => {
       console.log(`  Player 1 cards: ${card1}, ${card2}`);
 
       const result = await generateDeckProof({
+        handId: handId(0n, 0n),
         deckSeed,
         playerSeat: 1,
         card1,
@@ -108,6 +110,7 @@ describe("ZK Proof Generation Tests", () => {
       console.log(`  Flop cards: [${flopCards.join(", ")}]`);
 
       const proof = await generateRevealProof({
+        handId: handId(0n, 0n),
         deckSeed,
         cards: Array.from(flopCards),
         numCards: 3,
@@ -129,6 +132,7 @@ describe("ZK Proof Generation Tests", () => {
       console.log(`  Turn card: ${turnCard}`);
 
       const proof = await generateRevealProof({
+        handId: handId(0n, 0n),
         deckSeed,
         cards,
         numCards: 4,
@@ -151,6 +155,7 @@ describe("ZK Proof Generation Tests", () => {
       console.log(`  River card: ${riverCard}`);
 
       const proof = await generateRevealProof({
+        handId: handId(0n, 0n),
         deckSeed,
         cards,
         numCards: 5,
@@ -190,6 +195,7 @@ describe("ZK Proof Generation Tests", () => {
 
       // Use commitments from DECK circuit, not TypeScript computation
       const result = await generateShowdownProof({
+        handId: handId(0n, 0n),
         commitment1: player0Commitments[0],
         commitment2: player0Commitments[1],
         communityCards,
@@ -0,0 +1,426 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import * as crypto from "crypto";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  startHand,
+  runSeedProtocol,
+  commitHoleCards,
+  playerToAct,
+  act,
+  findGlobalConfig,
+  expectError,
+  REVEAL_VERIFIER,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+import { shuffleDeck, getCommunityCards } from "./utils/deck";
+import { generateRevealProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+describe("Proof Buffers", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+
+  const findBuffer = (hand: PublicKey, player: TestPlayer, proofType: ProofType) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proof_buffer"),
+        hand.toBuffer(),
+        player.keypair.publicKey.toBuffer(),
+        Buffer.from([proofType]),
+      ],
+      program.programId
+    )[0];
+
+  const initBuffer = (player: TestPlayer, proofType: ProofType, size = 388, forHand = hand) =>
+    program.methods
+      .initProofBuffer(proofType, size)
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        hand: forHand,
+        proofBuffer: findBuffer(forHand, player, proofType),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  const uploadChunk = (player: TestPlayer, proofBuffer: PublicKey, offset: number, data: Buffer) =>
+    program.methods
+      .uploadProofChunk(offset, data)
+      .accounts({ player: player.keypair.publicKey, proofBuffer })
+      .signers([player.keypair])
+      .rpc();
+
+  before("Create a full table and reach preflop", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+
+    ({ hand } = await playToPreflop(program, testTable, players));
+  });
+
+  describe("Stage validation", () => {
+    it("Rejects a Reveal buffer during preflop", async () => {
+      console.log("🧪 Testing: Reveal buffer before the flop");
+
+      await expectError(initBuffer(players[0], ProofType.Reveal), "InvalidProofType");
+
+      console.log("   ✅ Correctly rejected Reveal buffer in Preflop");
+    });
+
+    it("Rejects a Showdown buffer before showdown", async () => {
+      console.log("🧪 Testing: Showdown buffer before showdown");
+
+      await expectError(initBuffer(players[0], ProofType.Showdown), "InvalidProofType");
+
+      console.log("   ✅ Correctly rejected Showdown buffer in Preflop");
+    });
+  });
+
+  describe("Hand binding", () => {
+    it("Rejects a table account passed as the hand", async () => {
+      console.log("🧪 Testing: proof buffer bound to a non-Hand account");
+
+      await expectError(initBuffer(players[0], ProofType.Deck, 388, testTable.table), "AccountDiscriminatorMismatch");
+
+      console.log("   ✅ Correctly rejected with AccountDiscriminatorMismatch");
+    });
+
+    it("Rejects an arbitrary key passed as the hand", async () => {
+      console.log("🧪 Testing: proof buffer bound to an uninitialized key");
+
+      const fakeHand = new PublicKey(crypto.randomBytes(32));
+      await expectError(initBuffer(players[0], ProofType.Deck, 388, fakeHand), "AccountNotInitialized");
+
+      console.log("   ✅ Correctly rejected with AccountNotInitialized");
+    });
+  });
+
+  describe("Resizing", () => {
+    let cardHand: PublicKey;
+    let proofBuffer: PublicKey;
+    const proof = crypto.randomBytes(388);
+
+    before("Reach card commit on a new table", async function () {
+      this.timeout(120000);
+
+      const table = await createTable(program, provider);
+      for (const player of players) {
+        await joinTable(program, table, player);
+      }
+      cardHand = await startHand(program, table, players[0]);
+      await runSeedProtocol(program, table, cardHand, players);
+
+      await initBuffer(players[0], ProofType.Deck, 200, cardHand);
+      proofBuffer = findBuffer(cardHand, players[0], ProofType.Deck);
+      await uploadChunk(players[0], proofBuffer, 0, proof.subarray(0, 150));
+    });
+
+    it("Rejects shrinking below the uploaded bytes", async () => {
+      console.log("🧪 Testing: resize below uploaded");
+
+      await expectError(
+        program.methods
+          .resizeProofBuffer(100)
+          .accountsPartial({
+            player: players[0].keypair.publicKey,
+            proofBuffer,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([players[0].keypair])
+          .rpc(),
+        "InvalidBufferSize"
+      );
+
+      console.log("   ✅ Correctly rejected shrinking an in-progress buffer");
+    });
+
+    it("Grows a 200 byte buffer to 388 and completes the upload", async () => {
+      console.log("🧪 Testing: grow buffer with realloc");
+
+      await program.methods
+        .resizeProofBuffer(388)
+        .accountsPartial({
+          player: players[0].keypair.publicKey,
+          proofBuffer,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([players[0].keypair])
+        .rpc();
+
+      let buffer = await program.account.proofBuffer.fetch(proofBuffer);
+      assert.equal(buffer.size, 388);
+      assert.isFalse(buffer.complete);
+
+      await uploadChunk(players[0], proofBuffer, 150, proof.subarray(150, 388));
+
+      buffer = await program.account.proofBuffer.fetch(proofBuffer);
+      assert.isTrue(buffer.complete);
+      assert.deepEqual(Buffer.from(buffer.data), proof);
+
+      console.log("   ✅ Resized buffer accepted the final chunk");
+    });
+  });
+
+  describe("Size limits", () => {
+    let cardHand: PublicKey;
+
+    before("Reach card commit on a new table", async function () {
+      this.timeout(120000);
+
+      const table = await createTable(program, provider);
+      for (const player of players) {
+        await joinTable(program, table, player);
+      }
+      cardHand = await startHand(program, table, players[0]);
+      await runSeedProtocol(program, table, cardHand, players);
+    });
+
+    it("Rejects a zero-size buffer", async () => {
+      console.log("🧪 Testing: zero proof size");
+
+      await expectError(initBuffer(players[0], ProofType.Deck, 0, cardHand), "SizeZero");
+
+      console.log("   ✅ Correctly rejected empty buffer");
+    });
+
+    it("Rejects a buffer above the maximum size", async () => {
+      console.log("🧪 Testing: proof size over maximum");
+
+      await expectError(initBuffer(players[0], ProofType.Deck, 2049, cardHand), "SizeTooLarge");
+
+      console.log("   ✅ Correctly rejected 2049 byte buffer");
+    });
+
+    it("Accepts a buffer just under the maximum size", async () => {
+      console.log("🧪 Testing: proof size at maximum - 1");
+
+      await initBuffer(players[0], ProofType.Deck, 2047, cardHand);
+
+      const buffer = await program.account.proofBuffer.fetch(findBuffer(cardHand, players[0], ProofType.Deck));
+      assert.equal(buffer.size, 2047);
+
+      console.log("   ✅ 2047 byte buffer created");
+    });
+
+    it("Rejects empty, oversized and out-of-range chunks", async () => {
+      console.log("🧪 Testing: upload_proof_chunk bounds");
+
+      const proofBuffer = findBuffer(cardHand, players[0], ProofType.Deck);
+      await expectError(uploadChunk(players[0], proofBuffer, 0, Buffer.alloc(0)), "EmptyChunk");
+      await expectError(uploadChunk(players[0], proofBuffer, 0, crypto.randomBytes(901)), "ChunkTooLarge");
+      await expectError(uploadChunk(players[0], proofBuffer, 2047, crypto.randomBytes(1)), "OffsetOutOfRange");
+
+      // A full-size chunk just inside the buffer is still accepted
+      await uploadChunk(players[0], proofBuffer, 2047 - 900, crypto.randomBytes(900));
+      const buffer = await program.account.proofBuffer.fetch(proofBuffer);
+      assert.equal(buffer.uploaded, 2047);
+
+      console.log("   ✅ Empty, 901 byte and past-the-end chunks rejected");
+    });
+  });
+
+  describe("Open buffer limit", () => {
+    let limitTable: TestTable;
+    let limitHand: PublicKey;
+    let seat: number;
+
+    const openBuffers = async () => {
+      const handAccount = await program.account.hand.fetch(limitHand);
+      return seat === 0 ? handAccount.p1OpenBuffers : handAccount.p2OpenBuffers;
+    };
+
+    const closeBuffer = (proofType: ProofType) =>
+      program.methods
+        .closeProofBuffer()
+        .accounts({
+          player: players[0].keypair.publicKey,
+          proofBuffer: findBuffer(limitHand, players[0], proofType),
+          hand: limitHand,
+        })
+        .signers([players[0].keypair])
+        .rpc();
+
+    before("Leave Deck and Deal buffers open and reach the flop", async function () {
+      this.timeout(300000);
+
+      limitTable = await createTable(program, provider);
+      for (const player of players) {
+        await joinTable(program, limitTable, player);
+      }
+      limitHand = await startHand(program, limitTable, players[0]);
+      await runSeedProtocol(program, limitTable, limitHand, players);
+
+      // Commit first, then open two more buffers the hand will never consume
+      await commitHoleCards(program, limitTable, limitHand, players[0]);
+      await initBuffer(players[0], ProofType.Deck, 388, limitHand);
+      await initBuffer(players[0], ProofType.Deal, 388, limitHand);
+      await commitHoleCards(program, limitTable, limitHand, players[1]);
+
+      const limper = await playerToAct(program, limitTable, limitHand, players);
+      await act(program, limitTable, limitHand, limper, "call");
+      await act(program, limitTable, limitHand, players.find((p) => p !== limper), "check");
+
+      const tableAccount = await program.account.table.fetch(limitTable.table);
+      seat = tableAccount.playerOne.equals(players[0].keypair.publicKey) ? 0 : 1;
+      assert.equal(await openBuffers(), 2);
+    });
+
+    it("Rejects a fourth open buffer", async () => {
+      console.log("🧪 Testing: fourth proof buffer in one hand");
+
+      await initBuffer(players[0], ProofType.Reveal, 388, limitHand);
+      assert.equal(await openBuffers(), 3);
+
+      await expectError(initBuffer(players[0], ProofType.Showdown, 388, limitHand), "TooManyBuffers");
+
+      console.log("   ✅ Correctly rejected with TooManyBuffers");
+    });
+
+    it("Frees a slot when a buffer is closed", async () => {
+      console.log("🧪 Testing: close_proof_buffer releases a slot");
+
+      await closeBuffer(ProofType.Reveal);
+      assert.equal(await openBuffers(), 2);
+
+      await initBuffer(players[0], ProofType.Reveal, 388, limitHand);
+      assert.equal(await openBuffers(), 3);
+
+      console.log("   ✅ Closed buffer's slot reused");
+    });
+  });
+
+  describe("Reset after a failed verification", () => {
+    let resetTable: TestTable;
+    let resetHand: PublicKey;
+    let proofBuffer: PublicKey;
+    let flop: number[];
+    let proof: Buffer;
+
+    const revealFlop = () =>
+      program.methods
+        .revealFlop(flop)
+        .accountsPartial({
+          player: players[0].keypair.publicKey,
+          globalConfig: findGlobalConfig(program),
+          table: resetTable.table,
+          hand: resetHand,
+          proofBuffer,
+          verifierProgram: REVEAL_VERIFIER,
+        })
+        .signers([players[0].keypair])
+        .rpc();
+
+    before("Limp to the flop and upload a tampered reveal proof", async function () {
+      this.timeout(300000);
+
+      resetTable = await createTable(program, provider);
+      for (const player of players) {
+        await joinTable(program, resetTable, player);
+      }
+      ({ hand: resetHand } = await playToPreflop(program, resetTable, players));
+      const limper = await playerToAct(program, resetTable, resetHand, players);
+      await act(program, resetTable, resetHand, limper, "call");
+      await act(program, resetTable, resetHand, players.find((p) => p !== limper), "check");
+
+      const handAccount = await program.account.hand.fetch(resetHand);
+      const deckSeed = Buffer.from(handAccount.deckSeed);
+      const shuffledDeck = shuffleDeck(deckSeed);
+      ({ flop } = getCommunityCards(shuffledDeck));
+      proof = await generateRevealProof({
+        handId: handId(BigInt(resetTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+        deckSeed,
+        cards: flop,
+        numCards: 3,
+        shuffledDeck,
+      });
+
+      const tampered = Buffer.from(proof);
+      tampered[0] ^= 0xff;
+      proofBuffer = await uploadProofToBuffer(program, tampered, resetHand, players[0].keypair, ProofType.Reveal);
+    });
+
+    it("Keeps the buffer intact when the reveal fails", async function () {
+      this.timeout(60000);
+      console.log("🧪 Testing: reveal_flop with a tampered proof");
+
+      await expectError(revealFlop(), "ProofVerificationFailed");
+
+      const buffer = await program.account.proofBuffer.fetch(proofBuffer);
+      assert.isTrue(buffer.complete);
+      assert.equal(buffer.uploaded, proof.length);
+      assert.notDeepEqual(Buffer.from(buffer.data), proof);
+
+      console.log("   ✅ Buffer still open and complete after the failed reveal");
+    });
+
+    it("Clears the buffer and accepts a corrected proof", async function () {
+      this.timeout(60000);
+      console.log("🧪 Testing: reset_proof_buffer and retry");
+
+      await program.methods
+        .resetProofBuffer()
+        .accounts({ player: players[0].keypair.publicKey, proofBuffer })
+        .signers([players[0].keypair])
+        .rpc();
+
+      const buffer = await program.account.proofBuffer.fetch(proofBuffer);
+      assert.equal(buffer.size, proof.length);
+      assert.equal(buffer.uploaded, 0);
+      assert.isFalse(buffer.complete);
+
+      for (let offset = 0; offset < proof.length; offset += 900) {
+        await uploadChunk(players[0], proofBuffer, offset, proof.subarray(offset, offset + 900));
+      }
+      await revealFlop();
+
+      const handAccount = await program.account.hand.fetch(resetHand);
+      assert.deepEqual(Array.from(handAccount.flop), flop);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.FLOP_REVEALED));
+      assert.isNull(await program.account.proofBuffer.fetchNullable(proofBuffer));
+
+      console.log("   ✅ Corrected proof verified from the reset buffer");
+    });
+
+    it("Rejects a reset by another player", async () => {
+      console.log("🧪 Testing: reset_proof_buffer on someone else's buffer");
+
+      await initBuffer(players[0], ProofType.Reveal, 388, resetHand);
+      await expectError(
+        program.methods
+          .resetProofBuffer()
+          .accounts({
+            player: players[1].keypair.publicKey,
+            proofBuffer: findBuffer(resetHand, players[0], ProofType.Reveal),
+          })
+          .signers([players[1].keypair])
+          .rpc(),
+        "Unauthorized"
+      );
+
+      console.log("   ✅ Correctly rejected with Unauthorized");
+    });
+  });
+});
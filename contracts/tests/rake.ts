@@ -0,0 +1,184 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  findTableStats,
+  expectError,
+  act,
+  seatProfiles,
+  REVEAL_VERIFIER,
+} from "./utils/game";
+import { shuffleDeck, getCommunityCards } from "./utils/deck";
+import { generateRevealProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+const BUY_IN = 500_000000;
+// 5% of the 1000 chip pot would be 50, capped at 20
+const RAKE_BPS = 500;
+const RAKE_CAP = 20_000000;
+
+describe("Rake", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  const chipsInPlay = async () => {
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    return tableAccount.playerOneChips.toNumber() + tableAccount.playerTwoChips.toNumber();
+  };
+
+  before("Seat two players at a raked table", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider, { rakeBps: RAKE_BPS, rakeCap: new anchor.BN(RAKE_CAP) });
+    for (const player of players) {
+      await joinTable(program, testTable, player, new anchor.BN(BUY_IN));
+    }
+  });
+
+  it("Rejects a rake above the maximum", async () => {
+    console.log("🧪 Testing: reject rake above 10%");
+
+    await expectError(createTable(program, provider, { rakeBps: 1001 }), "InvalidTableConfig");
+
+    console.log("   ✅ Correctly rejected with InvalidTableConfig");
+  });
+
+  it("Takes no rake from a pot that ends before the flop", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: no flop, no drop");
+
+    const { hand } = await playToPreflop(program, testTable, players);
+    const folder = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, folder, "fold");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const stats = await program.account.tableStats.fetch(findTableStats(program, testTable.table));
+    assert.equal(tableAccount.rakeOwed.toNumber(), 0);
+    assert.equal(stats.rakeCollected.toNumber(), 0);
+    assert.equal(await chipsInPlay(), 2 * BUY_IN);
+
+    console.log("   ✅ Preflop fold paid out in full");
+  });
+
+  it("Rakes a pot that reaches showdown, up to the cap", async function () {
+    this.timeout(600000);
+    console.log("🧪 Testing: rake on a showdown pot");
+
+    const { hand } = await playToPreflop(program, testTable, players);
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "call");
+
+    // Run out the board so the flop is revealed
+    const handAccount = await program.account.hand.fetch(hand);
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const shuffledDeck = shuffleDeck(deckSeed);
+    const { flop, turn, river } = getCommunityCards(shuffledDeck);
+    const board: [number, number, number, number, number] = [...flop, turn, river];
+    const proof = await generateRevealProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+      deckSeed,
+      cards: board,
+      numCards: 5,
+      shuffledDeck,
+    });
+    await program.methods
+      .revealAllCommunity(board)
+      .accountsPartial({
+        player: shover.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer: await uploadProofToBuffer(program, proof, hand, shover.keypair, ProofType.Reveal),
+        verifierProgram: REVEAL_VERIFIER,
+      })
+      .signers([shover.keypair])
+      .rpc();
+
+    for (const player of players) {
+      await program.methods
+        .agreeResult({ split: {} } as any)
+        .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+        .signers([player.keypair])
+        .rpc();
+    }
+    await program.methods
+      .claimPot()
+      .accountsPartial({
+        player: players[0].keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([players[0].keypair])
+      .rpc();
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const stats = await program.account.tableStats.fetch(findTableStats(program, testTable.table));
+    assert.equal(tableAccount.rakeOwed.toNumber(), RAKE_CAP);
+    assert.equal(stats.rakeCollected.toNumber(), RAKE_CAP);
+    assert.equal(await chipsInPlay(), 2 * BUY_IN - RAKE_CAP);
+
+    console.log("   ✅ Showdown pot raked at the cap");
+  });
+
+  it("Lets only the authority collect the rake", async () => {
+    console.log("🧪 Testing: collect_rake");
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    const destination = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        (provider.wallet as anchor.Wallet).payer,
+        config.usdcMint,
+        provider.wallet.publicKey
+      )
+    ).address;
+    const collect = (authority: PublicKey) =>
+      program.methods.collectRake().accounts({
+        authority,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        destination,
+        vault: testTable.vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      });
+
+    await expectError(
+      collect(players[0].keypair.publicKey).signers([players[0].keypair]).rpc(),
+      "Unauthorized"
+    );
+
+    const before = Number((await provider.connection.getTokenAccountBalance(destination)).value.amount);
+    await collect(provider.wallet.publicKey).rpc();
+    const after = Number((await provider.connection.getTokenAccountBalance(destination)).value.amount);
+
+    assert.equal(after - before, RAKE_CAP);
+    assert.equal((await program.account.table.fetch(testTable.table)).rakeOwed.toNumber(), 0);
+    const vault = await provider.connection.getTokenAccountBalance(testTable.vault);
+    assert.equal(Number(vault.value.amount), await chipsInPlay());
+
+    console.log("   ✅ Rake moved to the authority, vault matches stacks");
+  });
+});
@@ -0,0 +1,113 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  findGlobalConfig,
+  findHand,
+  expectError,
+} from "./utils/game";
+
+describe("Auto-continue", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  const readyUp = (player: TestPlayer, hand: anchor.web3.PublicKey | null) =>
+    program.methods
+      .readyUp()
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        coPayer: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  before("Create a full auto-continue table", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider, { autoContinue: true });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+  });
+
+  it("Blocks start_hand until both players are ready", async () => {
+    console.log("🧪 Testing: start_hand gated on readiness");
+
+    await expectError(startHand(program, testTable, players[0]), "PlayersNotReady");
+
+    console.log("   ✅ start_hand rejected before ready_up");
+  });
+
+  it("Starts the next hand on the second ready_up", async () => {
+    console.log("🧪 Testing: ready → ready → hand start");
+
+    await readyUp(players[0], null);
+
+    let tableAccount = await program.account.table.fetch(testTable.table);
+    const seat = tableAccount.playerOne.equals(players[0].keypair.publicKey) ? 0 : 1;
+    assert.isTrue(tableAccount.ready[seat]);
+    assert.isFalse(tableAccount.ready[1 - seat]);
+    assert.deepEqual(tableAccount.status, { between: {} });
+
+    const hand = findHand(program, testTable.table, tableAccount.handsPlayed);
+    await readyUp(players[1], hand);
+
+    tableAccount = await program.account.table.fetch(testTable.table);
+    assert.deepEqual(tableAccount.status, { playing: {} });
+    assert.ok(tableAccount.currentHand.equals(hand));
+    assert.deepEqual(tableAccount.ready, [false, false]);
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.equal(handAccount.pot.toNumber(), 30_000000);
+
+    console.log("   ✅ Hand initialized once both seats were ready");
+  });
+
+  it("Rejects ready_up on a table without auto-continue", async () => {
+    console.log("🧪 Testing: ready_up requires auto-continue");
+
+    const manual = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, manual, player);
+    }
+
+    await expectError(
+      program.methods
+        .readyUp()
+        .accountsPartial({
+          player: players[0].keypair.publicKey,
+          globalConfig: findGlobalConfig(program),
+          table: manual.table,
+          hand: null,
+          coPayer: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([players[0].keypair])
+        .rpc(),
+      "AutoContinueDisabled"
+    );
+
+    console.log("   ✅ Correctly rejected ready_up on a manual table");
+  });
+});
@@ -0,0 +1,198 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  startHand,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+} from "./utils/game";
+
+const BIG_BLIND = 20_000000;
+
+describe("Busted Players", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let shortStack: TestPlayer;
+  let bigStack: TestPlayer;
+  let testTable: TestTable;
+  /** Blinds each seat posted in the last hand played */
+  let lastBlinds: number[];
+
+  /**
+   * Short stack shoves, big stack only calls, then both agree `loser` lost
+   * and the pot is claimed. Returns the blinds each seat posted.
+   */
+  async function playAllIn(loser: TestPlayer): Promise<number[]> {
+    const players = [shortStack, bigStack];
+    const { hand } = await playToPreflop(program, testTable, players);
+    const blinds = (await program.account.hand.fetch(hand)).totalBet.map((bet) => bet.toNumber());
+
+    for (;;) {
+      const handAccount = await program.account.hand.fetch(hand);
+      if (!("preflop" in handAccount.stage)) break;
+      const actor = await playerToAct(program, testTable, hand, players);
+      await act(program, testTable, hand, actor, actor === shortStack ? "allIn" : "call");
+    }
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const loserSeat = tableAccount.playerOne.equals(loser.keypair.publicKey) ? 0 : 1;
+    const result = loserSeat === 0 ? { seat1: {} } : { seat0: {} };
+    for (const player of players) {
+      await program.methods
+        .agreeResult(result as any)
+        .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+        .signers([player.keypair])
+        .rpc();
+    }
+
+    await program.methods
+      .claimPot()
+      .accountsPartial({
+        player: shortStack.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([shortStack.keypair])
+      .rpc();
+
+    return blinds;
+  }
+
+  before("Seat a 10 BB stack against an 11 BB stack", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    shortStack = await createPlayer(provider, config.usdcMint);
+    bigStack = await createPlayer(provider, config.usdcMint);
+
+    testTable = await createTable(program, provider);
+    await joinTable(program, testTable, shortStack, new anchor.BN(10 * BIG_BLIND));
+    await joinTable(program, testTable, bigStack, new anchor.BN(11 * BIG_BLIND));
+  });
+
+  it("Keeps a player left with exactly the big blind in the game", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: stack of exactly one big blind");
+
+    await playAllIn(bigStack);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const seat = tableAccount.playerOne.equals(bigStack.keypair.publicKey) ? 0 : 1;
+    const chips = seat === 0 ? tableAccount.playerOneChips : tableAccount.playerTwoChips;
+
+    assert.equal(chips.toNumber(), BIG_BLIND);
+    assert.deepEqual(tableAccount.sittingOut, [false, false]);
+    assert.deepEqual(tableAccount.status, { between: {} });
+
+    console.log("   ✅ One big blind is enough to keep playing");
+  });
+
+  it("Sits out a player who busts to zero until they add chips", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: bust sits the player out");
+
+    // The big stack is now the short one and shoves its last big blind
+    [shortStack, bigStack] = [bigStack, shortStack];
+    await playAllIn(shortStack);
+
+    let tableAccount = await program.account.table.fetch(testTable.table);
+    const seat = tableAccount.playerOne.equals(shortStack.keypair.publicKey) ? 0 : 1;
+    const chips = seat === 0 ? tableAccount.playerOneChips : tableAccount.playerTwoChips;
+
+    assert.equal(chips.toNumber(), 0);
+    assert.isTrue(tableAccount.sittingOut[seat]);
+    assert.deepEqual(tableAccount.status, { waiting: {} });
+    await expectError(startHand(program, testTable, bigStack), "InvalidStage");
+
+    await program.methods
+      .addChips(new anchor.BN(10 * BIG_BLIND))
+      .accounts({
+        player: shortStack.keypair.publicKey,
+        table: testTable.table,
+        playerTokenAccount: shortStack.ata,
+        vault: testTable.vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([shortStack.keypair])
+      .rpc();
+
+    tableAccount = await program.account.table.fetch(testTable.table);
+    assert.deepEqual(tableAccount.sittingOut, [false, false]);
+    assert.deepEqual(tableAccount.status, { between: {} });
+    // Dead-blind rule: the returning player owes the big blind
+    assert.equal(tableAccount.button, 1 - seat);
+
+    console.log("   ✅ Busted player sat out, back in after a rebuy");
+  });
+
+  it("Posts the big blind for the player returning from sit-out", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: dead blind after sitting out");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const seat = tableAccount.playerOne.equals(shortStack.keypair.publicKey) ? 0 : 1;
+
+    // The returning player shoves from the big blind and busts again
+    lastBlinds = await playAllIn(shortStack);
+    assert.equal(lastBlinds[seat], BIG_BLIND);
+    assert.equal(lastBlinds[1 - seat], BIG_BLIND / 2);
+
+    console.log("   ✅ Returning player posted the big blind");
+  });
+
+  it("Seats a newcomer cleanly after a busted player leaves", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: re-entry after a bust");
+
+    await program.methods
+      .leaveTable()
+      .accounts({
+        player: shortStack.keypair.publicKey,
+        table: testTable.table,
+        hand: null,
+        playerTokenAccount: shortStack.ata,
+        vault: testTable.vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([shortStack.keypair])
+      .rpc();
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    const newcomer = await createPlayer(provider, config.usdcMint);
+    await joinTable(program, testTable, newcomer, new anchor.BN(10 * BIG_BLIND));
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const stayingSeat = tableAccount.playerOne.equals(bigStack.keypair.publicKey) ? 0 : 1;
+    assert.deepEqual(tableAccount.status, { between: {} });
+    assert.deepEqual(tableAccount.sittingOut, [false, false]);
+    assert.deepEqual(tableAccount.ready, [false, false]);
+    assert.isNull(tableAccount.currentHand);
+    // The newcomer takes the vacated seat's button from the last rotation
+    assert.equal(tableAccount.button, 1 - stayingSeat);
+
+    // The player who stayed posted the small blind last hand, so now the big
+    const hand = await startHand(program, testTable, bigStack);
+    const handAccount = await program.account.hand.fetch(hand);
+    const bets = handAccount.totalBet.map((bet) => bet.toNumber());
+    assert.equal(lastBlinds[stayingSeat], BIG_BLIND / 2);
+    assert.equal(bets[stayingSeat], BIG_BLIND);
+    assert.equal(bets[1 - stayingSeat], BIG_BLIND / 2);
+
+    console.log("   ✅ Newcomer seated on the button, no blind posted twice in a row");
+  });
+});
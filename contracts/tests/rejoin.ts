@@ -0,0 +1,93 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  findGlobalConfig,
+  expectError,
+  sleep,
+} from "./utils/game";
+
+const COOLDOWN = 4;
+
+describe("Rejoin Cooldown", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  const leaveTable = (player: TestPlayer) =>
+    program.methods
+      .leaveTable()
+      .accounts({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand: null,
+        playerTokenAccount: player.ata,
+        vault: testTable.vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  before("Seat both players at a table with a rejoin cooldown", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider, { rejoinCooldown: new anchor.BN(COOLDOWN) });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+  });
+
+  it("Rejects a rejoin inside the cooldown", async () => {
+    console.log("🧪 Testing: join_table right after leave_table");
+
+    await leaveTable(players[1]);
+    await expectError(joinTable(program, testTable, players[1]), "RejoinCooldown");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.isTrue(tableAccount.leftBy.some((key) => key.equals(players[1].keypair.publicKey)));
+
+    console.log("   ✅ Correctly rejected with RejoinCooldown");
+  });
+
+  it("Accepts the rejoin once the cooldown has passed", async function () {
+    this.timeout(30000);
+    console.log("🧪 Testing: join_table after the cooldown");
+
+    await sleep((COOLDOWN + 1) * 1000);
+    await joinTable(program, testTable, players[1]);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.deepEqual(tableAccount.status, { between: {} });
+
+    console.log("   ✅ Player seated again after the cooldown");
+  });
+
+  it("Lets a different player take the seat", async () => {
+    console.log("🧪 Testing: cooldown only applies to the player who left");
+
+    await leaveTable(players[0]);
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    const newcomer = await createPlayer(provider, config.usdcMint);
+    await joinTable(program, testTable, newcomer);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.deepEqual(tableAccount.status, { between: {} });
+
+    console.log("   ✅ Newcomer took the vacated seat without waiting");
+  });
+});
@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  runSeedProtocol,
+  commitHoleCards,
+  findGlobalConfig,
+  findHand,
+  act,
+} from "./utils/game";
+
+describe("Rematch", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+  const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  /** Start the next hand with `rematch`; returns the hand and its RematchStarted event */
+  async function rematch(player: TestPlayer) {
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const hand = findHand(program, testTable.table, tableAccount.handsPlayed);
+
+    const signature = await program.methods
+      .rematch()
+      .accounts({
+        player: player.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        coPayer: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player.keypair])
+      .rpc({ commitment: "confirmed" });
+
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = [...parser.parseLogs(tx.meta.logMessages)].filter((event) => event.name === "rematchStarted");
+    assert.lengthOf(events, 1);
+    return { hand, event: events[0].data };
+  }
+
+  /** Deal the hand and have the first player to act fold */
+  async function foldHand(hand: PublicKey): Promise<void> {
+    await runSeedProtocol(program, testTable, hand, players);
+    for (const player of players) {
+      await commitHoleCards(program, testTable, hand, player);
+    }
+    await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "fold");
+  }
+
+  before("Seat two players and play a first hand", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+
+    const { hand } = await playToPreflop(program, testTable, players);
+    await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "fold");
+  });
+
+  it("Alternates the button across consecutive rematches", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: button rotation over rematches");
+
+    let previousButton = (await program.account.table.fetch(testTable.table)).button;
+    for (let i = 0; i < 3; i++) {
+      const { hand, event } = await rematch(players[i % 2]);
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      const handAccount = await program.account.hand.fetch(hand);
+      assert.isTrue(event.table.equals(testTable.table));
+      assert.equal(event.tableId.toString(), testTable.tableId.toString());
+      assert.equal(event.handNumber.toString(), handAccount.handNumber.toString());
+      assert.equal(event.button, tableAccount.button);
+      if (i > 0) {
+        assert.notEqual(event.button, previousButton);
+      }
+      assert.deepEqual(tableAccount.status, { playing: {} });
+
+      previousButton = event.button;
+      await foldHand(hand);
+    }
+
+    console.log("   ✅ Button swapped seats on every rematch");
+  });
+});
@@ -0,0 +1,152 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  startHand,
+  runSeedProtocol,
+  commitHoleCards,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+} from "./utils/game";
+
+describe("Hand Rent", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+
+  const closeHand = (rentPayer: PublicKey, coPayer: PublicKey | null = null) =>
+    program.methods
+      .closeHand()
+      .accountsPartial({ table: testTable.table, hand, rentPayer, coPayer })
+      .rpc();
+
+  before("Start a hand", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    ({ hand } = await playToPreflop(program, testTable, players));
+  });
+
+  it("Records the player who started the hand as its rent payer", async () => {
+    console.log("🧪 Testing: hand rent payer");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.isTrue(handAccount.rentPayer.equals(players[0].keypair.publicKey));
+
+    console.log("   ✅ rent_payer is the hand starter");
+  });
+
+  it("Cannot close a hand that is still being played", async () => {
+    console.log("🧪 Testing: close_hand mid-hand");
+
+    await expectError(closeHand(players[0].keypair.publicKey), "HandNotComplete");
+
+    console.log("   ✅ Correctly rejected with HandNotComplete");
+  });
+
+  it("Refunds the rent to the player who started the hand", async () => {
+    console.log("🧪 Testing: close_hand after a fold");
+
+    await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "fold");
+
+    // Only the account that paid for the hand can receive its rent
+    await expectError(closeHand(players[1].keypair.publicKey), "ConstraintAddress");
+
+    const starter = players[0].keypair.publicKey;
+    const rent = await provider.connection.getBalance(hand);
+    const before = await provider.connection.getBalance(starter);
+
+    await closeHand(starter);
+
+    const after = await provider.connection.getBalance(starter);
+    assert.equal(after - before, rent);
+    assert.isNull(await provider.connection.getAccountInfo(hand));
+
+    console.log("   ✅ Hand closed, rent returned to the starter");
+  });
+
+  describe("Split rent", () => {
+    const balances = () => Promise.all(players.map((p) => provider.connection.getBalance(p.keypair.publicKey)));
+
+    before("Seat both players at a split-rent table", async function () {
+      this.timeout(60000);
+
+      testTable = await createTable(program, provider, { splitHandRent: true });
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+    });
+
+    it("Rejects a hand started without the other player co-signing", async () => {
+      console.log("🧪 Testing: single-signer start_hand on a split-rent table");
+
+      await expectError(startHand(program, testTable, players[0]), "CoPayerRequired");
+
+      console.log("   ✅ Correctly rejected with CoPayerRequired");
+    });
+
+    it("Charges both players half the rent", async function () {
+      this.timeout(120000);
+      console.log("🧪 Testing: start_hand with a co-payer");
+
+      const before = await balances();
+      hand = await startHand(program, testTable, players[0], players[1]);
+      const after = await balances();
+
+      const rent = await provider.connection.getBalance(hand);
+      assert.equal(before[0] - after[0], rent / 2);
+      assert.equal(before[1] - after[1], rent / 2);
+
+      console.log(`   ✅ Each player paid ${rent / 2} lamports`);
+    });
+
+    it("Refunds half the rent to each player", async function () {
+      this.timeout(120000);
+      console.log("🧪 Testing: close_hand on a split-rent table");
+
+      await runSeedProtocol(program, testTable, hand, players);
+      for (const player of players) {
+        await commitHoleCards(program, testTable, hand, player);
+      }
+      await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "fold");
+
+      const starter = players[0].keypair.publicKey;
+      await expectError(closeHand(starter), "CoPayerRequired");
+
+      const rent = await provider.connection.getBalance(hand);
+      const before = await balances();
+      await closeHand(starter, players[1].keypair.publicKey);
+      const after = await balances();
+
+      assert.equal(after[0] - before[0], rent / 2);
+      assert.equal(after[1] - before[1], rent / 2);
+      assert.isNull(await provider.connection.getAccountInfo(hand));
+
+      console.log("   ✅ Hand closed, rent returned to both players");
+    });
+  });
+});
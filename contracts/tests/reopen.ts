@@ -0,0 +1,133 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  act,
+  seatProfiles,
+  expectError,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+
+const SHORT_BUY_IN = 300_000000;
+const DEEP_BUY_IN = 500_000000;
+const BIG_BLIND = 20_000000;
+
+describe("All-In Reopening", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const raise = async (testTable: TestTable, hand: PublicKey, player: TestPlayer, amount: anchor.BN) =>
+    program.methods
+      .raiseTo(amount)
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /**
+   * The deep stack raises preflop to `raiseTo` (after a limp if the short
+   * stack is first to act) and the short stack shoves its 300 over it.
+   * Returns the hand, its account after the shove and the deep stack's seat.
+   */
+  async function shoveOverRaise(raiseTo: number) {
+    const [shortStack, deepStack] = players;
+    const testTable = await createTable(program, provider);
+    await joinTable(program, testTable, shortStack, new anchor.BN(SHORT_BUY_IN));
+    await joinTable(program, testTable, deepStack, new anchor.BN(DEEP_BUY_IN));
+
+    const { hand } = await playToPreflop(program, testTable, players);
+    if ((await playerToAct(program, testTable, hand, players)) === shortStack) {
+      await act(program, testTable, hand, shortStack, "call");
+    }
+
+    await raise(testTable, hand, deepStack, new anchor.BN(raiseTo));
+    await act(program, testTable, hand, shortStack, "allIn");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const deepSeat = tableAccount.playerOne.equals(deepStack.keypair.publicKey) ? 0 : 1;
+    return { testTable, hand, handAccount: await program.account.hand.fetch(hand), deepSeat };
+  }
+
+  const actedFlag = (seat: number) =>
+    seat === 0 ? HAND_FLAGS.P1_ACTED_THIS_STREET : HAND_FLAGS.P2_ACTED_THIS_STREET;
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Reopens the action after an all-in of at least a full raise", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: full-raise all-in");
+
+    // Raise to 5 BB adds 4 BB; the shove to 15 BB adds 10 BB
+    const { handAccount, deepSeat } = await shoveOverRaise(5 * BIG_BLIND);
+
+    assert.deepEqual(handAccount.stage, { preflop: {} });
+    assert.equal(handAccount.actionOn, deepSeat);
+    assert.isFalse(hasFlag(handAccount, actedFlag(deepSeat)));
+    assert.equal(handAccount.lastRaiseSize.toNumber(), 10 * BIG_BLIND);
+
+    console.log("   ✅ Raiser must act again, minimum raise reset to the shove");
+  });
+
+  it("Leaves the action closed after a short all-in", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: sub-minimum all-in");
+
+    // Raise to 10 BB adds 9 BB; the shove to 15 BB adds only 5 BB
+    const { testTable, hand, handAccount, deepSeat } = await shoveOverRaise(10 * BIG_BLIND);
+
+    assert.deepEqual(handAccount.stage, { preflop: {} });
+    assert.equal(handAccount.actionOn, deepSeat);
+    assert.isTrue(hasFlag(handAccount, actedFlag(deepSeat)));
+    assert.equal(handAccount.lastRaiseSize.toNumber(), 9 * BIG_BLIND);
+
+    // The raiser may not re-raise over the short shove
+    await expectError(raise(testTable, hand, players[1], handAccount.currentBet.muln(2)), "CannotRaise");
+
+    console.log("   ✅ Raiser may only call or fold, minimum raise unchanged");
+  });
+
+  it("Caps an all-in from a seat that may not raise to a call", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: all-in without reopened action");
+
+    const { testTable, hand, deepSeat } = await shoveOverRaise(10 * BIG_BLIND);
+
+    // The deep stack shoves 500 over the short 300; only the call goes in
+    await act(program, testTable, hand, players[1], "allIn");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const deepChips = deepSeat === 0 ? tableAccount.playerOneChips : tableAccount.playerTwoChips;
+    assert.equal(handAccount.currentBet.toNumber(), SHORT_BUY_IN);
+    assert.equal(handAccount.pot.toNumber(), 2 * SHORT_BUY_IN);
+    assert.equal(deepChips.toNumber(), DEEP_BUY_IN - SHORT_BUY_IN);
+    assert.isFalse(hasFlag(handAccount, deepSeat === 0 ? HAND_FLAGS.P1_ALL_IN : HAND_FLAGS.P2_ALL_IN));
+
+    console.log("   ✅ Shove cut to a call, the rest stays in the deep stack");
+  });
+});
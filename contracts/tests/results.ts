@@ -0,0 +1,169 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  act,
+  sleep,
+  seatProfiles,
+} from "./utils/game";
+
+const BUY_IN = 500_000000;
+
+describe("Hand Results", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+  const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+
+  let players: TestPlayer[];
+
+  /** Decode the HandResult events emitted by a confirmed transaction */
+  async function handResults(signature: string) {
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return [...parser.parseLogs(tx.meta.logMessages)].filter((event) => event.name === "handResult");
+  }
+
+  /** Assert a single HandResult was emitted and it matches the table's final state */
+  async function assertOneResult(
+    signature: string,
+    testTable: TestTable,
+    hand: PublicKey,
+    pot: number,
+    reason: "fold" | "showdown" | "timeout" | "split" | "agreement"
+  ) {
+    const events = await handResults(signature);
+    assert.lengthOf(events, 1);
+    const result = events[0].data;
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.isTrue(result.table.equals(testTable.table));
+    assert.equal(result.tableId.toString(), testTable.tableId.toString());
+    assert.equal(result.handNumber.toString(), handAccount.handNumber.toString());
+    assert.deepEqual(result.winner, handAccount.winner);
+    assert.deepEqual(result.reason, { [reason]: {} });
+    assert.deepEqual(handAccount.completionReason, { [reason]: {} });
+    assert.equal(result.pot.toNumber(), pot);
+    assert.equal(result.rake.toNumber(), 0);
+    assert.deepEqual(
+      result.stacks.map((s) => s.toNumber()),
+      [tableAccount.playerOneChips.toNumber(), tableAccount.playerTwoChips.toNumber()]
+    );
+    assert.equal(tableAccount.playerOneChips.toNumber() + tableAccount.playerTwoChips.toNumber(), 2 * BUY_IN);
+  }
+
+  /** Seat both players at a fresh table and reach preflop */
+  async function newHand(options = {}) {
+    const testTable = await createTable(program, provider, options);
+    for (const player of players) {
+      await joinTable(program, testTable, player, new anchor.BN(BUY_IN));
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+    return { testTable, hand };
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Emits one result when a fold ends the hand", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: HandResult on fold");
+
+    const { testTable, hand } = await newHand();
+    const { pot } = await program.account.hand.fetch(hand);
+    const folder = await playerToAct(program, testTable, hand, players);
+    const signature = await program.methods
+      .fold()
+      .accountsPartial({
+        player: folder.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([folder.keypair])
+      .rpc();
+
+    await assertOneResult(signature, testTable, hand, pot.toNumber(), "fold");
+
+    console.log("   ✅ Fold result emitted once");
+  });
+
+  it("Emits one result when the pot is claimed", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: HandResult on claim_pot");
+
+    const { testTable, hand } = await newHand();
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "call");
+
+    for (const player of players) {
+      await program.methods
+        .agreeResult({ seat0: {} } as any)
+        .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+        .signers([player.keypair])
+        .rpc();
+    }
+    const { pot } = await program.account.hand.fetch(hand);
+    const signature = await program.methods
+      .claimPot()
+      .accountsPartial({
+        player: players[0].keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([players[0].keypair])
+      .rpc();
+
+    await assertOneResult(signature, testTable, hand, pot.toNumber(), "agreement");
+
+    console.log("   ✅ Claim result emitted once");
+  });
+
+  it("Emits one result when a player times out", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: HandResult on timeout");
+
+    const { testTable, hand } = await newHand({ actionTimeout: new anchor.BN(10), timeBank: new anchor.BN(0) });
+    const { pot } = await program.account.hand.fetch(hand);
+    const actor = await playerToAct(program, testTable, hand, players);
+    const caller = players.find((p) => p !== actor);
+    await sleep(12_000);
+
+    const signature = await program.methods
+      .timeout()
+      .accountsPartial({
+        caller: caller.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([caller.keypair])
+      .rpc();
+
+    await assertOneResult(signature, testTable, hand, pot.toNumber(), "timeout");
+
+    console.log("   ✅ Timeout result emitted once");
+  });
+});
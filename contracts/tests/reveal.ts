@@ -0,0 +1,357 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import * as crypto from "crypto";
+import {
+  TestPlayer,
+  TestTable,
+  TableOptions,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  REVEAL_VERIFIER,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+import { shuffleDeck, getCommunityCards } from "./utils/deck";
+import { generateRevealProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+describe("Community Reveal", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const revealAll = (testTable: TestTable, hand: PublicKey, player: TestPlayer, cards: number[], proofBuffer: PublicKey) =>
+    program.methods
+      .revealAllCommunity(cards)
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer,
+        verifierProgram: REVEAL_VERIFIER,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  async function newHand(options: TableOptions = {}): Promise<{ testTable: TestTable; hand: PublicKey }> {
+    const testTable = await createTable(program, provider, options);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+    return { testTable, hand };
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Reveals the whole board with one proof after an all-in", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: reveal_all_community in an all-in runout");
+
+    const { testTable, hand } = await newHand();
+
+    // Shove and call preflop
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "call");
+
+    let handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { flop: {} });
+
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const shuffledDeck = shuffleDeck(deckSeed);
+    const { flop, turn, river } = getCommunityCards(shuffledDeck);
+    const cards = [...flop, turn, river];
+
+    const proof = await generateRevealProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+      deckSeed,
+      cards,
+      numCards: 5,
+      shuffledDeck,
+    });
+    const proofBuffer = await uploadProofToBuffer(program, proof, hand, shover.keypair, ProofType.Reveal);
+
+    await revealAll(testTable, hand, shover, cards, proofBuffer);
+
+    handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(Array.from(handAccount.flop), flop);
+    assert.equal(handAccount.turn, turn);
+    assert.equal(handAccount.river, river);
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.FLOP_REVEALED));
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.TURN_REVEALED));
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.RIVER_REVEALED));
+    assert.deepEqual(handAccount.stage, { showdown: {} });
+
+    console.log("   ✅ Flop, turn and river revealed in one transaction");
+  });
+
+  it("Rejects a batched reveal while betting is still open", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: reveal_all_community mid-betting");
+
+    const { testTable, hand } = await newHand();
+
+    // Limp and check to the flop
+    const first = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, first, "call");
+    await act(program, testTable, hand, players.find((p) => p !== first), "check");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { flop: {} });
+
+    const proofBuffer = await uploadProofToBuffer(
+      program,
+      crypto.randomBytes(388),
+      hand,
+      players[0].keypair,
+      ProofType.Reveal
+    );
+
+    await expectError(revealAll(testTable, hand, players[0], [0, 1, 2, 3, 4], proofBuffer), "NotAllInRunout");
+
+    console.log("   ✅ Correctly rejected batched reveal without an all-in");
+  });
+
+  const revealStreet = (
+    method: "revealFlop" | "revealTurn",
+    testTable: TestTable,
+    hand: PublicKey,
+    player: TestPlayer,
+    cards: number[] | number,
+    proofBuffer: PublicKey
+  ) =>
+    program.methods[method](cards as any)
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer,
+        verifierProgram: REVEAL_VERIFIER,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Both players check (or limp and check) through the current street */
+  async function checkThrough(testTable: TestTable, hand: PublicKey, limp = false): Promise<void> {
+    const first = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, first, limp ? "call" : "check");
+    await act(program, testTable, hand, players.find((p) => p !== first), "check");
+  }
+
+  describe("Duplicate cards", () => {
+    it("Rejects a flop with the same card twice", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: duplicated flop card");
+
+      const { testTable, hand } = await newHand();
+      await checkThrough(testTable, hand, true);
+
+      // Rejected before verification, so any proof bytes will do
+      const proofBuffer = await uploadProofToBuffer(
+        program,
+        crypto.randomBytes(388),
+        hand,
+        players[0].keypair,
+        ProofType.Reveal
+      );
+
+      await expectError(
+        revealStreet("revealFlop", testTable, hand, players[0], [7, 7, 12], proofBuffer),
+        "DuplicateCommunityCard"
+      );
+
+      console.log("   ✅ Correctly rejected with DuplicateCommunityCard");
+    });
+
+    it("Rejects a turn equal to a flop card", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: turn duplicating the flop");
+
+      const { testTable, hand } = await newHand();
+      await checkThrough(testTable, hand, true);
+
+      const handAccount = await program.account.hand.fetch(hand);
+      const deckSeed = Buffer.from(handAccount.deckSeed);
+      const shuffledDeck = shuffleDeck(deckSeed);
+      const { flop } = getCommunityCards(shuffledDeck);
+
+      const proof = await generateRevealProof({
+        handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+        deckSeed,
+        cards: flop,
+        numCards: 3,
+        shuffledDeck,
+      });
+      const flopBuffer = await uploadProofToBuffer(program, proof, hand, players[0].keypair, ProofType.Reveal);
+      await revealStreet("revealFlop", testTable, hand, players[0], flop, flopBuffer);
+
+      await checkThrough(testTable, hand);
+
+      const turnBuffer = await uploadProofToBuffer(
+        program,
+        crypto.randomBytes(388),
+        hand,
+        players[1].keypair,
+        ProofType.Reveal
+      );
+
+      await expectError(
+        revealStreet("revealTurn", testTable, hand, players[1], flop[1], turnBuffer),
+        "DuplicateCommunityCard"
+      );
+
+      console.log("   ✅ Correctly rejected with DuplicateCommunityCard");
+    });
+  });
+
+  describe("Street gating", () => {
+    it("Rejects betting the flop before it is revealed", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: check on an unrevealed flop");
+
+      const { testTable, hand } = await newHand();
+      await checkThrough(testTable, hand, true);
+
+      let handAccount = await program.account.hand.fetch(hand);
+      assert.deepEqual(handAccount.stage, { flop: {} });
+      assert.isFalse(hasFlag(handAccount, HAND_FLAGS.FLOP_REVEALED));
+
+      const first = await playerToAct(program, testTable, hand, players);
+      await expectError(
+        program.methods
+          .check()
+          .accountsPartial({
+            player: first.keypair.publicKey,
+            table: testTable.table,
+            hand,
+            ...(await seatProfiles(program, testTable)),
+          })
+          .signers([first.keypair])
+          .rpc(),
+        "StreetNotRevealed"
+      );
+
+      // `act` reveals the flop first, which opens its betting
+      await act(program, testTable, hand, first, "check");
+
+      handAccount = await program.account.hand.fetch(hand);
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.FLOP_REVEALED));
+      assert.notEqual(await playerToAct(program, testTable, hand, players), first);
+
+      console.log("   ✅ Correctly rejected with StreetNotRevealed until the flop was out");
+    });
+  });
+
+  describe("Buffer ownership", () => {
+    it("Rejects a reveal through another player's buffer", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: revealFlop with the opponent's proof buffer");
+
+      const { testTable, hand } = await newHand();
+      await checkThrough(testTable, hand, true);
+
+      // Rejected before verification, so any proof bytes will do
+      const proofBuffer = await uploadProofToBuffer(
+        program,
+        crypto.randomBytes(388),
+        hand,
+        players[0].keypair,
+        ProofType.Reveal
+      );
+
+      await expectError(
+        revealStreet("revealFlop", testTable, hand, players[1], [1, 2, 3], proofBuffer),
+        "BufferNotOwned"
+      );
+
+      console.log("   ✅ Correctly rejected with BufferNotOwned");
+    });
+
+    it("Rejects a reveal from a player without a seat", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: revealFlop from an outsider's own buffer");
+
+      const { testTable, hand } = await newHand();
+      await checkThrough(testTable, hand, true);
+
+      const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+      const outsider = await createPlayer(provider, config.usdcMint);
+      const proofBuffer = await uploadProofToBuffer(
+        program,
+        crypto.randomBytes(388),
+        hand,
+        outsider.keypair,
+        ProofType.Reveal
+      );
+
+      await expectError(
+        revealStreet("revealFlop", testTable, hand, outsider, [1, 2, 3], proofBuffer),
+        "PlayerNotInHand"
+      );
+
+      console.log("   ✅ Correctly rejected with PlayerNotInHand");
+    });
+  });
+
+  describe("Short deck", () => {
+    it("Rejects a deck size other than 52 or 36", async () => {
+      console.log("🧪 Testing: create_table with a 40-card deck");
+
+      await expectError(createTable(program, provider, { deckSize: 40 }), "InvalidTableConfig");
+
+      console.log("   ✅ Correctly rejected with InvalidTableConfig");
+    });
+
+    it("Rejects card indices 36..51 on a 36-card table", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: flop indices past a short deck");
+
+      const { testTable, hand } = await newHand({ deckSize: 36 });
+      await checkThrough(testTable, hand, true);
+
+      // Rejected before verification, so any proof bytes will do
+      const proofBuffer = await uploadProofToBuffer(
+        program,
+        crypto.randomBytes(388),
+        hand,
+        players[0].keypair,
+        ProofType.Reveal
+      );
+
+      for (const card of [36, 44, 51]) {
+        await expectError(
+          revealStreet("revealFlop", testTable, hand, players[0], [0, 1, card], proofBuffer),
+          "InvalidCardIndex"
+        );
+      }
+      // The top of the short deck passes the index check and fails on the proof
+      await expectError(
+        revealStreet("revealFlop", testTable, hand, players[0], [0, 1, 35], proofBuffer),
+        "InvalidProofFormat"
+      );
+
+      console.log("   ✅ Correctly rejected with InvalidCardIndex");
+    });
+  });
+});
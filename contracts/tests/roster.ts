@@ -0,0 +1,131 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { assert } from "chai";
+import * as crypto from "crypto";
+import { keccak256 } from "js-sha3";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  seatProfiles,
+  act,
+} from "./utils/game";
+
+describe("Hand Roster", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let attacker: TestPlayer;
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+    attacker = await createPlayer(provider, config.usdcMint);
+  });
+
+  it("Records both seated players when the hand starts", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: hand roster at start");
+
+    const testTable: TestTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.isTrue(handAccount.playerOne.equals(tableAccount.playerOne));
+    assert.isTrue(handAccount.playerTwo.equals(tableAccount.playerTwo));
+
+    console.log("   ✅ Hand stores player_one and player_two");
+  });
+
+  describe("Swapped seat", () => {
+    let testTable: TestTable;
+    let hand: anchor.web3.PublicKey;
+
+    before("Replace the player to act mid-hand with the attacker", async function () {
+      this.timeout(120000);
+
+      testTable = await createTable(program, provider);
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+      ({ hand } = await playToPreflop(program, testTable, players));
+
+      // The player to act leaves, and the attacker takes the empty seat
+      const leaver = await playerToAct(program, testTable, hand, players);
+      await program.methods
+        .foldAndLeave()
+        .accountsPartial({
+          player: leaver.keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+          playerTokenAccount: leaver.ata,
+          vault: testTable.vault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([leaver.keypair])
+        .rpc();
+      await joinTable(program, testTable, attacker);
+    });
+
+    it("Rejects seed actions from a player who took over a seat", async () => {
+      console.log("🧪 Testing: swapped-seat attacker commits a seed");
+
+      const seedHash = Buffer.from(keccak256(crypto.randomBytes(32)), "hex");
+      await expectError(
+        program.methods
+          .commitSeed(Array.from(seedHash))
+          .accounts({ player: attacker.keypair.publicKey, table: testTable.table, hand })
+          .signers([attacker.keypair])
+          .rpc(),
+        "PlayerNotInHand"
+      );
+
+      console.log("   ✅ Correctly rejected a player not dealt into the hand");
+    });
+
+    it("Rejects betting and claims from a player who took over a seat", async () => {
+      console.log("🧪 Testing: swapped-seat attacker bets and claims");
+
+      await expectError(act(program, testTable, hand, attacker, "check"), "PlayerNotInHand");
+      await expectError(
+        program.methods
+          .claimPot()
+          .accountsPartial({
+            player: attacker.keypair.publicKey,
+            table: testTable.table,
+            hand,
+            ...(await seatProfiles(program, testTable)),
+          })
+          .signers([attacker.keypair])
+          .rpc(),
+        "PlayerNotInHand"
+      );
+
+      // The hand still names the original players
+      const handAccount = await program.account.hand.fetch(hand);
+      assert.isFalse(handAccount.playerOne.equals(attacker.keypair.publicKey));
+      assert.isFalse(handAccount.playerTwo.equals(attacker.keypair.publicKey));
+
+      console.log("   ✅ Hand actions follow the roster frozen at start");
+    });
+  });
+});
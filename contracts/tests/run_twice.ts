@@ -0,0 +1,311 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import * as crypto from "crypto";
+import { keccak256 } from "js-sha3";
+import {
+  TestPlayer,
+  TestTable,
+  HoleCardSecrets,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  REVEAL_VERIFIER,
+  SHOWDOWN_VERIFIER,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+import { shuffleDeck, getHoleCards } from "./utils/deck";
+import { evaluateHandFromIndices } from "./utils/hand-eval";
+import {
+  generateRevealProof,
+  generateShowdownProof,
+  uploadProofToBuffer,
+  ProofType,
+  handId,
+} from "./utils/prover";
+
+const COMMUNITY_START = 18;
+const BUY_IN = 500_000000;
+
+type RunWinner = 0 | 1 | "split";
+
+describe("Run It Twice", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const consent = (testTable: TestTable, hand: PublicKey, player: TestPlayer) =>
+    program.methods
+      .consentRunTwice()
+      .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+      .signers([player.keypair])
+      .rpc();
+
+  const revealBoard = (
+    method: "revealAllCommunity" | "revealSecondBoard",
+    testTable: TestTable,
+    hand: PublicKey,
+    player: TestPlayer,
+    cards: number[],
+    proofBuffer: PublicKey
+  ) =>
+    program.methods[method](cards)
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer,
+        verifierProgram: REVEAL_VERIFIER,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Winner of one run given both players' hole cards and the board */
+  function runWinner(holes: [number, number][], board: number[]): RunWinner {
+    const [rankOne, rankTwo] = holes.map((hole) => evaluateHandFromIndices([...hole, ...board]));
+    if (rankOne === rankTwo) return "split";
+    return rankOne > rankTwo ? 0 : 1;
+  }
+
+  /**
+   * Grind shuffle seeds until the two boards produce the wanted results.
+   * Seeds are returned in `players` order; the deck seed hashes them in seat order.
+   */
+  function grindSeeds(playerOneIndex: number, wanted: [RunWinner, RunWinner]): Buffer[] {
+    for (;;) {
+      const seeds = [crypto.randomBytes(32), crypto.randomBytes(32)];
+      const seatSeeds = [seeds[playerOneIndex], seeds[1 - playerOneIndex]];
+      const deck = shuffleDeck(Buffer.from(keccak256(Buffer.concat(seatSeeds)), "hex"));
+      const holes = [getHoleCards(deck, 0), getHoleCards(deck, 1)];
+      const first = deck.slice(COMMUNITY_START, COMMUNITY_START + 5);
+      const second = deck.slice(COMMUNITY_START + 5, COMMUNITY_START + 10);
+
+      if (runWinner(holes, first) === wanted[0] && runWinner(holes, second) === wanted[1]) {
+        return seeds;
+      }
+    }
+  }
+
+  /** New table where both players are all-in preflop, heading into the runout */
+  async function allInHand(
+    seeds?: (playerOneIndex: number) => Buffer[]
+  ): Promise<{ testTable: TestTable; hand: PublicKey; secrets: HoleCardSecrets[]; seats: number[] }> {
+    const testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player, new anchor.BN(BUY_IN));
+    }
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const playerOneIndex = tableAccount.playerOne.equals(players[0].keypair.publicKey) ? 0 : 1;
+    const seats = players.map((_, i) => (i === playerOneIndex ? 0 : 1));
+
+    const { hand, secrets } = await playToPreflop(program, testTable, players, seeds?.(playerOneIndex));
+
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "allIn");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { flop: {} });
+
+    return { testTable, hand, secrets, seats };
+  }
+
+  /** Run both boards, reveal both hands on each and claim the pot */
+  async function runItTwice(testTable: TestTable, hand: PublicKey, secrets: HoleCardSecrets[]): Promise<void> {
+    for (const player of players) {
+      await consent(testTable, hand, player);
+    }
+
+    let handAccount = await program.account.hand.fetch(hand);
+    assert.equal(handAccount.runCount, 2);
+
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const shuffledDeck = shuffleDeck(deckSeed);
+    const proofHandId = handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion);
+    const boards = [
+      shuffledDeck.slice(COMMUNITY_START, COMMUNITY_START + 5),
+      shuffledDeck.slice(COMMUNITY_START + 5, COMMUNITY_START + 10),
+    ];
+
+    // Second board first, then the first board moves the hand to showdown
+    for (const [method, offset, board] of [
+      ["revealSecondBoard", 5, boards[1]],
+      ["revealAllCommunity", 0, boards[0]],
+    ] as ["revealAllCommunity" | "revealSecondBoard", number, number[]][]) {
+      const proof = await generateRevealProof({
+        handId: proofHandId,
+        deckSeed,
+        cards: board,
+        numCards: 5,
+        shuffledDeck,
+        offset,
+      });
+      const proofBuffer = await uploadProofToBuffer(program, proof, hand, players[0].keypair, ProofType.Reveal);
+      await revealBoard(method, testTable, hand, players[0], board, proofBuffer);
+    }
+
+    handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(Array.from(handAccount.secondBoard), boards[1]);
+    assert.isTrue(hasFlag(handAccount, HAND_FLAGS.SECOND_BOARD_REVEALED));
+    assert.deepEqual(handAccount.stage, { showdown: {} });
+
+    for (const [i, player] of players.entries()) {
+      for (const [method, board] of [
+        ["revealHand", boards[0]],
+        ["revealSecondHand", boards[1]],
+      ] as ["revealHand" | "revealSecondHand", number[]][]) {
+        const { proof, handRank } = await generateShowdownProof({
+          handId: proofHandId,
+          commitment1: secrets[i].commitments[0],
+          commitment2: secrets[i].commitments[1],
+          communityCards: board as [number, number, number, number, number],
+          holeCard1: secrets[i].cards[0],
+          holeCard2: secrets[i].cards[1],
+          salt1: secrets[i].salts[0],
+          salt2: secrets[i].salts[1],
+        });
+        const proofBuffer = await uploadProofToBuffer(program, proof, hand, player.keypair, ProofType.Showdown);
+
+        await program.methods[method](new anchor.BN(handRank.toString()))
+          .accountsPartial({
+            player: player.keypair.publicKey,
+            globalConfig: findGlobalConfig(program),
+            table: testTable.table,
+            hand,
+            proofBuffer,
+            verifierProgram: SHOWDOWN_VERIFIER,
+          })
+          .signers([player.keypair])
+          .rpc();
+      }
+    }
+
+    await program.methods
+      .claimPot()
+      .accountsPartial({
+        player: players[0].keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([players[0].keypair])
+      .rpc();
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  describe("Payout", () => {
+    it("Splits the pot when each player wins one run", async function () {
+      this.timeout(600000);
+      console.log("🧪 Testing: run it twice split 1-1");
+
+      const { testTable, hand, secrets } = await allInHand((playerOneIndex) => grindSeeds(playerOneIndex, [0, 1]));
+      await runItTwice(testTable, hand, secrets);
+
+      const handAccount = await program.account.hand.fetch(hand);
+      assert.deepEqual(handAccount.winner, { seat0: {} });
+      assert.deepEqual(handAccount.secondWinner, { seat1: {} });
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      assert.equal(tableAccount.playerOneChips.toNumber(), BUY_IN);
+      assert.equal(tableAccount.playerTwoChips.toNumber(), BUY_IN);
+
+      console.log("   ✅ Each player won half of the pot");
+    });
+
+    it("Awards the whole pot when one player wins both runs", async function () {
+      this.timeout(600000);
+      console.log("🧪 Testing: run it twice sweep 2-0");
+
+      const { testTable, hand, secrets } = await allInHand((playerOneIndex) => grindSeeds(playerOneIndex, [1, 1]));
+      await runItTwice(testTable, hand, secrets);
+
+      const handAccount = await program.account.hand.fetch(hand);
+      assert.deepEqual(handAccount.winner, { seat1: {} });
+      assert.deepEqual(handAccount.secondWinner, { seat1: {} });
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      assert.equal(tableAccount.playerOneChips.toNumber(), 0);
+      assert.equal(tableAccount.playerTwoChips.toNumber(), 2 * BUY_IN);
+
+      console.log("   ✅ Seat 1 swept both runs");
+    });
+  });
+
+  describe("Consent", () => {
+    it("Rejects consent while betting is still open", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: consent_run_twice before an all-in");
+
+      const testTable = await createTable(program, provider);
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+      const { hand } = await playToPreflop(program, testTable, players);
+
+      await expectError(consent(testTable, hand, players[0]), "NotAllInRunout");
+
+      console.log("   ✅ Correctly rejected consent without an all-in");
+    });
+
+    it("Deals one board until both players agree", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: run it twice needs both players");
+
+      const { testTable, hand, seats } = await allInHand();
+
+      await consent(testTable, hand, players[0]);
+      await expectError(consent(testTable, hand, players[0]), "RunTwiceAlreadyConsented");
+
+      let handAccount = await program.account.hand.fetch(hand);
+      const consentFlag = seats[0] === 0 ? HAND_FLAGS.P1_RUN_TWICE_CONSENT : HAND_FLAGS.P2_RUN_TWICE_CONSENT;
+      assert.isTrue(hasFlag(handAccount, consentFlag));
+      assert.equal(handAccount.runCount, 1);
+
+      // Rejected before verification, so the same buffer serves both attempts
+      const proofBuffer = await uploadProofToBuffer(
+        program,
+        crypto.randomBytes(388),
+        hand,
+        players[0].keypair,
+        ProofType.Reveal
+      );
+
+      await expectError(
+        revealBoard("revealSecondBoard", testTable, hand, players[0], [0, 1, 2, 3, 4], proofBuffer),
+        "RunTwiceNotAgreed"
+      );
+
+      await consent(testTable, hand, players[1]);
+      handAccount = await program.account.hand.fetch(hand);
+      assert.equal(handAccount.runCount, 2);
+
+      await expectError(
+        revealBoard("revealAllCommunity", testTable, hand, players[0], [0, 1, 2, 3, 4], proofBuffer),
+        "SecondBoardNotRevealed"
+      );
+
+      console.log("   ✅ Second board only after both players agree, and before the first board");
+    });
+  });
+});
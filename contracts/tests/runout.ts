@@ -0,0 +1,119 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  REVEAL_VERIFIER,
+} from "./utils/game";
+import { shuffleDeck, getCommunityCards } from "./utils/deck";
+import { generateRevealProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+describe("All-In Runout", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+  // Not seated, stands in for a keeper bot
+  const keeper = Keypair.generate();
+
+  const advanceRunout = () =>
+    program.methods
+      .advanceRunout()
+      .accounts({ caller: keeper.publicKey, table: testTable.table, hand })
+      .signers([keeper])
+      .rpc();
+
+  const stage = async () => Object.keys((await program.account.hand.fetch(hand)).stage)[0];
+
+  before("Seat two players and reach preflop", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    ({ hand } = await playToPreflop(program, testTable, players));
+  });
+
+  it("Rejects advancing while chips can still be bet", async () => {
+    console.log("🧪 Testing: advance_runout before an all-in");
+
+    await expectError(advanceRunout(), "NotAllInRunout");
+
+    console.log("   ✅ Correctly rejected with NotAllInRunout");
+  });
+
+  it("Steps an all-in hand to showdown as each street is revealed", async function () {
+    this.timeout(600000);
+    console.log("🧪 Testing: advance_runout after an all-in");
+
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "call");
+    assert.equal(await stage(), "flop");
+
+    // The flop is not out yet
+    await expectError(advanceRunout(), "StreetNotRevealed");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    const proofHandId = handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion);
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const shuffledDeck = shuffleDeck(deckSeed);
+    const { flop, turn, river } = getCommunityCards(shuffledDeck);
+    const board = [...flop, turn, river];
+
+    const streets = [
+      { method: "revealFlop", arg: [...flop], numCards: 3, next: "turn" },
+      { method: "revealTurn", arg: turn, numCards: 4, next: "river" },
+      { method: "revealRiver", arg: river, numCards: 5, next: "showdown" },
+    ];
+    for (const { method, arg, numCards, next } of streets) {
+      const proof = await generateRevealProof({
+        handId: proofHandId,
+        deckSeed,
+        cards: board.slice(0, numCards),
+        numCards,
+        shuffledDeck,
+      });
+      await program.methods[method](arg)
+        .accountsPartial({
+          player: shover.keypair.publicKey,
+          globalConfig: findGlobalConfig(program),
+          table: testTable.table,
+          hand,
+          proofBuffer: await uploadProofToBuffer(program, proof, hand, shover.keypair, ProofType.Reveal),
+          verifierProgram: REVEAL_VERIFIER,
+        })
+        .signers([shover.keypair])
+        .rpc();
+
+      await advanceRunout();
+      assert.equal(await stage(), next);
+    }
+
+    await expectError(advanceRunout(), "NotAllInRunout");
+
+    console.log("   ✅ Keeper moved the runout through to showdown");
+  });
+});
@@ -0,0 +1,49 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  createPlayer,
+  createTable,
+  joinTable,
+  findGlobalConfig,
+} from "./utils/game";
+
+describe("Deterministic Seating", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  before("Create players ordered by pubkey", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+    players.sort((a, b) => Buffer.compare(a.keypair.publicKey.toBuffer(), b.keypair.publicKey.toBuffer()));
+  });
+
+  it("Seats the smaller pubkey first in either join order", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: seats follow pubkey order");
+
+    for (const joinOrder of [players, [...players].reverse()]) {
+      const testTable = await createTable(program, provider, { deterministicSeating: true });
+      for (const player of joinOrder) {
+        await joinTable(program, testTable, player);
+      }
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      assert.isTrue(tableAccount.playerOne.equals(players[0].keypair.publicKey));
+      assert.isTrue(tableAccount.playerTwo.equals(players[1].keypair.publicKey));
+      assert.equal(tableAccount.button, 0);
+      assert.deepEqual(tableAccount.status, { between: {} });
+    }
+
+    console.log("   ✅ Same seats and button for both join orders");
+  });
+});
@@ -0,0 +1,88 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import * as crypto from "crypto";
+import { keccak256 } from "js-sha3";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+describe("Seed Protocol", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+
+  const commit = (player: TestPlayer, seedHash: Buffer) =>
+    program.methods
+      .commitSeed(Array.from(seedHash))
+      .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+      .signers([player.keypair])
+      .rpc();
+
+  const reveal = (player: TestPlayer, seed: Buffer) =>
+    program.methods
+      .revealSeed(Array.from(seed))
+      .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+      .signers([player.keypair])
+      .rpc();
+
+  const hash = (seed: Buffer) => Buffer.from(keccak256(seed), "hex");
+
+  before("Start a hand", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    hand = await startHand(program, testTable, players[0]);
+  });
+
+  it("Rejects a copy of the opponent's seed commitment", async () => {
+    console.log("🧪 Testing: duplicate seed commitment");
+
+    const seeds = [crypto.randomBytes(32), crypto.randomBytes(32)];
+    await commit(players[0], hash(seeds[0]));
+    await expectError(commit(players[1], hash(seeds[0])), "DuplicateSeedCommitment");
+
+    await commit(players[1], hash(seeds[1]));
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { seedReveal: {} });
+
+    console.log("   ✅ Copied commitment rejected with DuplicateSeedCommitment");
+  });
+
+  it("Cannot reveal the opponent's seed after being forced to commit its own", async () => {
+    console.log("🧪 Testing: duplicate seed reveal");
+
+    // With distinct commitments, the opponent's seed never matches the copier's hash
+    const handAccount = await program.account.hand.fetch(hand);
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const firstIsSeatZero = tableAccount.playerOne.equals(players[0].keypair.publicKey);
+    const firstCommit = Buffer.from(firstIsSeatZero ? handAccount.seedCommitOne : handAccount.seedCommitTwo);
+
+    const seed = crypto.randomBytes(32);
+    assert.isFalse(hash(seed).equals(firstCommit));
+    await expectError(reveal(players[1], seed), "InvalidSeedReveal");
+
+    console.log("   ✅ Only the committed seed can be revealed");
+  });
+});
@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  act,
+  seatProfiles,
+  HAND_FLAGS,
+  hasFlag,
+  BettingMethod,
+  revealStreet,
+} from "./utils/game";
+
+const SHORT_BUY_IN = 200_000000;
+const DEEP_BUY_IN = 500_000000;
+const BIG_BLIND = 20_000000;
+const FLOP_BET = 300_000000;
+
+describe("Short All-In Call", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const bet = async (testTable: TestTable, hand: PublicKey, player: TestPlayer, amount: number) =>
+    program.methods
+      .bet(new anchor.BN(amount))
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  for (const method of ["call", "allIn"] as BettingMethod[]) {
+    it(`Returns the uncalled excess when the short stack uses ${method}`, async function () {
+      this.timeout(120000);
+      console.log(`🧪 Testing: short stack ${method} against a bigger flop bet`);
+
+      const [shortStack, deepStack] = players;
+      const testTable = await createTable(program, provider);
+      await joinTable(program, testTable, shortStack, new anchor.BN(SHORT_BUY_IN));
+      await joinTable(program, testTable, deepStack, new anchor.BN(DEEP_BUY_IN));
+
+      // Limp and check to the flop, then the short stack checks to the bet
+      const { hand } = await playToPreflop(program, testTable, players);
+      const smallBlind = await playerToAct(program, testTable, hand, players);
+      await act(program, testTable, hand, smallBlind, "call");
+      await act(program, testTable, hand, players.find((p) => p !== smallBlind), "check");
+      if ((await playerToAct(program, testTable, hand, players)) === shortStack) {
+        await act(program, testTable, hand, shortStack, "check");
+      }
+
+      await revealStreet(program, testTable, hand, deepStack);
+      await bet(testTable, hand, deepStack, FLOP_BET);
+      await act(program, testTable, hand, shortStack, method);
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      const handAccount = await program.account.hand.fetch(hand);
+      const shortSeat = tableAccount.playerOne.equals(shortStack.keypair.publicKey) ? 0 : 1;
+      const stacks = [tableAccount.playerOneChips.toNumber(), tableAccount.playerTwoChips.toNumber()];
+
+      // The short stack covered 180 of the 300 bet; the other 120 went back
+      const called = SHORT_BUY_IN - BIG_BLIND;
+      assert.equal(stacks[shortSeat], 0);
+      assert.equal(stacks[1 - shortSeat], DEEP_BUY_IN - BIG_BLIND - called);
+      assert.deepEqual(handAccount.totalBet.map((b) => b.toNumber()), [SHORT_BUY_IN, SHORT_BUY_IN]);
+      assert.equal(handAccount.pot.toNumber(), 2 * SHORT_BUY_IN);
+
+      // The street closed straight into the runout
+      assert.deepEqual(handAccount.stage, { turn: {} });
+      assert.isTrue(hasFlag(handAccount, HAND_FLAGS.FAST_SHOWDOWN));
+
+      console.log("   ✅ Excess returned, street closed into the runout");
+    });
+  }
+});
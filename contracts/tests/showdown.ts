@@ -0,0 +1,247 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import * as crypto from "crypto";
+import {
+  TestPlayer,
+  TestTable,
+  HoleCardSecrets,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  SHOWDOWN_VERIFIER,
+  HAND_FLAGS,
+  hasFlag,
+  revealStreet,
+  sleep,
+} from "./utils/game";
+import { shuffleDeck, getCommunityCards } from "./utils/deck";
+import { generateShowdownProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+// Royal flush: category 9 followed by A-K-Q-J-T, four bits per value
+const MAX_HAND_RANK = 0x9cba98n;
+
+describe("Showdown Order", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+  let secrets: HoleCardSecrets[];
+  let aggressor: TestPlayer;
+  let caller: TestPlayer;
+
+  const revealHand = (player: TestPlayer, handRank: bigint, proofBuffer: PublicKey) =>
+    program.methods
+      .revealHand(new anchor.BN(handRank.toString()))
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer,
+        verifierProgram: SHOWDOWN_VERIFIER,
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Check down to the river, then the first to act bets and the other calls */
+  async function betRiverAndCall(testTable: TestTable) {
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const { hand, secrets } = await playToPreflop(program, testTable, players);
+
+    // Limp preflop, check the flop and turn
+    const limper = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, limper, "call");
+    await act(program, testTable, hand, players.find((p) => p !== limper), "check");
+    for (let street = 0; street < 2; street++) {
+      for (let i = 0; i < 2; i++) {
+        await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "check");
+      }
+    }
+
+    // River: first to act bets, the other calls
+    const aggressor = await playerToAct(program, testTable, hand, players);
+    const caller = players.find((p) => p !== aggressor);
+    await revealStreet(program, testTable, hand, aggressor);
+    await program.methods
+      .bet(new anchor.BN(20_000000))
+      .accountsPartial({
+        player: aggressor.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([aggressor.keypair])
+      .rpc();
+    await act(program, testTable, hand, caller, "call");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { showdown: {} });
+    return { hand, secrets, aggressor, caller };
+  }
+
+  before("Check down to the river, then bet and call", async function () {
+    this.timeout(300000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    ({ hand, secrets, aggressor, caller } = await betRiverAndCall(testTable));
+  });
+
+  it("Rejects the caller revealing before the river aggressor", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: out-of-order showdown reveal");
+
+    // Rejected before verification, so any bytes will do
+    const proofBuffer = await uploadProofToBuffer(
+      program,
+      crypto.randomBytes(388),
+      hand,
+      caller.keypair,
+      ProofType.Showdown
+    );
+
+    await expectError(revealHand(caller, 0n, proofBuffer), "ShowdownOrderViolation");
+
+    console.log("   ✅ Correctly rejected reveal out of order");
+  });
+
+  it("Rejects hand ranks outside the encoding range", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: hand rank range check");
+
+    // Range is checked before the proof, so a short buffer shows which check failed
+    const proofBuffer = await uploadProofToBuffer(
+      program,
+      crypto.randomBytes(388),
+      hand,
+      aggressor.keypair,
+      ProofType.Showdown
+    );
+
+    await expectError(revealHand(aggressor, 0n, proofBuffer), "InvalidHandRank");
+    await expectError(revealHand(aggressor, MAX_HAND_RANK + 1n, proofBuffer), "InvalidHandRank");
+    // A royal flush is in range and only fails on the proof itself
+    await expectError(revealHand(aggressor, MAX_HAND_RANK, proofBuffer), "InvalidProofFormat");
+
+    await program.methods
+      .closeProofBuffer()
+      .accounts({ player: aggressor.keypair.publicKey, proofBuffer, hand })
+      .signers([aggressor.keypair])
+      .rpc();
+
+    console.log("   ✅ Zero and over-range ranks rejected, max rank accepted");
+  });
+
+  it("Lets the aggressor show first and the caller muck", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: aggressor shows, caller mucks");
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    let handAccount = await program.account.hand.fetch(hand);
+    const aggressorIndex = players.indexOf(aggressor);
+    const aggressorSeat = tableAccount.playerOne.equals(aggressor.keypair.publicKey) ? 0 : 1;
+    assert.equal(handAccount.actionOn, aggressorSeat);
+
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const { flop, turn, river } = getCommunityCards(shuffleDeck(deckSeed));
+    const { proof, handRank } = await generateShowdownProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+      commitment1: secrets[aggressorIndex].commitments[0],
+      commitment2: secrets[aggressorIndex].commitments[1],
+      communityCards: [...flop, turn, river],
+      holeCard1: secrets[aggressorIndex].cards[0],
+      holeCard2: secrets[aggressorIndex].cards[1],
+      salt1: secrets[aggressorIndex].salts[0],
+      salt2: secrets[aggressorIndex].salts[1],
+    });
+    const proofBuffer = await uploadProofToBuffer(program, proof, hand, aggressor.keypair, ProofType.Showdown);
+    // The proof outputs the rank, so a higher claim on the same proof fails
+    await expectError(revealHand(aggressor, handRank + 1n, proofBuffer), "RankMismatch");
+    await revealHand(aggressor, handRank, proofBuffer);
+
+    await program.methods
+      .muckHand()
+      .accounts({ player: caller.keypair.publicKey, table: testTable.table, hand })
+      .signers([caller.keypair])
+      .rpc();
+
+    handAccount = await program.account.hand.fetch(hand);
+    assert.isTrue(hasFlag(handAccount, aggressorSeat === 0 ? HAND_FLAGS.P2_MUCKED : HAND_FLAGS.P1_MUCKED));
+    assert.deepEqual(handAccount.winner, aggressorSeat === 0 ? { seat0: {} } : { seat1: {} });
+
+    const pot = handAccount.pot.toNumber();
+    const chipsBefore = aggressorSeat === 0 ? tableAccount.playerOneChips : tableAccount.playerTwoChips;
+
+    await program.methods
+      .claimPot()
+      .accountsPartial({
+        player: aggressor.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([aggressor.keypair])
+      .rpc();
+
+    const after = await program.account.table.fetch(testTable.table);
+    const chipsAfter = aggressorSeat === 0 ? after.playerOneChips : after.playerTwoChips;
+    assert.equal(chipsAfter.toNumber() - chipsBefore.toNumber(), pot);
+    assert.deepEqual((await program.account.hand.fetch(hand)).completionReason, { showdown: {} });
+
+    console.log("   ✅ Aggressor showed, caller mucked, aggressor claimed the pot");
+  });
+
+  it("Times out the aggressor in seat 1 for refusing to show first", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: stalled first reveal at showdown");
+
+    const stallTable = await createTable(program, provider, { actionTimeout: new anchor.BN(10) });
+    const stalled = await betRiverAndCall(stallTable);
+    const tableAccount = await program.account.table.fetch(stallTable.table);
+    const aggressorSeat = tableAccount.playerOne.equals(stalled.aggressor.keypair.publicKey) ? 0 : 1;
+    assert.equal(aggressorSeat, 1);
+
+    const timeout = async (caller: TestPlayer) =>
+      program.methods
+        .timeout()
+        .accountsPartial({
+          caller: caller.keypair.publicKey,
+          table: stallTable.table,
+          hand: stalled.hand,
+          ...(await seatProfiles(program, stallTable)),
+        })
+        .signers([caller.keypair])
+        .rpc();
+
+    // The stalling aggressor cannot pin the timeout on the seat waiting for it
+    await expectError(timeout(stalled.aggressor), "CannotTimeoutSelf");
+
+    await sleep(11_000);
+    await timeout(stalled.caller);
+
+    const handAccount = await program.account.hand.fetch(stalled.hand);
+    assert.deepEqual(handAccount.winner, { seat0: {} });
+
+    console.log("   ✅ Seat 0 timed out the stalling aggressor and won the pot");
+  });
+});
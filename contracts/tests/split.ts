@@ -0,0 +1,237 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import { keccak256 } from "js-sha3";
+import * as crypto from "crypto";
+import {
+  TestPlayer,
+  TestTable,
+  HoleCardSecrets,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  seatProfiles,
+  REVEAL_VERIFIER,
+  SHOWDOWN_VERIFIER,
+} from "./utils/game";
+import { shuffleDeck, getCommunityCards, getHoleCards } from "./utils/deck";
+import { evaluateHandFromIndices } from "./utils/hand-eval";
+import { generateRevealProof, generateShowdownProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+const BUY_IN = 500_000000;
+
+describe("Split Pot", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+  let secrets: HoleCardSecrets[];
+  let proofs: Buffer[];
+  let ranks: bigint[];
+
+  /**
+   * Draw seed pairs (in seat order) until the deck they shuffle gives both
+   * seats equal best hands, or different ones when `tied` is false
+   */
+  function seedsFor(tied: boolean): Buffer[] {
+    for (;;) {
+      const seeds = [crypto.randomBytes(32), crypto.randomBytes(32)];
+      const deck = shuffleDeck(Buffer.from(keccak256(Buffer.concat(seeds)), "hex"));
+      const { flop, turn, river } = getCommunityCards(deck);
+      const [first, second] = [0, 1].map((seat) => evaluateHandFromIndices([...getHoleCards(deck, seat), ...flop, turn, river]));
+      if ((first === second) === tied) {
+        return seeds;
+      }
+    }
+  }
+
+  const revealHand = async (i: number, rank: bigint = ranks[i]) =>
+    program.methods
+      .revealHand(new anchor.BN(rank.toString()))
+      .accountsPartial({
+        player: players[i].keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer: await uploadProofToBuffer(program, proofs[i], hand, players[i].keypair, ProofType.Showdown),
+        verifierProgram: SHOWDOWN_VERIFIER,
+      })
+      .signers([players[i].keypair])
+      .rpc();
+
+  const claimPot = async (player: TestPlayer) =>
+    program.methods
+      .claimPot()
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /**
+   * Seat two fresh players with the given buy-ins, run an all-in hand to
+   * showdown on a deck where the hands tie (or not, when `tied` is false)
+   * and prove both hands. Returns the buy-ins in the final `players` order.
+   */
+  async function allInToShowdown(buyIns: number[], tied = true): Promise<number[]> {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const [i, player] of players.entries()) {
+      await joinTable(program, testTable, player, new anchor.BN(buyIns[i]));
+    }
+    ({ hand, secrets } = await playToPreflop(program, testTable, players, seedsFor(tied)));
+
+    const shover = await playerToAct(program, testTable, hand, players);
+    await act(program, testTable, hand, shover, "allIn");
+    await act(program, testTable, hand, players.find((p) => p !== shover), "call");
+    // The aggressor shows first, so keep them as players[0]
+    if (shover !== players[0]) {
+      players.reverse();
+      secrets.reverse();
+      buyIns = [...buyIns].reverse();
+    }
+
+    const handAccount = await program.account.hand.fetch(hand);
+    const proofHandId = handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion);
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const shuffledDeck = shuffleDeck(deckSeed);
+    const { flop, turn, river } = getCommunityCards(shuffledDeck);
+    const board: [number, number, number, number, number] = [...flop, turn, river];
+
+    const revealProof = await generateRevealProof({ handId: proofHandId, deckSeed, cards: board, numCards: 5, shuffledDeck });
+    await program.methods
+      .revealAllCommunity(board)
+      .accountsPartial({
+        player: shover.keypair.publicKey,
+        globalConfig: findGlobalConfig(program),
+        table: testTable.table,
+        hand,
+        proofBuffer: await uploadProofToBuffer(program, revealProof, hand, shover.keypair, ProofType.Reveal),
+        verifierProgram: REVEAL_VERIFIER,
+      })
+      .signers([shover.keypair])
+      .rpc();
+
+    proofs = [];
+    ranks = [];
+    for (const secret of secrets) {
+      const { proof, handRank } = await generateShowdownProof({
+        handId: proofHandId,
+        commitment1: secret.commitments[0],
+        commitment2: secret.commitments[1],
+        communityCards: board,
+        holeCard1: secret.cards[0],
+        holeCard2: secret.cards[1],
+        salt1: secret.salts[0],
+        salt2: secret.salts[1],
+      });
+      proofs.push(proof);
+      ranks.push(handRank);
+    }
+    return buyIns;
+  }
+
+  /** Reveal both hands and claim the chop; returns each player's stack */
+  async function chop(): Promise<number[]> {
+    await revealHand(0);
+    await revealHand(1);
+    await claimPot(players[0]);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    return players.map((p) =>
+      (tableAccount.playerOne.equals(p.keypair.publicKey) ? tableAccount.playerOneChips : tableAccount.playerTwoChips).toNumber()
+    );
+  }
+
+  before("Run an all-in hand to showdown and prove both hands", async function () {
+    this.timeout(600000);
+    await allInToShowdown([BUY_IN, BUY_IN]);
+  });
+
+  it("Refuses to pay a split before the second reveal", async function () {
+    this.timeout(60000);
+    console.log("🧪 Testing: claim_pot with one hand revealed");
+
+    await revealHand(0);
+    await expectError(claimPot(players[0]), "PlayersNotRevealed");
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.winner, { none: {} });
+
+    console.log("   ✅ Correctly rejected with PlayersNotRevealed");
+  });
+
+  it("Chops the pot once both equal ranks are revealed", async function () {
+    this.timeout(60000);
+    console.log("🧪 Testing: split pot after two reveals");
+
+    await revealHand(1);
+    assert.deepEqual((await program.account.hand.fetch(hand)).winner, { split: {} });
+
+    await claimPot(players[0]);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.equal(tableAccount.playerOneChips.toNumber(), BUY_IN);
+    assert.equal(tableAccount.playerTwoChips.toNumber(), BUY_IN);
+    assert.deepEqual((await program.account.hand.fetch(hand)).completionReason, { split: {} });
+
+    console.log("   ✅ Both stacks back to the buy-in");
+  });
+
+  describe("Claimed chop", () => {
+    it("Rejects a tie claimed with a rank the proof does not output", async function () {
+      this.timeout(600000);
+      console.log("🧪 Testing: copied rank to force a chop");
+
+      await allInToShowdown([BUY_IN, BUY_IN], false);
+      const [better, worse] = ranks[0] > ranks[1] ? [0, 1] : [1, 0];
+
+      // The weaker hand copies the stronger rank; its proof outputs its own
+      await revealHand(better);
+      await expectError(revealHand(worse, ranks[better]), "RankMismatch");
+
+      const handAccount = await program.account.hand.fetch(hand);
+      assert.deepEqual(handAccount.winner, { none: {} });
+
+      console.log("   ✅ Correctly rejected with RankMismatch, no split declared");
+    });
+  });
+
+  describe("Unequal all-in", () => {
+    it("Returns each stack's own contribution when a short all-in chops", async function () {
+      this.timeout(600000);
+      console.log("🧪 Testing: chop between a 300 and a 500 stack");
+
+      const buyIns = await allInToShowdown([300_000000, BUY_IN]);
+
+      // The uncalled 200 went back when the short stack's all-in was called
+      const handAccount = await program.account.hand.fetch(hand);
+      const totals = handAccount.totalBet.map((b: anchor.BN) => b.toNumber());
+      assert.deepEqual(totals, [300_000000, 300_000000]);
+      assert.equal(handAccount.pot.toNumber(), 600_000000);
+
+      assert.deepEqual(await chop(), buyIns);
+
+      console.log("   ✅ Short stack got 300 back, deep stack 500");
+    });
+  });
+});
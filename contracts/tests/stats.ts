@@ -0,0 +1,88 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  findTableStats,
+  seatProfiles,
+} from "./utils/game";
+
+describe("Table Stats", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  before("Create a full table", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+  });
+
+  it("Does not create stats before a hand completes", async () => {
+    console.log("🧪 Testing: stats are created lazily");
+
+    const info = await provider.connection.getAccountInfo(findTableStats(program, testTable.table));
+    assert.isNull(info);
+
+    console.log("   ✅ No stats account for a fresh table");
+  });
+
+  it("Accumulates stats across several folded hands", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: stats accumulate across hands");
+
+    const hands = 3;
+    const wins = [0, 0];
+
+    for (let i = 0; i < hands; i++) {
+      const { hand } = await playToPreflop(program, testTable, players);
+      const folder = await playerToAct(program, testTable, hand, players);
+      const winner = players.find((p) => p !== folder);
+
+      await program.methods
+        .fold()
+        .accountsPartial({
+          player: folder.keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([folder.keypair])
+        .rpc();
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      wins[tableAccount.playerOne.equals(winner.keypair.publicKey) ? 0 : 1]++;
+    }
+
+    const stats = await program.account.tableStats.fetch(findTableStats(program, testTable.table));
+    const pot = 30_000000; // small blind + big blind
+
+    assert.ok(stats.table.equals(testTable.table));
+    assert.equal(stats.totalHands.toNumber(), hands);
+    assert.equal(stats.totalVolume.toNumber(), hands * pot);
+    assert.equal(stats.biggestPot.toNumber(), pot);
+    assert.equal(stats.rakeCollected.toNumber(), 0);
+    assert.deepEqual(stats.handsWon.map((w) => w.toNumber()), wins);
+
+    console.log(`   ✅ ${hands} hands recorded, wins per seat: ${wins}`);
+  });
+});
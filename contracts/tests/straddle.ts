@@ -0,0 +1,99 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  runSeedProtocol,
+  commitHoleCards,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+const BIG_BLIND = 20_000000;
+
+describe("Straddle", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  const straddle = (testTable: TestTable, hand: PublicKey, player: TestPlayer) =>
+    program.methods
+      .straddle()
+      .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Start a hand and return it with the button (small blind) and big blind players */
+  async function newHand(
+    straddleAllowed: boolean
+  ): Promise<{ testTable: TestTable; hand: PublicKey; button: TestPlayer; bigBlind: TestPlayer }> {
+    const testTable = await createTable(program, provider, { straddleAllowed });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const hand = await startHand(program, testTable, players[0]);
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const buttonKey = tableAccount.button === 0 ? tableAccount.playerOne : tableAccount.playerTwo;
+    const button = players.find((p) => p.keypair.publicKey.equals(buttonKey));
+    const bigBlind = players.find((p) => p !== button);
+
+    return { testTable, hand, button, bigBlind };
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Raises the preflop bet to match and lets the big blind act first", async function () {
+    this.timeout(300000);
+    console.log("🧪 Testing: button straddle");
+
+    const { testTable, hand, button, bigBlind } = await newHand(true);
+
+    await expectError(straddle(testTable, hand, bigBlind), "NotYourTurn");
+    await straddle(testTable, hand, button);
+    await expectError(straddle(testTable, hand, button), "AlreadyStraddled");
+
+    await runSeedProtocol(program, testTable, hand, players);
+    for (const player of players) {
+      await commitHoleCards(program, testTable, hand, player);
+    }
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { preflop: {} });
+    assert.equal(handAccount.straddleAmount.toNumber(), 2 * BIG_BLIND);
+    assert.equal(handAccount.currentBet.toNumber(), 2 * BIG_BLIND);
+    assert.equal(handAccount.pot.toNumber(), 3 * BIG_BLIND);
+
+    const actor = await playerToAct(program, testTable, hand, players);
+    assert.equal(actor, bigBlind);
+
+    console.log("   ✅ Bet to match is 2 BB and the big blind acts first");
+  });
+
+  it("Rejects a straddle on a table that does not allow it", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: straddle disabled");
+
+    const { testTable, hand, button } = await newHand(false);
+    await expectError(straddle(testTable, hand, button), "StraddleNotAllowed");
+
+    console.log("   ✅ Correctly rejected with StraddleNotAllowed");
+  });
+});
@@ -0,0 +1,143 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  act,
+  seatProfiles,
+  HAND_FLAGS,
+  hasFlag,
+  revealStreet,
+} from "./utils/game";
+
+const BUY_IN = 500_000000;
+const SMALL_BLIND = 10_000000;
+const BIG_BLIND = 20_000000;
+
+describe("Street Accounting", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+  let seatOf: (player: TestPlayer) => number;
+
+  const bet = async (player: TestPlayer, amount: number) =>
+    program.methods
+      .bet(new anchor.BN(amount))
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+  /** Assert both seats' street and hand bets, indexed by seat */
+  async function assertBets(betThisStreet: [number, number], totalBet: [number, number]) {
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.betThisStreet.map((b) => b.toNumber()), betThisStreet);
+    assert.deepEqual(handAccount.totalBet.map((b) => b.toNumber()), totalBet);
+    assert.equal(handAccount.pot.toNumber(), totalBet[0] + totalBet[1]);
+    return handAccount;
+  }
+
+  /** Both seats' amounts in seat order from a per-player mapping */
+  const bySeat = (amounts: Map<TestPlayer, number>): [number, number] => {
+    const seats: [number, number] = [0, 0];
+    for (const [player, amount] of amounts) seats[seatOf(player)] = amount;
+    return seats;
+  };
+
+  before("Seat two players and reach preflop", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player, new anchor.BN(BUY_IN));
+    }
+    ({ hand } = await playToPreflop(program, testTable, players));
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    seatOf = (player) => (tableAccount.playerOne.equals(player.keypair.publicKey) ? 0 : 1);
+  });
+
+  it("Tracks each seat's bets through a full hand", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: per-seat bets across every street");
+
+    const sb = await playerToAct(program, testTable, hand, players);
+    const bb = players.find((p) => p !== sb);
+
+    // Preflop: blinds posted, small blind completes, big blind checks
+    const blinds = bySeat(new Map([[sb, SMALL_BLIND], [bb, BIG_BLIND]]));
+    await assertBets(blinds, blinds);
+    await act(program, testTable, hand, sb, "call");
+    await act(program, testTable, hand, bb, "check");
+    let handAccount = await assertBets([0, 0], [BIG_BLIND, BIG_BLIND]);
+    assert.deepEqual(handAccount.stage, { flop: {} });
+    assert.isFalse(hasFlag(handAccount, HAND_FLAGS.P1_ACTED_THIS_STREET));
+    assert.isFalse(hasFlag(handAccount, HAND_FLAGS.P2_ACTED_THIS_STREET));
+
+    // Flop: bet and call
+    const flopBettor = await playerToAct(program, testTable, hand, players);
+    const flopCaller = players.find((p) => p !== flopBettor);
+    await revealStreet(program, testTable, hand, flopBettor);
+    await bet(flopBettor, 2 * BIG_BLIND);
+    await assertBets(
+      bySeat(new Map([[flopBettor, 2 * BIG_BLIND], [flopCaller, 0]])),
+      bySeat(new Map([[flopBettor, 3 * BIG_BLIND], [flopCaller, BIG_BLIND]]))
+    );
+    await act(program, testTable, hand, flopCaller, "call");
+    handAccount = await assertBets([0, 0], [3 * BIG_BLIND, 3 * BIG_BLIND]);
+    assert.deepEqual(handAccount.stage, { turn: {} });
+
+    // Turn: checked through
+    for (let i = 0; i < 2; i++) {
+      await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "check");
+    }
+    handAccount = await assertBets([0, 0], [3 * BIG_BLIND, 3 * BIG_BLIND]);
+    assert.deepEqual(handAccount.stage, { river: {} });
+
+    // River: bet, the other player folds
+    const riverBettor = await playerToAct(program, testTable, hand, players);
+    const folder = players.find((p) => p !== riverBettor);
+    await revealStreet(program, testTable, hand, riverBettor);
+    await bet(riverBettor, 5 * BIG_BLIND);
+    await act(program, testTable, hand, folder, "fold");
+
+    handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { complete: {} });
+    assert.isTrue(hasFlag(handAccount, seatOf(folder) === 0 ? HAND_FLAGS.P1_FOLDED : HAND_FLAGS.P2_FOLDED));
+    assert.deepEqual(
+      handAccount.totalBet.map((b) => b.toNumber()),
+      bySeat(new Map([[riverBettor, 8 * BIG_BLIND], [folder, 3 * BIG_BLIND]]))
+    );
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const stacks = [tableAccount.playerOneChips.toNumber(), tableAccount.playerTwoChips.toNumber()];
+    assert.equal(stacks[seatOf(riverBettor)], BUY_IN + 3 * BIG_BLIND);
+    assert.equal(stacks[seatOf(folder)], BUY_IN - 3 * BIG_BLIND);
+
+    console.log("   ✅ Street and hand bets match seat by seat");
+  });
+});
@@ -0,0 +1,97 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  findGlobalConfig,
+  expectError,
+} from "./utils/game";
+
+const FIRST_BUY_IN = 300_000000;
+const SECOND_BUY_IN = 500_000000;
+
+describe("Swap Seats", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+
+  const swapSeats = (player: TestPlayer, opponent: TestPlayer) =>
+    program.methods
+      .swapSeats()
+      .accounts({
+        player: player.keypair.publicKey,
+        opponent: opponent.keypair.publicKey,
+        table: testTable.table,
+      })
+      .signers([player.keypair, opponent.keypair])
+      .rpc();
+
+  before("Seat two players with different stacks", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    await joinTable(program, testTable, players[0], new anchor.BN(FIRST_BUY_IN));
+    await joinTable(program, testTable, players[1], new anchor.BN(SECOND_BUY_IN));
+  });
+
+  it("Moves each stack and the button with its player", async () => {
+    console.log("🧪 Testing: swap_seats between hands");
+
+    const before = await program.account.table.fetch(testTable.table);
+    const buttonHolder = before.button === 0 ? before.playerOne : before.playerTwo;
+
+    await swapSeats(players[1], players[0]);
+
+    const after = await program.account.table.fetch(testTable.table);
+    assert.isTrue(after.playerOne.equals(before.playerTwo));
+    assert.isTrue(after.playerTwo.equals(before.playerOne));
+    assert.equal(after.playerOneChips.toString(), before.playerTwoChips.toString());
+    assert.equal(after.playerTwoChips.toString(), before.playerOneChips.toString());
+
+    const chipsOf = (player: TestPlayer) =>
+      (after.playerOne.equals(player.keypair.publicKey) ? after.playerOneChips : after.playerTwoChips).toNumber();
+    assert.equal(chipsOf(players[0]), FIRST_BUY_IN);
+    assert.equal(chipsOf(players[1]), SECOND_BUY_IN);
+    assert.isTrue((after.button === 0 ? after.playerOne : after.playerTwo).equals(buttonHolder));
+
+    console.log("   ✅ Stacks and button followed their players");
+  });
+
+  it("Requires both seated players to sign", async () => {
+    console.log("🧪 Testing: swap_seats with an outsider");
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    const outsider = await createPlayer(provider, config.usdcMint);
+
+    await expectError(swapSeats(players[0], outsider), "PlayerNotAtTable");
+    await expectError(swapSeats(players[0], players[0]), "PlayerNotAtTable");
+
+    console.log("   ✅ Correctly rejected with PlayerNotAtTable");
+  });
+
+  it("Rejects swapping during a hand", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: swap_seats mid-hand");
+
+    await playToPreflop(program, testTable, players);
+    await expectError(swapSeats(players[0], players[1]), "HandInProgress");
+
+    console.log("   ✅ Correctly rejected with HandInProgress");
+  });
+});
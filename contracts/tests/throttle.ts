@@ -0,0 +1,74 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  act,
+  sleep,
+} from "./utils/game";
+
+const MIN_INTERVAL = 3;
+
+describe("Action Throttle", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  it("Rejects an action inside the interval and accepts it after", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: back-to-back actions on a throttled table");
+
+    const testTable = await createTable(program, provider, { minActionInterval: new anchor.BN(MIN_INTERVAL) });
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const { hand } = await playToPreflop(program, testTable, players);
+    const smallBlind = await playerToAct(program, testTable, hand, players);
+    const bigBlind = players.find((p) => p !== smallBlind);
+
+    await sleep((MIN_INTERVAL + 1) * 1000);
+    await act(program, testTable, hand, smallBlind, "call");
+
+    // The big blind answers straight away
+    await expectError(act(program, testTable, hand, bigBlind, "check"), "ActingTooFast");
+
+    await sleep((MIN_INTERVAL + 1) * 1000);
+    await act(program, testTable, hand, bigBlind, "check");
+    assert.deepEqual((await program.account.hand.fetch(hand)).stage, { flop: {} });
+
+    console.log("   ✅ Instant reply rejected, delayed reply accepted");
+  });
+
+  it("Rejects an interval that reaches the action timeout", async () => {
+    console.log("🧪 Testing: minActionInterval equal to the action timeout");
+
+    await expectError(
+      createTable(program, provider, {
+        actionTimeout: new anchor.BN(30),
+        minActionInterval: new anchor.BN(30),
+      }),
+      "InvalidTimeoutConfig"
+    );
+
+    console.log("   ✅ Correctly rejected with InvalidTimeoutConfig");
+  });
+});
@@ -0,0 +1,465 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  expectError,
+  sleep,
+  seatProfiles,
+  startHand,
+  act,
+  runSeedProtocol,
+  verifyDeal,
+  commitHoleCards,
+} from "./utils/game";
+import * as crypto from "crypto";
+import { keccak256 } from "js-sha3";
+
+describe("Timeouts & Time Bank", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+  });
+
+  describe("Time bank", () => {
+    let testTable: TestTable;
+    let hand: anchor.web3.PublicKey;
+
+    before("Create a 10s table with a 30s time bank and reach preflop", async function () {
+      this.timeout(120000);
+
+      testTable = await createTable(program, provider, {
+        actionTimeout: new anchor.BN(10),
+        timeBank: new anchor.BN(30),
+      });
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      assert.equal(tableAccount.timeBank.toNumber(), 30);
+      assert.deepEqual(tableAccount.timeBankRemaining.map((t) => t.toNumber()), [30, 30]);
+
+      ({ hand } = await playToPreflop(program, testTable, players));
+    });
+
+    it("Does not time out a player who is still within their time bank", async function () {
+      this.timeout(60000);
+      console.log("🧪 Testing: timeout waits for the time bank");
+
+      await sleep(13_000);
+
+      const actor = await playerToAct(program, testTable, hand, players);
+      const caller = players.find((p) => p !== actor);
+      await expectError(
+        program.methods
+          .timeout()
+          .accountsPartial({
+            caller: caller.keypair.publicKey,
+            table: testTable.table,
+            hand,
+            ...(await seatProfiles(program, testTable)),
+          })
+          .signers([caller.keypair])
+          .rpc(),
+        "NoTimeout"
+      );
+
+      console.log("   ✅ Timeout rejected while the time bank is running");
+    });
+
+    it("Deducts the time used past the action timeout from the bank", async function () {
+      this.timeout(60000);
+      console.log("🧪 Testing: late action consumes time bank");
+
+      const actor = await playerToAct(program, testTable, hand, players);
+      const before = await program.account.table.fetch(testTable.table);
+      const seat = before.playerOne.equals(actor.keypair.publicKey) ? 0 : 1;
+
+      await program.methods
+        .call()
+        .accountsPartial({
+          player: actor.keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([actor.keypair])
+        .rpc();
+
+      const after = await program.account.table.fetch(testTable.table);
+      const used = before.timeBankRemaining[seat].toNumber() - after.timeBankRemaining[seat].toNumber();
+      assert.isAtLeast(used, 3);
+      assert.isBelow(after.timeBankRemaining[seat].toNumber(), 30);
+      assert.equal(after.timeBankRemaining[1 - seat].toNumber(), 30);
+
+      console.log(`   ✅ Seat ${seat} used ${used}s of time bank`);
+    });
+  });
+
+  describe("Pre-deal setup", () => {
+    /**
+     * Start a hand on a 10s table, let players[0] finish `stage` while
+     * players[1] stalls, then time players[1] out
+     */
+    async function stallSetup(stage: "seedCommit" | "seedReveal"): Promise<void> {
+      const testTable = await createTable(program, provider, { actionTimeout: new anchor.BN(10) });
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+      const hand = await startHand(program, testTable, players[0]);
+
+      const seeds = players.map(() => crypto.randomBytes(32));
+      const committers = stage === "seedCommit" ? [players[0]] : players;
+      for (const [i, player] of committers.entries()) {
+        await program.methods
+          .commitSeed(Array.from(Buffer.from(keccak256(seeds[i]), "hex")))
+          .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+          .signers([player.keypair])
+          .rpc();
+      }
+
+      if (stage === "seedReveal") {
+        await program.methods
+          .revealSeed(Array.from(seeds[0]))
+          .accounts({ player: players[0].keypair.publicKey, table: testTable.table, hand })
+          .signers([players[0].keypair])
+          .rpc();
+      }
+
+      const before = await program.account.hand.fetch(hand);
+      assert.deepEqual(before.stage, { [stage]: {} });
+      assert.isAbove(before.pot.toNumber(), 0);
+
+      await sleep(11_000);
+
+      await program.methods
+        .timeout()
+        .accountsPartial({
+          caller: players[0].keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([players[0].keypair])
+        .rpc();
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      const handAccount = await program.account.hand.fetch(hand);
+      const honestSeat = tableAccount.playerOne.equals(players[0].keypair.publicKey) ? 0 : 1;
+
+      // Forfeit is recorded, but both blinds went back to their owners
+      assert.deepEqual(handAccount.winner, honestSeat === 0 ? { seat0: {} } : { seat1: {} });
+      assert.equal(handAccount.pot.toNumber(), 0);
+      assert.equal(tableAccount.playerOneChips.toNumber(), 500_000000);
+      assert.equal(tableAccount.playerTwoChips.toNumber(), 500_000000);
+    }
+
+    it("Returns the blinds to their owners when a seed is never committed", async function () {
+      this.timeout(120000);
+      console.log("🧪 Testing: seed commit timeout refunds blinds");
+
+      await stallSetup("seedCommit");
+
+      console.log("   ✅ Both stacks restored after a seed commit timeout");
+    });
+
+    it("Returns the blinds to their owners when a seed is never revealed", async function () {
+      this.timeout(120000);
+      console.log("🧪 Testing: seed reveal timeout refunds blinds");
+
+      await stallSetup("seedReveal");
+
+      console.log("   ✅ Both stacks restored, forfeit recorded against the non-revealer");
+    });
+  });
+
+  describe("Card commit deadline", () => {
+    it("Times out a card commit only after the commit deadline", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: 60s commit deadline on a 10s table");
+
+      const testTable = await createTable(program, provider, {
+        actionTimeout: new anchor.BN(10),
+        commitDeadline: new anchor.BN(60),
+      });
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+      const hand = await startHand(program, testTable, players[0]);
+      await runSeedProtocol(program, testTable, hand, players);
+      await verifyDeal(program, testTable, hand, players[0]);
+      await commitHoleCards(program, testTable, hand, players[0]);
+
+      const timeout = async () =>
+        program.methods
+          .timeout()
+          .accountsPartial({
+            caller: players[0].keypair.publicKey,
+            table: testTable.table,
+            hand,
+            ...(await seatProfiles(program, testTable)),
+          })
+          .signers([players[0].keypair])
+          .rpc();
+
+      // Past the action timeout, players[1] is still generating its proof
+      await sleep(11_000);
+      await expectError(timeout(), "NoTimeout");
+
+      await sleep(50_000);
+      await timeout();
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      const handAccount = await program.account.hand.fetch(hand);
+      const honestSeat = tableAccount.playerOne.equals(players[0].keypair.publicKey) ? 0 : 1;
+      assert.deepEqual(handAccount.winner, honestSeat === 0 ? { seat0: {} } : { seat1: {} });
+
+      console.log("   ✅ NoTimeout at 11s, forfeit after the 60s deadline");
+    });
+
+    it("Bumps the default commit deadline of a fast table to the proof floor", async () => {
+      console.log("🧪 Testing: default commit deadline on a 10s table");
+
+      const testTable = await createTable(program, provider, { actionTimeout: new anchor.BN(10) });
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      assert.equal(tableAccount.actionTimeout.toNumber(), 10);
+      assert.equal(tableAccount.commitDeadline.toNumber(), 60);
+
+      console.log("   ✅ Card commits get 60s on a 10s table");
+    });
+
+    it("Rejects a commit deadline below the proof floor or above the maximum", async () => {
+      console.log("🧪 Testing: commit deadline bounds");
+
+      for (const commitDeadline of [5, 30, 301]) {
+        await expectError(
+          createTable(program, provider, {
+            actionTimeout: new anchor.BN(10),
+            commitDeadline: new anchor.BN(commitDeadline),
+          }),
+          "InvalidTimeoutConfig"
+        );
+      }
+
+      console.log("   ✅ Correctly rejected 5s, 30s and 301s deadlines");
+    });
+  });
+
+  describe("Caller standing", () => {
+    let testTable: TestTable;
+    let hand: anchor.web3.PublicKey;
+
+    const claimTimeout = (caller: TestPlayer) =>
+      seatProfiles(program, testTable).then((profiles) =>
+        program.methods
+          .timeout()
+          .accountsPartial({ caller: caller.keypair.publicKey, table: testTable.table, hand, ...profiles })
+          .signers([caller.keypair])
+          .rpc()
+      );
+
+    before("Let the player to act stall past a 10s timeout", async function () {
+      this.timeout(120000);
+
+      testTable = await createTable(program, provider, { actionTimeout: new anchor.BN(10), timeBank: new anchor.BN(0) });
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+      ({ hand } = await playToPreflop(program, testTable, players));
+      await sleep(12_000);
+    });
+
+    it("Rejects a player timing out their own seat", async () => {
+      console.log("🧪 Testing: self-timeout by the stalling player");
+
+      const actor = await playerToAct(program, testTable, hand, players);
+      await expectError(claimTimeout(actor), "CannotTimeoutSelf");
+
+      console.log("   ✅ Correctly rejected with CannotTimeoutSelf");
+    });
+
+    it("Lets an unseated keeper settle the stalled hand", async () => {
+      console.log("🧪 Testing: timeout called by a keeper");
+
+      const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+      const keeper = await createPlayer(provider, config.usdcMint);
+      const actor = await playerToAct(program, testTable, hand, players);
+      await claimTimeout(keeper);
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      const handAccount = await program.account.hand.fetch(hand);
+      const actorSeat = tableAccount.playerOne.equals(actor.keypair.publicKey) ? 0 : 1;
+      assert.deepEqual(handAccount.winner, actorSeat === 0 ? { seat1: {} } : { seat0: {} });
+      assert.deepEqual(handAccount.stage, { complete: {} });
+
+      console.log("   ✅ Keeper timed out the stalling seat, opponent awarded the pot");
+    });
+  });
+
+  describe("Settled hands", () => {
+    it("Refuses to time out a hand whose pot was already claimed", async function () {
+      this.timeout(120000);
+      console.log("🧪 Testing: timeout after claim_pot");
+
+      const testTable = await createTable(program, provider, { actionTimeout: new anchor.BN(10), timeBank: new anchor.BN(0) });
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+      const { hand } = await playToPreflop(program, testTable, players);
+      const shover = await playerToAct(program, testTable, hand, players);
+      for (const [player, method] of [
+        [shover, "allIn"],
+        [players.find((p) => p !== shover), "call"],
+      ] as const) {
+        await program.methods[method]()
+          .accountsPartial({
+            player: player.keypair.publicKey,
+            table: testTable.table,
+            hand,
+            ...(await seatProfiles(program, testTable)),
+          })
+          .signers([player.keypair])
+          .rpc();
+      }
+
+      for (const player of players) {
+        await program.methods
+          .agreeResult({ seat0: {} } as any)
+          .accounts({ player: player.keypair.publicKey, table: testTable.table, hand })
+          .signers([player.keypair])
+          .rpc();
+      }
+      await program.methods
+        .claimPot()
+        .accountsPartial({
+          player: players[0].keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([players[0].keypair])
+        .rpc();
+      const settled = await program.account.table.fetch(testTable.table);
+
+      await sleep(12_000);
+      await expectError(
+        program.methods
+          .timeout()
+          .accountsPartial({
+            caller: players[1].keypair.publicKey,
+            table: testTable.table,
+            hand,
+            ...(await seatProfiles(program, testTable)),
+          })
+          .signers([players[1].keypair])
+          .rpc(),
+        "HandAlreadyComplete"
+      );
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      assert.equal(tableAccount.playerOneChips.toNumber(), settled.playerOneChips.toNumber());
+      assert.equal(tableAccount.playerTwoChips.toNumber(), settled.playerTwoChips.toNumber());
+
+      console.log("   ✅ Correctly rejected with HandAlreadyComplete, stacks unchanged");
+    });
+  });
+
+  describe("Auto check/fold policy", () => {
+    /** Fresh 10s auto-check/fold table at preflop, with the small blind to act */
+    async function newHand() {
+      const testTable = await createTable(program, provider, {
+        actionTimeout: new anchor.BN(10),
+        timeBank: new anchor.BN(0),
+        timeoutPolicy: { autoCheckFold: {} },
+      });
+      for (const player of players) {
+        await joinTable(program, testTable, player);
+      }
+      const { hand } = await playToPreflop(program, testTable, players);
+      const smallBlind = await playerToAct(program, testTable, hand, players);
+      const bigBlind = players.find((p) => p !== smallBlind);
+      return { testTable, hand, smallBlind, bigBlind };
+    }
+
+    /** Let the action clock run out, then time the hand out as `caller` */
+    async function timeOut(testTable: TestTable, hand: anchor.web3.PublicKey, caller: TestPlayer) {
+      await sleep(12_000);
+      await program.methods
+        .timeout()
+        .accountsPartial({
+          caller: caller.keypair.publicKey,
+          table: testTable.table,
+          hand,
+          ...(await seatProfiles(program, testTable)),
+        })
+        .signers([caller.keypair])
+        .rpc();
+    }
+
+    it("Checks for a timed-out seat with no bet to face", async function () {
+      this.timeout(120000);
+      console.log("🧪 Testing: timeout on the big blind's option");
+
+      const { testTable, hand, smallBlind } = await newHand();
+      await act(program, testTable, hand, smallBlind, "call");
+      await timeOut(testTable, hand, smallBlind);
+
+      const handAccount = await program.account.hand.fetch(hand);
+      assert.deepEqual(handAccount.stage, { flop: {} });
+      assert.deepEqual(handAccount.winner, { none: {} });
+
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      assert.equal(tableAccount.playerOneChips.toNumber(), tableAccount.playerTwoChips.toNumber());
+      assert.isNotNull(tableAccount.currentHand);
+
+      console.log("   ✅ Big blind checked and the hand moved to the flop");
+    });
+
+    it("Folds for a timed-out seat facing a bet", async function () {
+      this.timeout(120000);
+      console.log("🧪 Testing: timeout on the small blind facing the big blind");
+
+      const { testTable, hand, bigBlind } = await newHand();
+      await timeOut(testTable, hand, bigBlind);
+
+      const handAccount = await program.account.hand.fetch(hand);
+      const tableAccount = await program.account.table.fetch(testTable.table);
+      const bigBlindSeat = tableAccount.playerOne.equals(bigBlind.keypair.publicKey) ? 0 : 1;
+      assert.deepEqual(handAccount.stage, { complete: {} });
+      assert.deepEqual(handAccount.completionReason, { fold: {} });
+      assert.deepEqual(handAccount.winner, bigBlindSeat === 0 ? { seat0: {} } : { seat1: {} });
+
+      console.log("   ✅ Small blind folded, big blind took the blinds");
+    });
+  });
+
+  it("Rejects a time bank above the maximum", async () => {
+    console.log("🧪 Testing: reject oversized time bank");
+
+    await expectError(
+      createTable(program, provider, { timeBank: new anchor.BN(601) }),
+      "InvalidTimeoutConfig"
+    );
+
+    console.log("   ✅ Correctly rejected time bank > 600s");
+  });
+});
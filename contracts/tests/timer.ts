@@ -0,0 +1,99 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  revealStreet,
+  seatProfiles,
+} from "./utils/game";
+
+describe("Action Timer Events", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+  const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+
+  /** Take a betting action and return the ActionTimerStarted events it emitted */
+  async function actionTimers(method: "call" | "check") {
+    const player = await playerToAct(program, testTable, hand, players);
+    await revealStreet(program, testTable, hand, player);
+    const signature = await program.methods[method]()
+      .accountsPartial({
+        player: player.keypair.publicKey,
+        table: testTable.table,
+        hand,
+        ...(await seatProfiles(program, testTable)),
+      })
+      .signers([player.keypair])
+      .rpc();
+
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return [...parser.parseLogs(tx.meta.logMessages)].filter((event) => event.name === "actionTimerStarted");
+  }
+
+  /** Assert one timer started for the seat to act, with the program's deadline */
+  async function assertTimer(events: anchor.Event[]) {
+    assert.lengthOf(events, 1);
+    const timer = events[0].data;
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    const timeout =
+      tableAccount.actionTimeout.toNumber() +
+      tableAccount.timeBankRemaining[handAccount.actionOn].toNumber();
+
+    assert.equal(timer.tableId.toString(), testTable.tableId.toString());
+    assert.equal(timer.handNumber.toString(), handAccount.handNumber.toString());
+    assert.equal(timer.seat, handAccount.actionOn);
+    assert.equal(timer.deadline.toNumber(), handAccount.lastActionAt.toNumber() + timeout);
+  }
+
+  before("Reach preflop", async function () {
+    this.timeout(120000);
+
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    ({ hand } = await playToPreflop(program, testTable, players));
+  });
+
+  it("Starts the next seat's timer after each betting action", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: ActionTimerStarted through preflop and the flop");
+
+    // Limp, check to the flop, then check the flop through to the turn
+    for (const method of ["call", "check", "check", "check"] as const) {
+      await assertTimer(await actionTimers(method));
+    }
+
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.deepEqual(handAccount.stage, { turn: {} });
+
+    console.log("   ✅ One event per action with deadline = last action + timeout");
+  });
+});
@@ -0,0 +1,188 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  startHand,
+  runSeedProtocol,
+  verifyDeal,
+  findGlobalConfig,
+  expectError,
+  DEAL_VERIFIER,
+  REVEAL_VERIFIER,
+  HAND_FLAGS,
+  hasFlag,
+} from "./utils/game";
+import { shuffleDeck } from "./utils/deck";
+import { generateDealProof, uploadProofToBuffer, ProofType, handId } from "./utils/prover";
+
+describe("Verifier Upgrade", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+  const authority = provider.wallet;
+  const globalConfig = findGlobalConfig(program);
+
+  let players: TestPlayer[];
+  let version: number;
+
+  /**
+   * Re-register the current verifiers, or a different deal verifier, which
+   * bumps the circuit version
+   */
+  const updateVerifiers = async (signer?: TestPlayer, dealVerifier?: PublicKey) => {
+    const config = await program.account.globalConfig.fetch(globalConfig);
+    const builder = program.methods
+      .updateVerifiers(
+        config.deckVerifier,
+        dealVerifier ?? config.dealVerifier,
+        config.revealVerifier,
+        config.showdownVerifier
+      )
+      .accounts({ authority: signer ? signer.keypair.publicKey : authority.publicKey, globalConfig });
+    return signer ? builder.signers([signer.keypair]).rpc() : builder.rpc();
+  };
+
+  /** New table with a hand through the seed protocol, ready for verify_deal */
+  async function handAtDeal(): Promise<{ testTable: TestTable; hand: PublicKey }> {
+    const testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+    const hand = await startHand(program, testTable, players[0]);
+    await runSeedProtocol(program, testTable, hand, players);
+    return { testTable, hand };
+  }
+
+  before("Create players", async () => {
+    const config = await program.account.globalConfig.fetch(globalConfig);
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+    version = config.circuitVersion;
+  });
+
+  it("Rejects an upgrade from anyone but the authority", async () => {
+    console.log("🧪 Testing: update_verifiers from a player");
+
+    await expectError(updateVerifiers(players[0]), "Unauthorized");
+
+    console.log("   ✅ Correctly rejected with Unauthorized");
+  });
+
+  it("Keeps an in-flight hand on the version it started under", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: verify_deal across an upgrade");
+
+    const { testTable, hand } = await handAtDeal();
+    assert.equal((await program.account.hand.fetch(hand)).circuitVersion, version);
+
+    await updateVerifiers();
+    assert.equal((await program.account.globalConfig.fetch(globalConfig)).circuitVersion, version + 1);
+
+    await verifyDeal(program, testTable, hand, players[0]);
+
+    console.log(`   ✅ Hand started under version ${version} still verified`);
+  });
+
+  it("Rejects a proof for the old version on a hand started after the upgrade", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: stale circuit version in the hand binding");
+
+    const { testTable, hand } = await handAtDeal();
+    const handAccount = await program.account.hand.fetch(hand);
+    assert.equal(handAccount.circuitVersion, version + 1);
+
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const staleProof = await generateDealProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), version),
+      deckSeed,
+      shuffledDeck: shuffleDeck(deckSeed),
+    });
+    const proofBuffer = await uploadProofToBuffer(program, staleProof, hand, players[0].keypair, ProofType.Deal);
+
+    await expectError(
+      program.methods
+        .verifyDeal()
+        .accounts({
+          player: players[0].keypair.publicKey,
+          globalConfig,
+          table: testTable.table,
+          hand,
+          proofBuffer,
+          verifierProgram: DEAL_VERIFIER,
+        })
+        .signers([players[0].keypair])
+        .rpc(),
+      "ProofHandMismatch"
+    );
+
+    // The same deal bound to the current version goes through
+    await verifyDeal(program, testTable, hand, players[1]);
+
+    console.log(`   ✅ Version ${version} proof rejected, version ${version + 1} accepted`);
+  });
+
+  it("Verifies proofs against the registered verifier, not the original one", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: verify_deal after registering a different deal verifier");
+
+    const { testTable, hand } = await handAtDeal();
+    const handAccount = await program.account.hand.fetch(hand);
+    const deckSeed = Buffer.from(handAccount.deckSeed);
+    const proof = await generateDealProof({
+      handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+      deckSeed,
+      shuffledDeck: shuffleDeck(deckSeed),
+    });
+    const proofBuffer = await uploadProofToBuffer(program, proof, hand, players[0].keypair, ProofType.Deal);
+
+    // Stand-in for an upgraded deal verifier: any other deployed verifier
+    await updateVerifiers(undefined, REVEAL_VERIFIER);
+    assert.isTrue((await program.account.globalConfig.fetch(globalConfig)).dealVerifier.equals(REVEAL_VERIFIER));
+
+    const submit = (verifierProgram: PublicKey) =>
+      program.methods
+        .verifyDeal()
+        .accounts({
+          player: players[0].keypair.publicKey,
+          globalConfig,
+          table: testTable.table,
+          hand,
+          proofBuffer,
+          verifierProgram,
+        })
+        .signers([players[0].keypair])
+        .rpc();
+
+    // The old program is no longer accepted
+    await expectError(submit(DEAL_VERIFIER), "ProofVerificationFailed");
+
+    // The registered program is the one the proof is sent to; it is not a
+    // deal verifier, so it rejects the proof
+    let logs: string[] = [];
+    try {
+      await submit(REVEAL_VERIFIER);
+      assert.fail("Should have thrown error");
+    } catch (err) {
+      assert.notInclude(err.message, "Should have thrown error");
+      logs = err.logs ?? [];
+    }
+    assert.isTrue(logs.some((line) => line.includes(`Verifier program: ${REVEAL_VERIFIER.toBase58()}`)));
+
+    // Back on the real deal verifier the same proof goes through
+    await updateVerifiers(undefined, DEAL_VERIFIER);
+    await submit(DEAL_VERIFIER);
+    assert.isTrue(hasFlag(await program.account.hand.fetch(hand), HAND_FLAGS.DEAL_VERIFIED));
+
+    console.log("   ✅ Proofs were sent to whichever verifier is registered");
+  });
+});
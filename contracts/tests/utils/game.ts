@@ -0,0 +1,587 @@
+/**
+ * Shared helpers for driving tables and hands through the program in tests
+ */
+
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../../target/types/contracts";
+import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import * as crypto from "crypto";
+import { keccak256 } from "js-sha3";
+import { shuffleDeck, getHoleCards, getCommunityCards } from "./deck";
+import { generateSalt } from "./crypto";
+import {
+  generateDeckProof,
+  generateDealProof,
+  generateRevealProof,
+  uploadProofToBuffer,
+  ProofType,
+  handId,
+} from "./prover";
+import { commitmentToBytes } from "./commitments";
+
+export const GLOBAL_SEED = Buffer.from("global");
+export const TABLE_SEED = Buffer.from("table");
+export const HAND_SEED = Buffer.from("hand");
+export const VAULT_SEED = Buffer.from("vault");
+export const TABLE_STATS_SEED = Buffer.from("table_stats");
+export const PROFILE_SEED = Buffer.from("profile");
+
+export const DECK_VERIFIER = new PublicKey("5mWDL7NZwacC8fxVouwEwUgvJGQMpcaAfjmyMNkwzWEd");
+export const DEAL_VERIFIER = new PublicKey("DewUCARGDNMyp2yWwn69VF5upEuchW7pfUMAAznFiJzy");
+export const REVEAL_VERIFIER = new PublicKey("9Yp14dZ4ZVY9ckWn5tzyEaymy4r1dH5VwCbCwKSRgvTx");
+export const SHOWDOWN_VERIFIER = new PublicKey("7urWEDFxTrKSE6X6zGdd9wgkCEieAWHXSCxEd8zxcTgh");
+
+/**
+ * Bits of the packed `Hand.flags` field (mirrors `hand_flags` in state/hand.rs)
+ */
+export const HAND_FLAGS = {
+  P1_SEED_COMMITTED: 1 << 0,
+  P2_SEED_COMMITTED: 1 << 1,
+  P1_SEED_REVEALED: 1 << 2,
+  P2_SEED_REVEALED: 1 << 3,
+  P1_CARDS_COMMITTED: 1 << 4,
+  P2_CARDS_COMMITTED: 1 << 5,
+  P1_REVEALED: 1 << 6,
+  P2_REVEALED: 1 << 7,
+  P1_FOLDED: 1 << 8,
+  P2_FOLDED: 1 << 9,
+  P1_ALL_IN: 1 << 10,
+  P2_ALL_IN: 1 << 11,
+  P1_ACTED_THIS_STREET: 1 << 12,
+  P2_ACTED_THIS_STREET: 1 << 13,
+  FLOP_REVEALED: 1 << 14,
+  TURN_REVEALED: 1 << 15,
+  RIVER_REVEALED: 1 << 16,
+  POT_CLAIMED: 1 << 17,
+  P1_RUN_TWICE_CONSENT: 1 << 18,
+  P2_RUN_TWICE_CONSENT: 1 << 19,
+  SECOND_BOARD_REVEALED: 1 << 20,
+  P1_SECOND_REVEALED: 1 << 21,
+  P2_SECOND_REVEALED: 1 << 22,
+  P1_RESULT_AGREED: 1 << 23,
+  P2_RESULT_AGREED: 1 << 24,
+  P1_MUCKED: 1 << 25,
+  P2_MUCKED: 1 << 26,
+  DEAL_VERIFIED: 1 << 27,
+  FAST_SHOWDOWN: 1 << 28,
+} as const;
+
+export function hasFlag(handAccount: { flags: number }, flag: number): boolean {
+  return (handAccount.flags & flag) !== 0;
+}
+
+export interface TestPlayer {
+  keypair: Keypair;
+  ata: PublicKey;
+}
+
+export interface TableOptions {
+  smallBlind?: anchor.BN;
+  bigBlind?: anchor.BN;
+  minBuyIn?: anchor.BN;
+  maxBuyIn?: anchor.BN;
+  actionTimeout?: anchor.BN | null;
+  timeBank?: anchor.BN | null;
+  autoContinue?: boolean;
+  blindSchedule?: BlindLevel[];
+  maxHands?: anchor.BN;
+  /** `{ allowlist: {} }` for an invite-only table */
+  access?: { public: {} } | { allowlist: {} };
+  straddleAllowed?: boolean;
+  requireBbMultiple?: boolean;
+  /** Seconds a showdown result can be challenged before payout */
+  disputeWindow?: anchor.BN;
+  /** Cap on the maximum buy-in in big blinds (0 = no cap) */
+  maxBuyInBb?: number;
+  /** Rake in basis points (0 = no rake) */
+  rakeBps?: number;
+  /** Maximum rake per pot (0 = uncapped) */
+  rakeCap?: anchor.BN;
+  /** Skip rake on hands that end before the flop (default true) */
+  rakeOnlyAfterFlop?: boolean;
+  /** Hands to play before leaving with winnings (0 = leave any time) */
+  minHandsBeforeLeave?: anchor.BN;
+  /** Seat the smaller pubkey in seat 0 before the first hand */
+  deterministicSeating?: boolean;
+  /** Draw the first hand's button instead of starting in seat 0 */
+  randomButton?: boolean;
+  /** Largest pot a hand may build (0 = uncapped) */
+  potCap?: anchor.BN;
+  /** Treat a seat silent past the action timeout after the other showed as mucked */
+  autoMuck?: boolean;
+  /** Cap game: most a seat may wager per hand, in big blinds (0 = no cap) */
+  handBetCapBb?: number;
+  /** Who opens the action on each street */
+  firstToActRule?: { standard: {} } | { buttonFirst: {} };
+  /** Forfeit the pot on a betting timeout, or check/fold for the seat */
+  timeoutPolicy?: { forfeit: {} } | { autoCheckFold: {} };
+  /** Seconds a betting action must wait after the hand's last action (0 = no throttle) */
+  minActionInterval?: anchor.BN;
+  /** Seconds a player who left must wait before rejoining (0 = no wait) */
+  rejoinCooldown?: anchor.BN;
+  /** Cards in the deck: 52, or 36 for short deck */
+  deckSize?: number;
+  /** Seconds to commit hole cards (null = the action timeout, at least 60) */
+  commitDeadline?: anchor.BN | null;
+  /** Both players pay half of each hand's rent */
+  splitHandRent?: boolean;
+  /** The button posts the big blind instead of the small blind */
+  buttonIsBigBlind?: boolean;
+  mint?: PublicKey;
+  /** Creates the table instead of the provider wallet */
+  creator?: Keypair;
+}
+
+export interface BlindLevel {
+  duration: anchor.BN;
+  smallBlind: anchor.BN;
+  bigBlind: anchor.BN;
+}
+
+export interface TestTable {
+  table: PublicKey;
+  vault: PublicKey;
+  tableId: anchor.BN;
+}
+
+export interface HoleCardSecrets {
+  cards: [number, number];
+  salts: [bigint, bigint];
+  commitments: [bigint, bigint];
+}
+
+export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+export function findGlobalConfig(program: Program<Contracts>): PublicKey {
+  return PublicKey.findProgramAddressSync([GLOBAL_SEED], program.programId)[0];
+}
+
+export function findTable(program: Program<Contracts>, tableId: anchor.BN): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [TABLE_SEED, tableId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
+
+export function findVault(program: Program<Contracts>, table: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([VAULT_SEED, table.toBuffer()], program.programId)[0];
+}
+
+export function findTableStats(program: Program<Contracts>, table: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([TABLE_STATS_SEED, table.toBuffer()], program.programId)[0];
+}
+
+export function findPlayerProfile(program: Program<Contracts>, player: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([PROFILE_SEED, player.toBuffer()], program.programId)[0];
+}
+
+/**
+ * Profile accounts for both seats, required by every instruction that can complete a hand
+ */
+export async function seatProfiles(
+  program: Program<Contracts>,
+  { table }: TestTable
+): Promise<{ playerOneProfile: PublicKey; playerTwoProfile: PublicKey }> {
+  const tableAccount = await program.account.table.fetch(table);
+  return {
+    playerOneProfile: findPlayerProfile(program, tableAccount.playerOne),
+    playerTwoProfile: findPlayerProfile(program, tableAccount.playerTwo),
+  };
+}
+
+export function findHand(program: Program<Contracts>, table: PublicKey, handNumber: anchor.BN): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [HAND_SEED, table.toBuffer(), handNumber.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  )[0];
+}
+
+/**
+ * Assert that a transaction fails with the given error name
+ */
+export async function expectError(promise: Promise<any>, errorName: string): Promise<void> {
+  try {
+    await promise;
+    assert.fail(`Expected ${errorName}`);
+  } catch (err) {
+    assert.include(err.message, errorName);
+  }
+}
+
+/**
+ * Create a player funded with SOL for fees and `mint` tokens for buy-ins
+ */
+export async function createPlayer(
+  provider: anchor.AnchorProvider,
+  mint: PublicKey,
+  amount: number = 10_000_000000
+): Promise<TestPlayer> {
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const keypair = Keypair.generate();
+
+  const transferIx = SystemProgram.transfer({
+    fromPubkey: provider.wallet.publicKey,
+    toPubkey: keypair.publicKey,
+    lamports: 0.1 * anchor.web3.LAMPORTS_PER_SOL,
+  });
+  await provider.sendAndConfirm(new anchor.web3.Transaction().add(transferIx));
+
+  const ata = await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, keypair.publicKey);
+  if (amount > 0) {
+    await mintTo(provider.connection, payer, mint, ata.address, provider.wallet.publicKey, amount);
+  }
+
+  return { keypair, ata: ata.address };
+}
+
+/**
+ * `create_table` config from the given options, with sensible defaults
+ * (10/20 blinds, 200-1000 buy-in) for the rest
+ */
+export function tableConfig(options: TableOptions = {}) {
+  return {
+    smallBlind: options.smallBlind ?? new anchor.BN(10_000000),
+    bigBlind: options.bigBlind ?? new anchor.BN(20_000000),
+    minBuyIn: options.minBuyIn ?? new anchor.BN(200_000000),
+    maxBuyIn: options.maxBuyIn ?? new anchor.BN(1000_000000),
+    actionTimeout: options.actionTimeout === undefined ? new anchor.BN(30) : options.actionTimeout,
+    timeBank: options.timeBank ?? null,
+    autoContinue: options.autoContinue ?? false,
+    blindSchedule: options.blindSchedule ?? [],
+    maxHands: options.maxHands ?? new anchor.BN(0),
+    access: options.access ?? { public: {} },
+    straddleAllowed: options.straddleAllowed ?? false,
+    requireBbMultiple: options.requireBbMultiple ?? false,
+    disputeWindow: options.disputeWindow ?? new anchor.BN(0),
+    maxBuyInBb: options.maxBuyInBb ?? 0,
+    rakeBps: options.rakeBps ?? 0,
+    rakeCap: options.rakeCap ?? new anchor.BN(0),
+    rakeOnlyAfterFlop: options.rakeOnlyAfterFlop ?? true,
+    minHandsBeforeLeave: options.minHandsBeforeLeave ?? new anchor.BN(0),
+    deterministicSeating: options.deterministicSeating ?? false,
+    randomButton: options.randomButton ?? false,
+    autoMuck: options.autoMuck ?? false,
+    potCap: options.potCap ?? new anchor.BN(0),
+    handBetCapBb: options.handBetCapBb ?? 0,
+    firstToActRule: options.firstToActRule ?? { standard: {} },
+    timeoutPolicy: options.timeoutPolicy ?? { forfeit: {} },
+    minActionInterval: options.minActionInterval ?? new anchor.BN(0),
+    rejoinCooldown: options.rejoinCooldown ?? new anchor.BN(0),
+    deckSize: options.deckSize ?? 52,
+    commitDeadline: options.commitDeadline ?? null,
+    splitHandRent: options.splitHandRent ?? false,
+    buttonIsBigBlind: options.buttonIsBigBlind ?? false,
+  };
+}
+
+/**
+ * Create a table with sensible defaults (see `tableConfig`)
+ */
+export async function createTable(
+  program: Program<Contracts>,
+  provider: anchor.AnchorProvider,
+  options: TableOptions = {}
+): Promise<TestTable> {
+  const globalConfig = findGlobalConfig(program);
+  const config = await program.account.globalConfig.fetch(globalConfig);
+  const tableId = config.tableCount;
+  const table = findTable(program, tableId);
+  const vault = findVault(program, table);
+
+  await program.methods
+    .createTable(tableConfig(options))
+    .accounts({
+      creator: options.creator?.publicKey ?? provider.wallet.publicKey,
+      globalConfig,
+      table,
+      vault,
+      mint: options.mint ?? config.usdcMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers(options.creator ? [options.creator] : [])
+    .rpc();
+
+  return { table, vault, tableId };
+}
+
+export async function joinTable(
+  program: Program<Contracts>,
+  { table, vault }: TestTable,
+  player: TestPlayer,
+  buyIn: anchor.BN = new anchor.BN(500_000000)
+): Promise<void> {
+  await program.methods
+    .joinTable(buyIn)
+    .accounts({
+      player: player.keypair.publicKey,
+      globalConfig: findGlobalConfig(program),
+      table,
+      playerTokenAccount: player.ata,
+      vault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([player.keypair])
+    .rpc();
+}
+
+export async function startHand(
+  program: Program<Contracts>,
+  { table }: TestTable,
+  player: TestPlayer,
+  coPayer: TestPlayer | null = null
+): Promise<PublicKey> {
+  const tableAccount = await program.account.table.fetch(table);
+  const hand = findHand(program, table, tableAccount.handsPlayed);
+
+  await program.methods
+    .startHand()
+    .accounts({
+      player: player.keypair.publicKey,
+      globalConfig: findGlobalConfig(program),
+      table,
+      hand,
+      coPayer: coPayer?.keypair.publicKey ?? null,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers(coPayer ? [player.keypair, coPayer.keypair] : [player.keypair])
+    .rpc();
+
+  return hand;
+}
+
+/**
+ * Run commit + reveal of shuffle seeds for both players
+ * (random seeds unless `seeds` is given, one per player)
+ */
+export async function runSeedProtocol(
+  program: Program<Contracts>,
+  { table }: TestTable,
+  hand: PublicKey,
+  players: TestPlayer[],
+  seeds: Buffer[] = players.map(() => crypto.randomBytes(32))
+): Promise<void> {
+
+  for (const [i, player] of players.entries()) {
+    const seedHash = Buffer.from(keccak256(seeds[i]), "hex");
+    await program.methods
+      .commitSeed(Array.from(seedHash))
+      .accounts({ player: player.keypair.publicKey, table, hand })
+      .signers([player.keypair])
+      .rpc();
+  }
+
+  for (const [i, player] of players.entries()) {
+    await program.methods
+      .revealSeed(Array.from(seeds[i]))
+      .accounts({ player: player.keypair.publicKey, table, hand })
+      .signers([player.keypair])
+      .rpc();
+  }
+}
+
+/**
+ * Prove the hand's deck shuffle with a DEAL proof (`deckSeed` overrides the
+ * seed the proof is generated for)
+ */
+export async function verifyDeal(
+  program: Program<Contracts>,
+  { table }: TestTable,
+  hand: PublicKey,
+  player: TestPlayer,
+  deckSeed?: Buffer
+): Promise<void> {
+  const tableAccount = await program.account.table.fetch(table);
+  const handAccount = await program.account.hand.fetch(hand);
+  const seed = deckSeed ?? Buffer.from(handAccount.deckSeed);
+
+  const proof = await generateDealProof({
+    handId: handId(BigInt(tableAccount.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+    deckSeed: seed,
+    shuffledDeck: shuffleDeck(seed),
+  });
+
+  const proofBuffer = await uploadProofToBuffer(program, proof, hand, player.keypair, ProofType.Deal);
+
+  await program.methods
+    .verifyDeal()
+    .accounts({
+      player: player.keypair.publicKey,
+      globalConfig: findGlobalConfig(program),
+      table,
+      hand,
+      proofBuffer,
+      verifierProgram: DEAL_VERIFIER,
+    })
+    .signers([player.keypair])
+    .rpc();
+}
+
+/**
+ * Commit a player's hole cards with a DECK proof uploaded through a proof buffer
+ */
+export async function commitHoleCards(
+  program: Program<Contracts>,
+  testTable: TestTable,
+  hand: PublicKey,
+  player: TestPlayer
+): Promise<HoleCardSecrets> {
+  const { table } = testTable;
+  const tableAccount = await program.account.table.fetch(table);
+  let handAccount = await program.account.hand.fetch(hand);
+
+  // The first player to commit proves the shuffle
+  if (!hasFlag(handAccount, HAND_FLAGS.DEAL_VERIFIED)) {
+    await verifyDeal(program, testTable, hand, player);
+    handAccount = await program.account.hand.fetch(hand);
+  }
+
+  const deckSeed = Buffer.from(handAccount.deckSeed);
+  const seat = tableAccount.playerOne.equals(player.keypair.publicKey) ? 0 : 1;
+
+  const cards = getHoleCards(shuffleDeck(deckSeed), seat);
+  const salts: [bigint, bigint] = [generateSalt(), generateSalt()];
+  const { proof, commitments } = await generateDeckProof({
+    handId: handId(BigInt(tableAccount.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+    deckSeed,
+    playerSeat: seat,
+    card1: cards[0],
+    card2: cards[1],
+    salt1: salts[0],
+    salt2: salts[1],
+  });
+
+  const proofBuffer = await uploadProofToBuffer(program, proof, hand, player.keypair, ProofType.Deck);
+
+  await program.methods
+    .commitHoleCards([commitmentToBytes(commitments[0]), commitmentToBytes(commitments[1])])
+    .accounts({
+      player: player.keypair.publicKey,
+      globalConfig: findGlobalConfig(program),
+      table,
+      hand,
+      proofBuffer,
+      verifierProgram: DECK_VERIFIER,
+    })
+    .signers([player.keypair])
+    .rpc();
+
+  return { cards, salts, commitments };
+}
+
+/**
+ * Start a hand and drive it through the seed and card commit phases into preflop betting
+ */
+export async function playToPreflop(
+  program: Program<Contracts>,
+  testTable: TestTable,
+  players: TestPlayer[],
+  seeds?: Buffer[]
+): Promise<{ hand: PublicKey; secrets: HoleCardSecrets[] }> {
+  const hand = await startHand(program, testTable, players[0]);
+  await runSeedProtocol(program, testTable, hand, players, seeds);
+
+  const secrets: HoleCardSecrets[] = [];
+  for (const player of players) {
+    secrets.push(await commitHoleCards(program, testTable, hand, player));
+  }
+
+  return { hand, secrets };
+}
+
+/**
+ * Return the player whose turn it is in the given hand
+ */
+export async function playerToAct(
+  program: Program<Contracts>,
+  { table }: TestTable,
+  hand: PublicKey,
+  players: TestPlayer[]
+): Promise<TestPlayer> {
+  const tableAccount = await program.account.table.fetch(table);
+  const handAccount = await program.account.hand.fetch(hand);
+  const seatKey = handAccount.actionOn === 0 ? tableAccount.playerOne : tableAccount.playerTwo;
+  return players.find((p) => p.keypair.publicKey.equals(seatKey));
+}
+
+/** Reveal instruction and board size for each postflop street */
+const STREETS = {
+  flop: { method: "revealFlop", numCards: 3, flag: HAND_FLAGS.FLOP_REVEALED },
+  turn: { method: "revealTurn", numCards: 4, flag: HAND_FLAGS.TURN_REVEALED },
+  river: { method: "revealRiver", numCards: 5, flag: HAND_FLAGS.RIVER_REVEALED },
+} as const;
+
+/**
+ * Reveal the current street's community cards so its betting can open.
+ * Does nothing preflop or once the street is already revealed.
+ */
+export async function revealStreet(
+  program: Program<Contracts>,
+  testTable: TestTable,
+  hand: PublicKey,
+  player: TestPlayer
+): Promise<void> {
+  const handAccount = await program.account.hand.fetch(hand);
+  const street = STREETS[Object.keys(handAccount.stage)[0]];
+  if (!street || hasFlag(handAccount, street.flag)) {
+    return;
+  }
+
+  const deckSeed = Buffer.from(handAccount.deckSeed);
+  const shuffledDeck = shuffleDeck(deckSeed);
+  const { flop, turn, river } = getCommunityCards(shuffledDeck);
+  const board = [...flop, turn, river];
+  const proof = await generateRevealProof({
+    handId: handId(BigInt(testTable.tableId.toString()), BigInt(handAccount.handNumber.toString()), handAccount.circuitVersion),
+    deckSeed,
+    cards: board.slice(0, street.numCards),
+    numCards: street.numCards,
+    shuffledDeck,
+  });
+  const proofBuffer = await uploadProofToBuffer(program, proof, hand, player.keypair, ProofType.Reveal);
+
+  const cards = street.numCards === 3 ? flop : board[street.numCards - 1];
+  await program.methods[street.method](cards as any)
+    .accountsPartial({
+      player: player.keypair.publicKey,
+      globalConfig: findGlobalConfig(program),
+      table: testTable.table,
+      hand,
+      proofBuffer,
+      verifierProgram: REVEAL_VERIFIER,
+    })
+    .signers([player.keypair])
+    .rpc();
+}
+
+export type BettingMethod = "check" | "call" | "fold" | "allIn";
+
+/**
+ * Take an argument-free betting action for a player, first revealing the
+ * street's cards if its betting has not opened yet
+ */
+export async function act(
+  program: Program<Contracts>,
+  testTable: TestTable,
+  hand: PublicKey,
+  player: TestPlayer,
+  method: BettingMethod
+): Promise<void> {
+  await revealStreet(program, testTable, hand, player);
+  await program.methods[method]()
+    .accountsPartial({
+      player: player.keypair.publicKey,
+      table: testTable.table,
+      hand,
+      ...(await seatProfiles(program, testTable)),
+    })
+    .signers([player.keypair])
+    .rpc();
+}
@@ -39,69 +39,60 @@ function countSuits(cards: Card[]): number[] {
   return counts;
 }
 
-/**
- * Check if hand contains a flush (5+ cards of same suit)
- */
-function hasFlush(cards: Card[]): boolean {
-  const suitCounts = countSuits(cards);
-  return suitCounts.some((count) => count >= 5);
-}
+const NO_VALUE = 13;
 
 /**
- * Sort card values (bubble sort matching circuit)
+ * Values present in a presence array from highest to lowest, skipping
+ * `skipA`/`skipB`, padded with zeros to five entries
  */
-function sortValues(values: number[]): number[] {
-  const sorted = [...values];
-  for (let i = 0; i < sorted.length - 1; i++) {
-    for (let j = 0; j < sorted.length - 1 - i; j++) {
-      if (sorted[j] > sorted[j + 1]) {
-        const temp = sorted[j];
-        sorted[j] = sorted[j + 1];
-        sorted[j + 1] = temp;
-      }
+function topValues(present: boolean[], skipA: number = NO_VALUE, skipB: number = NO_VALUE): number[] {
+  const out: number[] = [];
+  for (let v = 12; v >= 0; v--) {
+    if (present[v] && v !== skipA && v !== skipB && out.length < 5) {
+      out.push(v);
     }
   }
-  return sorted;
+  while (out.length < 5) {
+    out.push(0);
+  }
+  return out;
 }
 
 /**
- * Check if hand contains a straight (5 consecutive values)
+ * Highest value of the best straight in a presence array, or null.
+ * The wheel (A-2-3-4-5) counts as 5 high (value 3).
  */
-function hasStraight(cards: Card[]): boolean {
-  const values = cards.map((c) => c.value);
-  const sorted = sortValues(values);
-
-  // Check for normal straights
-  for (let start = 0; start < 3; start++) {
-    let consecutive = true;
-    for (let i = 0; i < 4; i++) {
-      const curr = sorted[start + i];
-      const next = sorted[start + i + 1];
-      if (next !== curr && next !== curr + 1) {
-        consecutive = false;
-      }
-    }
-    if (consecutive) {
-      const span = sorted[start + 4] - sorted[start];
-      if (span === 4) {
-        return true;
-      }
+function straightTop(present: boolean[]): number | null {
+  for (let top = 12; top >= 4; top--) {
+    if ([0, 1, 2, 3, 4].every((i) => present[top - i])) {
+      return top;
     }
   }
+  return present[12] && present[0] && present[1] && present[2] && present[3] ? 3 : null;
+}
 
-  // Check for wheel straight (A-2-3-4-5)
-  const hasAce = sorted[6] === 12;
-  const hasTwo = sorted[0] === 0;
-  const hasThree = sorted[1] === 1 || sorted[0] === 1;
-  const hasFour = sorted[2] === 2 || sorted[1] === 2 || sorted[0] === 2;
-  const hasFive = sorted[3] === 3 || sorted[2] === 3 || sorted[1] === 3 || sorted[0] === 3;
+/**
+ * Card values of a straight from its top card down (the wheel ends with the ace)
+ */
+function straightValues(top: number): number[] {
+  return [0, 1, 2, 3, 4].map((i) => (top + 13 - i) % 13);
+}
 
-  return hasAce && hasTwo && hasThree && hasFour && hasFive;
+/**
+ * Pack a hand category and its five ordered card values into a score:
+ * category << 20 | v1 << 16 | v2 << 12 | v3 << 8 | v4 << 4 | v5
+ *
+ * Matches: encode_hand_score() in zkpoker_lib
+ */
+export function encodeHandScore(category: number, values: number[]): number {
+  return values.reduce((score, value) => score * 16 + value, category);
 }
 
 /**
- * Evaluate a 7-card poker hand and return composite score
- * Returns: rank * 100 + primary_value
+ * Evaluate the best 5-card hand out of 7 cards as a fully ordered score
+ * (see encodeHandScore). Card values are listed in comparison order:
+ * groups first (higher group first), then kickers descending, so equal
+ * scores are true chops.
  *
  * This matches the circuit implementation exactly
  */
@@ -111,83 +102,56 @@ export function evaluateHandRank(cards: Card[]): number {
   }
 
   const valueCounts = countValues(cards);
-  const flush = hasFlush(cards);
-  const straight = hasStraight(cards);
-
-  let pairs = 0;
-  let trips = 0;
-  let quads = 0;
-  let pairValue = 0;
-  let pairValue2 = 0;
-  let tripValue = 0;
-  let quadValue = 0;
-  let highCard = 0;
-
-  for (let i = 0; i < 13; i++) {
-    const count = valueCounts[i];
-
-    if (count >= 1) {
-      highCard = i;
-    }
-    if (count === 2) {
-      pairValue2 = pairValue;
-      pairValue = i;
-      pairs++;
-    }
-    if (count === 3) {
-      tripValue = i;
-      trips++;
-    }
-    if (count === 4) {
-      quadValue = i;
-      quads++;
+  const suitCounts = countSuits(cards);
+  const flushSuit = suitCounts.findIndex((count) => count >= 5);
+
+  const present = valueCounts.map((count) => count > 0);
+  const flushPresent = new Array(13).fill(false);
+  for (const card of cards) {
+    if (card.suit === flushSuit) {
+      flushPresent[card.value] = true;
     }
   }
 
-  let rank = RANK_HIGH_CARD;
-  let primaryValue = highCard;
+  // Groups from the highest value down
+  const byCount = (count: number) =>
+    [...Array(13).keys()].reverse().filter((v) => valueCounts[v] === count);
+  const [quad = NO_VALUE] = byCount(4);
+  const [trip = NO_VALUE, trip2 = NO_VALUE] = byCount(3);
+  const [pair = NO_VALUE, pair2 = NO_VALUE] = byCount(2);
+
+  const straight = straightTop(present);
+  const straightFlush = flushSuit >= 0 ? straightTop(flushPresent) : null;
 
-  if (pairs > 0) {
-    rank = RANK_ONE_PAIR;
-    primaryValue = pairValue;
+  if (straightFlush !== null) {
+    const category = straightFlush === 12 ? RANK_ROYAL_FLUSH : RANK_STRAIGHT_FLUSH;
+    return encodeHandScore(category, straightValues(straightFlush));
   }
-  if (pairs > 1) {
-    rank = RANK_TWO_PAIR;
-    primaryValue = pairValue;
+  if (quad !== NO_VALUE) {
+    return encodeHandScore(RANK_FOUR_OF_KIND, [quad, quad, quad, quad, topValues(present, quad)[0]]);
   }
-  if (trips > 0) {
-    rank = RANK_THREE_OF_KIND;
-    primaryValue = tripValue;
+  if (trip !== NO_VALUE && (pair !== NO_VALUE || trip2 !== NO_VALUE)) {
+    const full = pair === NO_VALUE || (trip2 !== NO_VALUE && trip2 > pair) ? trip2 : pair;
+    return encodeHandScore(RANK_FULL_HOUSE, [trip, trip, trip, full, full]);
   }
-  if (straight) {
-    rank = RANK_STRAIGHT;
-    primaryValue = highCard;
+  if (flushSuit >= 0) {
+    return encodeHandScore(RANK_FLUSH, topValues(flushPresent));
   }
-  if (flush) {
-    rank = RANK_FLUSH;
-    primaryValue = highCard;
+  if (straight !== null) {
+    return encodeHandScore(RANK_STRAIGHT, straightValues(straight));
   }
-  if (trips > 0 && pairs > 0) {
-    rank = RANK_FULL_HOUSE;
-    primaryValue = tripValue;
+  if (trip !== NO_VALUE) {
+    const kickers = topValues(present, trip);
+    return encodeHandScore(RANK_THREE_OF_KIND, [trip, trip, trip, kickers[0], kickers[1]]);
   }
-  if (quads > 0) {
-    rank = RANK_FOUR_OF_KIND;
-    primaryValue = quadValue;
+  if (pair2 !== NO_VALUE) {
+    return encodeHandScore(RANK_TWO_PAIR, [pair, pair, pair2, pair2, topValues(present, pair, pair2)[0]]);
   }
-  if (straight && flush) {
-    const hasTen = cards.some((c) => c.value === 8); // 10 is index 8
-    const hasAce = cards.some((c) => c.value === 12);
-    if (hasTen && hasAce) {
-      rank = RANK_ROYAL_FLUSH;
-      primaryValue = 12;
-    } else {
-      rank = RANK_STRAIGHT_FLUSH;
-      primaryValue = highCard;
-    }
+  if (pair !== NO_VALUE) {
+    const kickers = topValues(present, pair);
+    return encodeHandScore(RANK_ONE_PAIR, [pair, pair, kickers[0], kickers[1], kickers[2]]);
   }
-
-  return rank * 100 + primaryValue;
+  return encodeHandScore(RANK_HIGH_CARD, topValues(present));
 }
 
 /**
@@ -215,7 +179,7 @@ export function createSevenCardHand(
  * Get hand rank name
  */
 export function getHandRankName(compositeScore: number): string {
-  const rank = Math.floor(compositeScore / 100);
+  const rank = Math.floor(compositeScore / (1 << 20));
   const names = [
     "High Card",
     "One Pair",
@@ -7,6 +7,7 @@ import { exec } from "child_process";
 import { promisify } from "util";
 import * as fs from "fs";
 import * as path from "path";
+import { shuffleDeck, commitDeck } from "./deck";
 
 const execAsync = promisify(exec);
 
@@ -104,19 +105,33 @@ async function generateProofWithSunspot(
   return { proof, publicWitness };
 }
 
+/**
+ * Hand binding public input (`circuit_version * 2^128 + table_id * 2^64 +
+ * hand_number`), checked on-chain as the first public input of every
+ * DECK/REVEAL/SHOWDOWN proof. Pass the hand's `circuitVersion`.
+ */
+export function handId(tableId: bigint, handNumber: bigint, circuitVersion = 0): bigint {
+  return (BigInt(circuitVersion) << 128n) + (tableId << 64n) + handNumber;
+}
+
 /**
  * Generate DECK circuit proof
  * Returns proof and commitments (from circuit public outputs)
  */
 export async function generateDeckProof(params: {
+  handId: bigint;
   deckSeed: Buffer;
   playerSeat: number;
   card1: number;
   card2: number;
   salt1: bigint;
   salt2: bigint;
+  /** Deck positions to prove the cards at (defaults to the seat's own) */
+  positions?: [number, number];
 }): Promise<{ proof: Buffer; commitments: [bigint, bigint] }> {
-  const { deckSeed, playerSeat, card1, card2, salt1, salt2 } = params;
+  const { handId, deckSeed, playerSeat, card1, card2, salt1, salt2 } = params;
+  const positions = params.positions ?? [playerSeat * 2, playerSeat * 2 + 1];
+  const shuffledDeck = shuffleDeck(deckSeed);
 
   try {
     const inputs = {
@@ -124,8 +139,10 @@ export async function generateDeckProof(params: {
       card2,
       salt1: salt1.toString(),
       salt2: salt2.toString(),
-      _deck_seed: Array.from(deckSeed),
-      _player_seat: playerSeat,
+      shuffled_deck: shuffledDeck,
+      _hand_id: handId.toString(),
+      deck_root: "0x" + commitDeck(shuffledDeck).toString(16).padStart(64, "0"),
+      positions,
     };
 
     const { witnessPath, publicOutputs } = await generateWitness("deck", inputs);
@@ -148,16 +165,45 @@ export async function generateDeckProof(params: {
   }
 }
 
+/**
+ * Generate DEAL circuit proof that the shuffled deck derives from the deck seed
+ */
+export async function generateDealProof(params: {
+  handId: bigint;
+  deckSeed: Buffer;
+  shuffledDeck: number[];
+}): Promise<Buffer> {
+  const { handId, deckSeed, shuffledDeck } = params;
+
+  try {
+    const inputs = {
+      shuffled_deck: shuffledDeck,
+      _hand_id: handId.toString(),
+      deck_seed: Array.from(deckSeed),
+    };
+
+    const { witnessPath } = await generateWitness("deal", inputs);
+    const { proof, publicWitness } = await generateProofWithSunspot("deal", witnessPath);
+    // Sunspot verifier expects proof + public witness concatenated
+    return Buffer.concat([proof, publicWitness]);
+  } catch (error: any) {
+    throw new Error(`DEAL proof generation failed: ${error.message}`);
+  }
+}
+
 /**
  * Generate REVEAL circuit proof
  */
 export async function generateRevealProof(params: {
+  handId: bigint;
   deckSeed: Buffer;
   cards: number[];
   numCards: number;
   shuffledDeck: number[];
+  /** Start of the cards relative to the first community position (5 for a second board) */
+  offset?: number;
 }): Promise<Buffer> {
-  const { deckSeed, cards, numCards, shuffledDeck } = params;
+  const { handId, deckSeed, cards, numCards, shuffledDeck, offset = 0 } = params;
 
   try {
     // Pad cards array to 5 elements
@@ -168,9 +214,11 @@ export async function generateRevealProof(params: {
 
     const inputs = {
       shuffled_deck: shuffledDeck,
+      _hand_id: handId.toString(),
       _deck_seed: Array.from(deckSeed),
       cards: paddedCards,
       num_cards: numCards,
+      offset,
     };
 
     const { witnessPath } = await generateWitness("reveal", inputs);
@@ -187,6 +235,7 @@ export async function generateRevealProof(params: {
  * Returns proof and hand rank (circuit public output)
  */
 export async function generateShowdownProof(params: {
+  handId: bigint;
   commitment1: bigint;
   commitment2: bigint;
   communityCards: [number, number, number, number, number];
@@ -195,10 +244,11 @@ export async function generateShowdownProof(params: {
   salt1: bigint;
   salt2: bigint;
 }): Promise<{ proof: Buffer; handRank: bigint }> {
-  const { commitment1, commitment2, communityCards, holeCard1, holeCard2, salt1, salt2 } = params;
+  const { handId, commitment1, commitment2, communityCards, holeCard1, holeCard2, salt1, salt2 } = params;
 
   try {
     const inputs = {
+      _hand_id: handId.toString(),
       commitment1: "0x" + commitment1.toString(16).padStart(64, "0"),
       commitment2: "0x" + commitment2.toString(16).padStart(64, "0"),
       community_cards: Array.from(communityCards),
@@ -252,6 +302,7 @@ export const ProofType = {
   Deck: 0,
   Reveal: 1,
   Showdown: 2,
+  Deal: 3,
 } as const;
 export type ProofType = (typeof ProofType)[keyof typeof ProofType];
 
@@ -0,0 +1,266 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Contracts } from "../target/types/contracts";
+import { PublicKey, SYSVAR_CLOCK_PUBKEY } from "@solana/web3.js";
+import { assert } from "chai";
+import {
+  TestPlayer,
+  TestTable,
+  createPlayer,
+  createTable,
+  joinTable,
+  playToPreflop,
+  playerToAct,
+  findGlobalConfig,
+  act,
+} from "./utils/game";
+
+describe("Game View", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Contracts as Program<Contracts>;
+
+  let players: TestPlayer[];
+  let testTable: TestTable;
+  let hand: PublicKey;
+
+  // Clock sysvar: slot, epoch_start_timestamp, epoch, leader_schedule_epoch, unix_timestamp
+  const clockNow = async () =>
+    Number((await provider.connection.getAccountInfo(SYSVAR_CLOCK_PUBKEY)).data.readBigInt64LE(32));
+
+  before("Create a full table", async () => {
+    const config = await program.account.globalConfig.fetch(findGlobalConfig(program));
+    players = [
+      await createPlayer(provider, config.usdcMint),
+      await createPlayer(provider, config.usdcMint),
+    ];
+
+    testTable = await createTable(program, provider);
+    for (const player of players) {
+      await joinTable(program, testTable, player);
+    }
+  });
+
+  it("Returns the table without a hand between hands", async () => {
+    console.log("🧪 Testing: view_game_state between hands");
+
+    const view = await program.methods
+      .viewGameState()
+      .accountsPartial({ table: testTable.table, hand: null })
+      .view();
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    assert.isTrue(view.tableId.eq(testTable.tableId));
+    assert.deepEqual(view.status, tableAccount.status);
+    assert.deepEqual(
+      view.chips.map((c: anchor.BN) => c.toNumber()),
+      [tableAccount.playerOneChips.toNumber(), tableAccount.playerTwoChips.toNumber()]
+    );
+    assert.isNull(view.hand);
+
+    console.log("   ✅ Table fields match, no hand");
+  });
+
+  it("Matches the accounts after several betting actions", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: view_game_state mid-hand");
+
+    ({ hand } = await playToPreflop(program, testTable, players));
+    await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "call");
+    await act(program, testTable, hand, await playerToAct(program, testTable, hand, players), "check");
+
+    const view = await program.methods
+      .viewGameState()
+      .accountsPartial({ table: testTable.table, hand })
+      .view();
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+
+    assert.deepEqual(view.status, { playing: {} });
+    assert.equal(view.bigBlind.toNumber(), tableAccount.bigBlind.toNumber());
+    assert.isTrue(view.players[0].equals(tableAccount.playerOne));
+    assert.isTrue(view.players[1].equals(tableAccount.playerTwo));
+    assert.equal(view.button, tableAccount.button);
+
+    assert.isTrue(view.hand.handNumber.eq(handAccount.handNumber));
+    assert.deepEqual(view.hand.stage, handAccount.stage);
+    assert.equal(view.hand.pot.toNumber(), handAccount.pot.toNumber());
+    assert.equal(view.hand.currentBet.toNumber(), handAccount.currentBet.toNumber());
+    assert.equal(view.hand.actionOn, handAccount.actionOn);
+    assert.deepEqual(Buffer.from(view.hand.communityCards), Buffer.alloc(0));
+    assert.deepEqual(view.hand.seedCommits[0], handAccount.seedCommitOne);
+    assert.deepEqual(view.hand.holeCommits[1], handAccount.p2HoleCommits);
+
+    console.log("   ✅ View matches table and hand accounts");
+  });
+
+  it("Counts down to the seat to act's timeout", async () => {
+    console.log("🧪 Testing: time_until_timeout on the flop");
+
+    const before = await clockNow();
+    const remaining = await program.methods
+      .timeUntilTimeout()
+      .accountsPartial({ table: testTable.table, hand })
+      .view();
+    const after = await clockNow();
+
+    const tableAccount = await program.account.table.fetch(testTable.table);
+    const handAccount = await program.account.hand.fetch(hand);
+    const timeout =
+      tableAccount.actionTimeout.toNumber() +
+      tableAccount.timeBankRemaining[handAccount.actionOn].toNumber();
+    const expected = (now: number) => Math.max(0, timeout - (now - handAccount.lastActionAt.toNumber()));
+
+    assert.isAtMost(remaining.toNumber(), expected(before));
+    assert.isAtLeast(remaining.toNumber(), expected(after));
+    assert.isAbove(remaining.toNumber(), 0);
+
+    console.log(`   ✅ ${remaining.toNumber()}s left, matching timeout - elapsed`);
+  });
+
+  it("Splits an over-bet into matched pot and refund", async function () {
+    this.timeout(120000);
+    console.log("🧪 Testing: get_pot_breakdown after a shove over a short stack");
+
+    const overBetTable = await createTable(program, provider);
+    const [deep, short] = players;
+    await joinTable(program, overBetTable, deep, new anchor.BN(500_000000));
+    await joinTable(program, overBetTable, short, new anchor.BN(300_000000));
+    const { hand: overBetHand } = await playToPreflop(program, overBetTable, players);
+
+    // Whoever opens, the deep stack ends up shoving into the short stack
+    if ((await playerToAct(program, overBetTable, overBetHand, players)) === short) {
+      await act(program, overBetTable, overBetHand, short, "call");
+    }
+    await act(program, overBetTable, overBetHand, deep, "allIn");
+
+    const breakdown = await program.methods
+      .getPotBreakdown()
+      .accountsPartial({ table: overBetTable.table, hand: overBetHand })
+      .view();
+
+    const tableAccount = await program.account.table.fetch(overBetTable.table);
+    const handAccount = await program.account.hand.fetch(overBetHand);
+    const deepSeat = tableAccount.playerOne.equals(deep.keypair.publicKey) ? 0 : 1;
+    const shortSeat = 1 - deepSeat;
+    const bets = handAccount.betThisStreet.map((b: anchor.BN) => b.toNumber());
+    const shortChips = [tableAccount.playerOneChips, tableAccount.playerTwoChips][shortSeat].toNumber();
+
+    const uncalled = bets[deepSeat] - bets[shortSeat];
+    assert.equal(breakdown.uncalled.toNumber(), uncalled);
+    assert.equal(breakdown.uncalledSeat, deepSeat);
+    assert.equal(breakdown.refund.toNumber(), uncalled - shortChips);
+    assert.equal(breakdown.refund.toNumber(), 200_000000);
+    assert.equal(breakdown.pot.toNumber(), handAccount.pot.toNumber());
+    assert.equal(breakdown.matchedPot.toNumber(), handAccount.pot.toNumber() - uncalled);
+    assert.deepEqual(
+      breakdown.totalBets.map((b: anchor.BN) => b.toNumber()),
+      handAccount.totalBet.map((b: anchor.BN) => b.toNumber())
+    );
+
+    console.log(`   ✅ ${uncalled / 1e6} uncalled, ${breakdown.refund.toNumber() / 1e6} returned at street close`);
+  });
+
+  describe("Spectator snapshot", () => {
+    const PUBLIC_FIELDS = [
+      "tableId",
+      "status",
+      "smallBlind",
+      "bigBlind",
+      "players",
+      "chips",
+      "button",
+      "handNumber",
+      "stage",
+      "pot",
+      "betsThisStreet",
+      "actionOn",
+      "communityCards",
+      "winner",
+    ];
+
+    let spectated: TestTable;
+    let spectatedHand: PublicKey | null = null;
+
+    const snapshot = () =>
+      program.methods
+        .spectatorSnapshot()
+        .accountsPartial({ table: spectated.table, hand: spectatedHand })
+        .view();
+
+    /** Exactly the public fields, none of the hand's secrets or commitments */
+    const assertPublicOnly = (snap: any) => {
+      assert.sameMembers(Object.keys(snap), PUBLIC_FIELDS);
+      for (const secret of ["seedCommits", "holeCommits", "deckSeed", "deckRoot", "p1HandRank", "p2HandRank"]) {
+        assert.notProperty(snap, secret);
+      }
+    };
+
+    before("Seat both players at a new table", async () => {
+      spectated = await createTable(program, provider);
+      for (const player of players) {
+        await joinTable(program, spectated, player);
+      }
+    });
+
+    it("Shows stacks and blinds between hands", async () => {
+      console.log("🧪 Testing: spectator_snapshot without a hand");
+
+      const snap = await snapshot();
+      assertPublicOnly(snap);
+
+      const tableAccount = await program.account.table.fetch(spectated.table);
+      assert.equal(snap.bigBlind.toNumber(), tableAccount.bigBlind.toNumber());
+      assert.deepEqual(
+        snap.chips.map((c: anchor.BN) => c.toNumber()),
+        [tableAccount.playerOneChips.toNumber(), tableAccount.playerTwoChips.toNumber()]
+      );
+      assert.isNull(snap.handNumber);
+      assert.isNull(snap.stage);
+      assert.isNull(snap.actionOn);
+      assert.equal(snap.pot.toNumber(), 0);
+
+      console.log("   ✅ Table fields only, no hand");
+    });
+
+    it("Shows the pot and seat to act preflop", async function () {
+      this.timeout(120000);
+      console.log("🧪 Testing: spectator_snapshot preflop");
+
+      ({ hand: spectatedHand } = await playToPreflop(program, spectated, players));
+
+      const snap = await snapshot();
+      assertPublicOnly(snap);
+
+      const handAccount = await program.account.hand.fetch(spectatedHand);
+      assert.deepEqual(snap.stage, { preflop: {} });
+      assert.equal(snap.pot.toNumber(), handAccount.pot.toNumber());
+      assert.equal(snap.actionOn, handAccount.actionOn);
+      assert.deepEqual(Buffer.from(snap.communityCards), Buffer.alloc(0));
+
+      console.log("   ✅ Pot and action shown, commitments withheld");
+    });
+
+    it("Shows the flop once it is revealed", async function () {
+      this.timeout(300000);
+      console.log("🧪 Testing: spectator_snapshot on the flop");
+
+      await act(program, spectated, spectatedHand, await playerToAct(program, spectated, spectatedHand, players), "call");
+      await act(program, spectated, spectatedHand, await playerToAct(program, spectated, spectatedHand, players), "check");
+      // `act` reveals the flop before the first flop decision
+      await act(program, spectated, spectatedHand, await playerToAct(program, spectated, spectatedHand, players), "check");
+
+      const snap = await snapshot();
+      assertPublicOnly(snap);
+
+      const handAccount = await program.account.hand.fetch(spectatedHand);
+      assert.deepEqual(snap.stage, { flop: {} });
+      assert.deepEqual(Array.from(snap.communityCards), Array.from(handAccount.flop));
+      assert.equal(snap.actionOn, handAccount.actionOn);
+
+      console.log(`   ✅ Flop ${Array.from(snap.communityCards).join(", ")} visible`);
+    });
+  });
+});